@@ -184,7 +184,7 @@ fn bench_history_manager(c: &mut Criterion) {
         }
 
         b.iter(|| {
-            history.save_state(elements.get_elements(), None);
+            history.save_state(elements.get_elements());
         });
     });
 
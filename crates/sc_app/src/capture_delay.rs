@@ -0,0 +1,87 @@
+/// Length of one countdown step; the on-screen number changes once per step.
+pub const TICK_MS: u32 = 1000;
+
+/// Delayed-capture lifecycle phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Phase {
+    /// No countdown in progress.
+    #[default]
+    Idle,
+    /// Waiting for the capture to fire.
+    CountingDown {
+        /// Time left until the capture fires.
+        remaining_ms: u32,
+        /// Interval the host timer is currently armed with.
+        interval_ms: u32,
+    },
+}
+
+/// Countdown before a delayed capture.
+///
+/// The first step absorbs the sub-second remainder of the delay so that every later tick
+/// lands on a whole second and the displayed number counts down evenly.
+#[derive(Debug, Default)]
+pub struct Model {
+    phase: Phase,
+}
+
+impl Model {
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn is_counting_down(&self) -> bool {
+        matches!(self.phase, Phase::CountingDown { .. })
+    }
+
+    /// Whole seconds left, rounded up; `None` when idle.
+    pub fn seconds_left(&self) -> Option<u32> {
+        match self.phase {
+            Phase::Idle => None,
+            Phase::CountingDown { remaining_ms, .. } => Some(remaining_ms.div_ceil(TICK_MS)),
+        }
+    }
+
+    /// Start counting down from `delay_ms` (must be non-zero). Returns the first timer interval.
+    pub fn start(&mut self, delay_ms: u32) -> u32 {
+        let interval_ms = match delay_ms % TICK_MS {
+            0 => TICK_MS,
+            remainder => remainder,
+        };
+        self.phase = Phase::CountingDown {
+            remaining_ms: delay_ms,
+            interval_ms,
+        };
+        interval_ms
+    }
+
+    /// Advance by one timer interval.
+    ///
+    /// Returns `None` if idle or if the countdown just finished (the model is then idle again),
+    /// otherwise `Some(interval_changed)`.
+    pub fn tick(&mut self) -> Option<bool> {
+        let Phase::CountingDown {
+            remaining_ms,
+            interval_ms,
+        } = self.phase
+        else {
+            return None;
+        };
+
+        let remaining_ms = remaining_ms.saturating_sub(interval_ms);
+        if remaining_ms == 0 {
+            self.phase = Phase::Idle;
+            return None;
+        }
+
+        self.phase = Phase::CountingDown {
+            remaining_ms,
+            interval_ms: TICK_MS,
+        };
+        Some(interval_ms != TICK_MS)
+    }
+
+    pub fn cancel(&mut self) {
+        self.phase = Phase::Idle;
+    }
+}
@@ -1,5 +1,6 @@
 use sc_drawing::DrawingTool;
 
+pub mod capture_delay;
 pub mod ocr;
 pub mod selection;
 
@@ -29,6 +30,15 @@ pub enum Action {
     PinSelection,
     /// Cancel the current flow (e.g. ESC).
     Cancel,
+    /// Capture after a countdown of `delay_ms`; zero captures immediately.
+    /// Requesting again while counting down cancels the countdown.
+    StartCaptureCountdown {
+        delay_ms: u32,
+    },
+    /// The countdown timer fired.
+    CaptureCountdownTick,
+    /// Abort a pending delayed capture.
+    CancelCaptureCountdown,
 }
 
 #[cfg(test)]
@@ -114,6 +124,163 @@ mod tests {
             ]
         );
     }
+
+    fn confirm_drag_selection(m: &mut super::AppModel) -> Vec<super::Effect> {
+        use super::selection::Action::*;
+
+        let _ = m.reduce(super::Action::Selection(MouseDown { x: 0, y: 0 }));
+        let _ = m.reduce(super::Action::Selection(MouseMove { x: 200, y: 100 }));
+        m.reduce(super::Action::Selection(MouseUp { x: 200, y: 100 }))
+    }
+
+    #[test]
+    fn auto_copy_off_leaves_effects_unchanged() {
+        let mut m = super::AppModel::new();
+        assert!(!m.auto_copy());
+
+        let eff = confirm_drag_selection(&mut m);
+        assert!(!eff.contains(&super::Effect::CopySelectionToClipboard));
+        assert_eq!(
+            m.reduce(super::Action::SaveSelectionToFile),
+            vec![super::Effect::SaveSelectionToFile]
+        );
+        assert_eq!(
+            m.reduce(super::Action::PinSelection),
+            vec![super::Effect::PinSelection]
+        );
+    }
+
+    #[test]
+    fn auto_copy_copies_on_confirm() {
+        let mut m = super::AppModel::new();
+        m.set_auto_copy(true);
+
+        let selection = super::selection::RectI32 {
+            left: 0,
+            top: 0,
+            right: 200,
+            bottom: 100,
+        };
+        assert_eq!(
+            confirm_drag_selection(&mut m),
+            vec![
+                super::Effect::Selection(super::selection::Effect::ShowToolbar { selection }),
+                super::Effect::CopySelectionToClipboard,
+            ]
+        );
+
+        // Moving the confirmed selection only repositions the toolbar; nothing is copied.
+        use super::selection::Action::*;
+        let _ = m.reduce(super::Action::Selection(BeginEditDrag {
+            drag_mode: sc_drawing::DragMode::Moving,
+            x: 10,
+            y: 10,
+        }));
+        let eff = m.reduce(super::Action::Selection(EditDragMove { x: 20, y: 20 }));
+        assert!(!eff.contains(&super::Effect::CopySelectionToClipboard));
+        let eff = m.reduce(super::Action::Selection(EndEditDrag));
+        assert!(!eff.contains(&super::Effect::CopySelectionToClipboard));
+    }
+
+    #[test]
+    fn auto_copy_copies_before_save_and_pin() {
+        let mut m = super::AppModel::new();
+        m.set_auto_copy(true);
+
+        assert_eq!(
+            m.reduce(super::Action::SaveSelectionToFile),
+            vec![
+                super::Effect::CopySelectionToClipboard,
+                super::Effect::SaveSelectionToFile,
+            ]
+        );
+        assert_eq!(
+            m.reduce(super::Action::PinSelection),
+            vec![
+                super::Effect::CopySelectionToClipboard,
+                super::Effect::PinSelection,
+            ]
+        );
+        // Saving to the clipboard already copies.
+        assert_eq!(
+            m.reduce(super::Action::SaveSelectionToClipboard),
+            vec![super::Effect::SaveSelectionToClipboard]
+        );
+    }
+
+    #[test]
+    fn zero_delay_fires_immediately() {
+        let mut m = super::AppModel::new();
+        let eff = m.reduce(super::Action::StartCaptureCountdown { delay_ms: 0 });
+        assert_eq!(eff, vec![super::Effect::FireCapture]);
+        assert!(!m.capture_delay().is_counting_down());
+    }
+
+    #[test]
+    fn countdown_aligns_ticks_to_whole_seconds_then_fires() {
+        use super::Effect::*;
+
+        let mut m = super::AppModel::new();
+        let eff = m.reduce(super::Action::StartCaptureCountdown { delay_ms: 2500 });
+        assert_eq!(
+            eff,
+            vec![
+                StartCaptureCountdownTimer { interval_ms: 500 },
+                ShowCaptureCountdown { seconds: 3 },
+            ]
+        );
+
+        let eff = m.reduce(super::Action::CaptureCountdownTick);
+        assert_eq!(
+            eff,
+            vec![
+                StartCaptureCountdownTimer { interval_ms: 1000 },
+                ShowCaptureCountdown { seconds: 2 },
+            ]
+        );
+        assert_eq!(
+            m.capture_delay().phase(),
+            super::capture_delay::Phase::CountingDown {
+                remaining_ms: 2000,
+                interval_ms: 1000,
+            }
+        );
+
+        let eff = m.reduce(super::Action::CaptureCountdownTick);
+        assert_eq!(eff, vec![ShowCaptureCountdown { seconds: 1 }]);
+
+        let eff = m.reduce(super::Action::CaptureCountdownTick);
+        assert_eq!(
+            eff,
+            vec![StopCaptureCountdownTimer, HideCaptureCountdown, FireCapture]
+        );
+        assert_eq!(m.capture_delay().phase(), super::capture_delay::Phase::Idle);
+
+        // A late tick after firing only stops the timer.
+        assert_eq!(
+            m.reduce(super::Action::CaptureCountdownTick),
+            vec![StopCaptureCountdownTimer, HideCaptureCountdown]
+        );
+    }
+
+    #[test]
+    fn cancel_and_repeat_request_stop_the_countdown() {
+        use super::Effect::*;
+
+        let mut m = super::AppModel::new();
+        assert!(m.reduce(super::Action::CancelCaptureCountdown).is_empty());
+
+        let _ = m.reduce(super::Action::StartCaptureCountdown { delay_ms: 3000 });
+        assert_eq!(m.capture_delay().seconds_left(), Some(3));
+        let eff = m.reduce(super::Action::CancelCaptureCountdown);
+        assert_eq!(eff, vec![StopCaptureCountdownTimer, HideCaptureCountdown]);
+        assert_eq!(m.capture_delay().seconds_left(), None);
+
+        let _ = m.reduce(super::Action::StartCaptureCountdown { delay_ms: 1000 });
+        let eff = m.reduce(super::Action::StartCaptureCountdown { delay_ms: 1000 });
+        assert_eq!(eff, vec![StopCaptureCountdownTimer, HideCaptureCountdown]);
+        assert!(!m.capture_delay().is_counting_down());
+    }
 }
 
 /// Top-level application effects.
@@ -126,6 +293,8 @@ pub enum Effect {
     SaveSelectionToFile,
     /// Save current selection to clipboard.
     SaveSelectionToClipboard,
+    /// Copy the current selection to the clipboard without leaving the capture (auto-copy).
+    CopySelectionToClipboard,
     /// Undo the last drawing action.
     Undo,
     /// Extract text (OCR) from the current selection.
@@ -146,6 +315,20 @@ pub enum Effect {
     ResetToInitialState,
     /// Hide the host window.
     HideWindow,
+    /// (Re)arm the countdown timer with the given interval.
+    StartCaptureCountdownTimer {
+        interval_ms: u32,
+    },
+    /// Stop the countdown timer.
+    StopCaptureCountdownTimer,
+    /// Show or update the on-screen countdown.
+    ShowCaptureCountdown {
+        seconds: u32,
+    },
+    /// Remove the on-screen countdown.
+    HideCaptureCountdown,
+    /// The countdown elapsed: take the screenshot now.
+    FireCapture,
 }
 
 /// Core app model.
@@ -155,6 +338,9 @@ pub struct AppModel {
     selection: selection::Model,
     drawing_tool: DrawingTool,
     ocr: ocr::Model,
+    capture_delay: capture_delay::Model,
+    /// Copy the selection to the clipboard on confirm, save and pin.
+    auto_copy: bool,
 }
 
 impl AppModel {
@@ -177,21 +363,44 @@ impl AppModel {
         &self.ocr
     }
 
+    pub fn auto_copy(&self) -> bool {
+        self.auto_copy
+    }
+
+    pub fn set_auto_copy(&mut self, enabled: bool) {
+        self.auto_copy = enabled;
+    }
+
+    pub fn capture_delay(&self) -> &capture_delay::Model {
+        &self.capture_delay
+    }
+
     pub fn reduce(&mut self, action: Action) -> Vec<Effect> {
         match action {
-            Action::Selection(a) => self
-                .selection
-                .reduce(a)
-                .into_iter()
-                .map(Effect::Selection)
-                .collect(),
+            Action::Selection(a) => {
+                let mut effects: Vec<Effect> = self
+                    .selection
+                    .reduce(a)
+                    .into_iter()
+                    .map(Effect::Selection)
+                    .collect();
+
+                // The toolbar is only shown when a selection has just been confirmed.
+                let confirmed = effects
+                    .iter()
+                    .any(|e| matches!(e, Effect::Selection(selection::Effect::ShowToolbar { .. })));
+                if confirmed && self.auto_copy {
+                    effects.push(Effect::CopySelectionToClipboard);
+                }
+                effects
+            }
 
             Action::SelectDrawingTool(tool) => {
                 self.drawing_tool = tool;
                 vec![Effect::SelectDrawingTool(tool)]
             }
 
-            Action::SaveSelectionToFile => vec![Effect::SaveSelectionToFile],
+            Action::SaveSelectionToFile => self.with_auto_copy(Effect::SaveSelectionToFile),
 
             Action::SaveSelectionToClipboard => vec![Effect::SaveSelectionToClipboard],
 
@@ -240,7 +449,7 @@ impl AppModel {
                 ]
             }
 
-            Action::PinSelection => vec![Effect::PinSelection],
+            Action::PinSelection => self.with_auto_copy(Effect::PinSelection),
 
             Action::Cancel => {
                 // Keep selection core state consistent with the host reset.
@@ -248,6 +457,78 @@ impl AppModel {
 
                 vec![Effect::ResetToInitialState, Effect::HideWindow]
             }
+
+            Action::StartCaptureCountdown { delay_ms } => {
+                if self.capture_delay.is_counting_down() {
+                    return self.cancel_capture_countdown();
+                }
+                if delay_ms == 0 {
+                    return vec![Effect::FireCapture];
+                }
+
+                let interval_ms = self.capture_delay.start(delay_ms);
+                vec![
+                    Effect::StartCaptureCountdownTimer { interval_ms },
+                    Effect::ShowCaptureCountdown {
+                        seconds: delay_ms.div_ceil(capture_delay::TICK_MS),
+                    },
+                ]
+            }
+
+            Action::CaptureCountdownTick => {
+                // A tick can still arrive after the countdown was cancelled or the model was
+                // reset; make sure the timer does not keep running.
+                if !self.capture_delay.is_counting_down() {
+                    return vec![
+                        Effect::StopCaptureCountdownTimer,
+                        Effect::HideCaptureCountdown,
+                    ];
+                }
+
+                match self.capture_delay.tick() {
+                    None => vec![
+                        Effect::StopCaptureCountdownTimer,
+                        Effect::HideCaptureCountdown,
+                        Effect::FireCapture,
+                    ],
+                    Some(interval_changed) => {
+                        let mut effects = Vec::new();
+                        if interval_changed {
+                            effects.push(Effect::StartCaptureCountdownTimer {
+                                interval_ms: capture_delay::TICK_MS,
+                            });
+                        }
+                        if let Some(seconds) = self.capture_delay.seconds_left() {
+                            effects.push(Effect::ShowCaptureCountdown { seconds });
+                        }
+                        effects
+                    }
+                }
+            }
+
+            Action::CancelCaptureCountdown => self.cancel_capture_countdown(),
         }
     }
+
+    /// `effect`, preceded by a clipboard copy when auto-copy is on. The copy goes first because
+    /// saving and pinning end the capture.
+    fn with_auto_copy(&self, effect: Effect) -> Vec<Effect> {
+        if self.auto_copy {
+            vec![Effect::CopySelectionToClipboard, effect]
+        } else {
+            vec![effect]
+        }
+    }
+
+    fn cancel_capture_countdown(&mut self) -> Vec<Effect> {
+        if !self.capture_delay.is_counting_down() {
+            return Vec::new();
+        }
+
+        self.capture_delay.cancel();
+        vec![
+            Effect::StopCaptureCountdownTimer,
+            Effect::HideCaptureCountdown,
+        ]
+    }
 }
@@ -0,0 +1,495 @@
+//! Arrow geometry: head and tail markers and curved shafts.
+//!
+//! An arrow runs from `points[0]` to `points[1]`. A third point bends it: the shaft becomes a
+//! quadratic curve that passes through that point halfway along, which is where the midpoint
+//! handle sits. The screen renderer and the exporters all draw the [`ArrowShape`] built here.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::element::{DrawingElement, Point, defaults};
+
+/// Segments used to approximate a curved shaft for hit testing and bounds.
+const CURVE_SEGMENTS: usize = 32;
+
+/// Radius of a dot marker, as a fraction of the head length.
+const DOT_RADIUS_SCALE: f64 = 0.35;
+
+/// Marker drawn at one end of an arrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ArrowHead {
+    #[default]
+    None,
+    /// Two strokes meeting at the tip.
+    Open,
+    /// Solid triangle.
+    Triangle,
+    /// Solid dot centred on the end.
+    Dot,
+    /// Short stroke across the end.
+    Bar,
+}
+
+impl ArrowHead {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim() {
+            "none" => Some(Self::None),
+            "open" => Some(Self::Open),
+            "triangle" => Some(Self::Triangle),
+            "dot" => Some(Self::Dot),
+            "bar" => Some(Self::Bar),
+            _ => None,
+        }
+    }
+
+    /// How far the shaft stops short of the end, so that it does not poke out of the marker.
+    fn inset(self, length: f64) -> f64 {
+        match self {
+            Self::Triangle => length * defaults::ARROW_HEAD_ANGLE.cos(),
+            _ => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for ArrowHead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Open => "open",
+            Self::Triangle => "triangle",
+            Self::Dot => "dot",
+            Self::Bar => "bar",
+        })
+    }
+}
+
+/// Markers at both ends of an arrow. The default is the classic open head with a bare tail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArrowStyle {
+    /// Marker at the end the arrow points to.
+    pub head: ArrowHead,
+    /// Marker at the end it starts from.
+    pub tail: ArrowHead,
+}
+
+impl Default for ArrowStyle {
+    fn default() -> Self {
+        Self {
+            head: ArrowHead::Open,
+            tail: ArrowHead::None,
+        }
+    }
+}
+
+impl ArrowStyle {
+    /// Build a style from its text forms; ends that do not parse keep their default.
+    pub fn parse(head: &str, tail: &str) -> Self {
+        let default = Self::default();
+        Self {
+            head: ArrowHead::parse(head).unwrap_or(default.head),
+            tail: ArrowHead::parse(tail).unwrap_or(default.tail),
+        }
+    }
+
+    /// Both ends carry a marker.
+    pub fn is_double_headed(&self) -> bool {
+        self.head != ArrowHead::None && self.tail != ArrowHead::None
+    }
+}
+
+/// Length of a head on a shaft `thickness` wide. Heads grow with the stroke so that thick
+/// arrows do not end in a stub.
+pub fn head_length(thickness: f32) -> f64 {
+    (thickness as f64 * defaults::ARROW_HEAD_SCALE).max(defaults::ARROW_HEAD_MIN_LENGTH)
+}
+
+/// Centre line of an arrow shaft: a straight segment or a quadratic Bézier curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArrowPath {
+    pub start: (f64, f64),
+    /// Bézier control point; `None` for a straight shaft.
+    pub control: Option<(f64, f64)>,
+    pub end: (f64, f64),
+}
+
+impl ArrowPath {
+    /// Shaft of an arrow element, or `None` if it has fewer than two points.
+    pub fn of(element: &DrawingElement) -> Option<Self> {
+        let [start, end, rest @ ..] = element.points.as_slice() else {
+            return None;
+        };
+        let (start, end) = (to_f64(*start), to_f64(*end));
+        // The curve passes through the bend point at t = 0.5, which puts the control point
+        // twice as far from the chord.
+        let control = rest.first().map(|bend| {
+            let bend = to_f64(*bend);
+            (
+                2.0 * bend.0 - (start.0 + end.0) / 2.0,
+                2.0 * bend.1 - (start.1 + end.1) / 2.0,
+            )
+        });
+        Some(Self {
+            start,
+            control,
+            end,
+        })
+    }
+
+    /// Point at parameter `t`, from 0 at the start to 1 at the end.
+    pub fn point_at(&self, t: f64) -> (f64, f64) {
+        let (s, e) = (self.start, self.end);
+        match self.control {
+            None => (s.0 + (e.0 - s.0) * t, s.1 + (e.1 - s.1) * t),
+            Some(c) => {
+                let u = 1.0 - t;
+                (
+                    u * u * s.0 + 2.0 * u * t * c.0 + t * t * e.0,
+                    u * u * s.1 + 2.0 * u * t * c.1 + t * t * e.1,
+                )
+            }
+        }
+    }
+
+    /// Halfway along the shaft, where the bend handle sits.
+    pub fn midpoint(&self) -> (f64, f64) {
+        self.point_at(0.5)
+    }
+
+    /// Points along the shaft, from start to end.
+    pub fn flatten(&self) -> Vec<(f64, f64)> {
+        let segments = if self.control.is_some() {
+            CURVE_SEGMENTS
+        } else {
+            1
+        };
+        (0..=segments)
+            .map(|i| self.point_at(i as f64 / segments as f64))
+            .collect()
+    }
+
+    /// Approximate length along the shaft.
+    pub fn length(&self) -> f64 {
+        self.flatten()
+            .windows(2)
+            .map(|pair| distance(pair[0], pair[1]))
+            .sum()
+    }
+
+    /// Unit vector pointing out of the shaft at its end or start, along the tangent.
+    fn outward(&self, at_end: bool) -> Option<(f64, f64)> {
+        let (tip, other) = if at_end {
+            (self.end, self.start)
+        } else {
+            (self.start, self.end)
+        };
+        // A control point on top of the tip has no tangent there; fall back to the chord.
+        [self.control.unwrap_or(other), other]
+            .into_iter()
+            .find_map(|from| unit(tip.0 - from.0, tip.1 - from.1))
+    }
+
+    /// The shaft with `start_by` and `end_by` pixels, measured straight from each end, cut
+    /// off. Neither cut goes past the middle.
+    fn trimmed(&self, start_by: f64, end_by: f64) -> Self {
+        let t0 = self.param_at_distance(false, start_by);
+        let t1 = self.param_at_distance(true, end_by);
+        let start = self.point_at(t0);
+        let control = self.control.map(|c| {
+            // Half the derivative at t0, stretched over the remaining parameter range.
+            let (s, e) = (self.start, self.end);
+            let u = 1.0 - t0;
+            let half_derivative = (
+                u * (c.0 - s.0) + t0 * (e.0 - c.0),
+                u * (c.1 - s.1) + t0 * (e.1 - c.1),
+            );
+            (
+                start.0 + (t1 - t0) * half_derivative.0,
+                start.1 + (t1 - t0) * half_derivative.1,
+            )
+        });
+        Self {
+            start,
+            control,
+            end: self.point_at(t1),
+        }
+    }
+
+    /// Parameter of the point `by` pixels in a straight line from the start or end.
+    fn param_at_distance(&self, from_end: bool, by: f64) -> f64 {
+        let param = |step: f64| if from_end { 1.0 - step } else { step };
+        if by <= 0.0 {
+            return param(0.0);
+        }
+        let anchor = if from_end { self.end } else { self.start };
+        let (mut near, mut far) = (0.0, 0.5);
+        for _ in 0..24 {
+            let step = (near + far) / 2.0;
+            if distance(self.point_at(param(step)), anchor) < by {
+                near = step;
+            } else {
+                far = step;
+            }
+        }
+        param((near + far) / 2.0)
+    }
+}
+
+/// Marker at one end of an arrow, in element coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArrowMarker {
+    /// Two strokes from the wings to the tip: `[wing, tip, wing]`.
+    Open([(f64, f64); 3]),
+    /// Filled triangle: `[wing, tip, wing]`.
+    Triangle([(f64, f64); 3]),
+    Dot {
+        center: (f64, f64),
+        radius: f64,
+    },
+    /// Stroke across the end.
+    Bar([(f64, f64); 2]),
+}
+
+impl ArrowMarker {
+    /// Marker of `kind` with its tip at `tip`, pointing along the unit vector `direction`.
+    fn new(kind: ArrowHead, tip: (f64, f64), direction: (f64, f64), length: f64) -> Option<Self> {
+        let (sin, cos) = defaults::ARROW_HEAD_ANGLE.sin_cos();
+        let (ux, uy) = direction;
+        let wing1 = (
+            tip.0 - length * (ux * cos + uy * sin),
+            tip.1 - length * (uy * cos - ux * sin),
+        );
+        let wing2 = (
+            tip.0 - length * (ux * cos - uy * sin),
+            tip.1 - length * (uy * cos + ux * sin),
+        );
+        match kind {
+            ArrowHead::None => None,
+            ArrowHead::Open => Some(Self::Open([wing1, tip, wing2])),
+            ArrowHead::Triangle => Some(Self::Triangle([wing1, tip, wing2])),
+            ArrowHead::Dot => Some(Self::Dot {
+                center: tip,
+                radius: length * DOT_RADIUS_SCALE,
+            }),
+            ArrowHead::Bar => {
+                // As wide as a head would be.
+                let half = length * sin;
+                Some(Self::Bar([
+                    (tip.0 - uy * half, tip.1 + ux * half),
+                    (tip.0 + uy * half, tip.1 - ux * half),
+                ]))
+            }
+        }
+    }
+
+    fn contains(&self, point: (f64, f64), tolerance: f64) -> bool {
+        match *self {
+            Self::Open([wing1, tip, wing2]) => {
+                segment_distance(point, wing1, tip) <= tolerance
+                    || segment_distance(point, tip, wing2) <= tolerance
+            }
+            Self::Triangle(corners) => triangle_contains(corners, point, tolerance),
+            Self::Dot { center, radius } => distance(point, center) <= radius + tolerance,
+            Self::Bar([a, b]) => segment_distance(point, a, b) <= tolerance,
+        }
+    }
+}
+
+/// Everything that is drawn for an arrow: its shaft, trimmed where a marker covers the end,
+/// and the markers themselves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArrowShape {
+    pub shaft: ArrowPath,
+    pub head: Option<ArrowMarker>,
+    pub tail: Option<ArrowMarker>,
+}
+
+impl ArrowShape {
+    /// Shape of an arrow element, or `None` if it has fewer than two points. Arrows shorter
+    /// than [`defaults::ARROW_MIN_LENGTH`] are drawn without markers.
+    pub fn of(element: &DrawingElement) -> Option<Self> {
+        let path = ArrowPath::of(element)?;
+        if path.length() <= defaults::ARROW_MIN_LENGTH {
+            return Some(Self {
+                shaft: path,
+                head: None,
+                tail: None,
+            });
+        }
+
+        let length = head_length(element.thickness);
+        let style = element.arrow;
+        let marker = |kind: ArrowHead, at_end: bool| {
+            let tip = if at_end { path.end } else { path.start };
+            path.outward(at_end)
+                .and_then(|direction| ArrowMarker::new(kind, tip, direction, length))
+        };
+        Some(Self {
+            shaft: path.trimmed(style.tail.inset(length), style.head.inset(length)),
+            head: marker(style.head, true),
+            tail: marker(style.tail, false),
+        })
+    }
+
+    /// Whether `(x, y)` is within `tolerance` of the shaft or on a marker.
+    pub fn contains(&self, x: i32, y: i32, tolerance: f64) -> bool {
+        let point = (x as f64, y as f64);
+        self.shaft
+            .flatten()
+            .windows(2)
+            .any(|pair| segment_distance(point, pair[0], pair[1]) <= tolerance)
+            || [self.head, self.tail]
+                .iter()
+                .flatten()
+                .any(|marker| marker.contains(point, tolerance))
+    }
+}
+
+/// Whether `point` is inside the triangle `[a, b, c]` or within `tolerance` of its edges.
+pub(crate) fn triangle_contains(
+    [a, b, c]: [(f64, f64); 3],
+    point: (f64, f64),
+    tolerance: f64,
+) -> bool {
+    let side = |p: (f64, f64), q: (f64, f64)| {
+        (q.0 - p.0) * (point.1 - p.1) - (q.1 - p.1) * (point.0 - p.0)
+    };
+    let (d1, d2, d3) = (side(a, b), side(b, c), side(c, a));
+    let inside = (d1 >= 0.0 && d2 >= 0.0 && d3 >= 0.0) || (d1 <= 0.0 && d2 <= 0.0 && d3 <= 0.0);
+    inside
+        || [(a, b), (b, c), (c, a)]
+            .iter()
+            .any(|&(p, q)| segment_distance(point, p, q) <= tolerance)
+}
+
+pub(crate) fn to_f64(point: Point) -> (f64, f64) {
+    (point.x as f64, point.y as f64)
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+fn unit(dx: f64, dy: f64) -> Option<(f64, f64)> {
+    let length = dx.hypot(dy);
+    (length > f64::EPSILON).then(|| (dx / length, dy / length))
+}
+
+fn segment_distance(point: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return distance(point, a);
+    }
+    let t = (((point.0 - a.0) * dx + (point.1 - a.1) * dy) / length_sq).clamp(0.0, 1.0);
+    distance(point, (a.0 + t * dx, a.1 + t * dy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DrawingTool;
+
+    fn arrow(points: &[(i32, i32)], style: ArrowStyle) -> DrawingElement {
+        let mut element = DrawingElement::new(DrawingTool::Arrow);
+        for &(x, y) in points {
+            element.add_point(x, y);
+        }
+        element.arrow = style;
+        element.update_bounding_rect();
+        element
+    }
+
+    fn close(a: (f64, f64), b: (f64, f64)) -> bool {
+        distance(a, b) < 0.01
+    }
+
+    #[test]
+    fn text_forms_round_trip() {
+        for head in [
+            ArrowHead::None,
+            ArrowHead::Open,
+            ArrowHead::Triangle,
+            ArrowHead::Dot,
+            ArrowHead::Bar,
+        ] {
+            assert_eq!(ArrowHead::parse(&head.to_string()), Some(head));
+        }
+        let style = ArrowStyle::parse("triangle", "feather");
+        assert_eq!(style.head, ArrowHead::Triangle);
+        assert_eq!(style.tail, ArrowHead::None);
+        assert!(!style.is_double_headed());
+    }
+
+    #[test]
+    fn heads_scale_with_thickness() {
+        assert_eq!(head_length(3.0), 15.0);
+        assert_eq!(head_length(8.0), 40.0);
+        assert_eq!(head_length(1.0), defaults::ARROW_HEAD_MIN_LENGTH);
+    }
+
+    #[test]
+    fn open_head_points_back_along_the_shaft() {
+        let shape = ArrowShape::of(&arrow(&[(0, 0), (100, 0)], ArrowStyle::default())).unwrap();
+        let Some(ArrowMarker::Open([wing1, tip, wing2])) = shape.head else {
+            panic!("expected an open head");
+        };
+        assert!(close(tip, (100.0, 0.0)));
+        assert!(wing1.0 < 100.0 && (wing1.0 - wing2.0).abs() < 1e-9);
+        assert!((wing1.1 + wing2.1).abs() < 1e-9);
+        assert_eq!(shape.tail, None);
+        assert_eq!(shape.shaft.end, (100.0, 0.0));
+
+        let short = ArrowShape::of(&arrow(&[(0, 0), (10, 0)], ArrowStyle::default())).unwrap();
+        assert_eq!(short.head, None);
+    }
+
+    #[test]
+    fn double_headed_arrows_trim_the_shaft_under_solid_heads() {
+        let style = ArrowStyle {
+            head: ArrowHead::Triangle,
+            tail: ArrowHead::Dot,
+        };
+        assert!(style.is_double_headed());
+        let shape = ArrowShape::of(&arrow(&[(0, 0), (100, 0)], style)).unwrap();
+        let inset = head_length(defaults::LINE_THICKNESS) * defaults::ARROW_HEAD_ANGLE.cos();
+        assert!(close(shape.shaft.end, (100.0 - inset, 0.0)));
+        assert!(close(shape.shaft.start, (0.0, 0.0)));
+        assert!(matches!(
+            shape.tail,
+            Some(ArrowMarker::Dot { center, .. }) if close(center, (0.0, 0.0))
+        ));
+        assert!(shape.contains(95, 2, 0.0));
+        assert!(!shape.contains(50, 20, 5.0));
+    }
+
+    #[test]
+    fn bend_point_lies_on_the_curve() {
+        let element = arrow(&[(0, 0), (100, 0), (50, 40)], ArrowStyle::default());
+        let path = ArrowPath::of(&element).unwrap();
+        assert!(close(path.midpoint(), (50.0, 40.0)));
+        assert!(element.rect.bottom >= 40 && element.rect.top <= 0);
+
+        // The head follows the tangent at the end, which comes down and to the right.
+        let shape = ArrowShape::of(&element).unwrap();
+        let Some(ArrowMarker::Open([wing1, _, wing2])) = shape.head else {
+            panic!("expected an open head");
+        };
+        assert!(wing1.1 > 0.0 && wing2.1 > 0.0);
+
+        assert!(shape.contains(50, 40, 1.0));
+        assert!(!shape.contains(50, 0, 5.0));
+
+        // Trimming keeps the curve on the same path.
+        let trimmed = path.trimmed(10.0, 10.0);
+        assert!((distance(trimmed.start, path.start) - 10.0).abs() < 0.01);
+        for t in [0.25, 0.5, 0.75] {
+            let point = trimmed.point_at(t);
+            let nearest = (0..=1000)
+                .map(|i| distance(point, path.point_at(i as f64 / 1000.0)))
+                .fold(f64::MAX, f64::min);
+            assert!(nearest < 0.1, "{point:?} is off the curve");
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Blending for translucent annotation strokes.
+//!
+//! Highlighters are meant to multiply their colour into whatever is underneath, so dark text
+//! stays dark while light backgrounds take on the tint. Backends without a multiply operator
+//! can get close with a per-channel minimum (darken) against [`multiply_tint`].
+
+use crate::element::Color;
+
+/// Opaque colour that, multiplied with the backdrop, gives `color` laid over it at `color.a`.
+///
+/// Per channel this is `1 - a * (1 - c)`: white where the stroke is fully transparent and
+/// the stroke colour itself where it is opaque.
+pub fn multiply_tint(color: Color) -> Color {
+    let a = color.a.clamp(0.0, 1.0);
+    let tint = |c: f32| 1.0 - a * (1.0 - c.clamp(0.0, 1.0));
+    Color::new(tint(color.r), tint(color.g), tint(color.b), 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YELLOW: Color = Color {
+        r: 1.0,
+        g: 0.9,
+        b: 0.0,
+        a: 0.5,
+    };
+
+    #[test]
+    fn tint_interpolates_from_white() {
+        let tint = multiply_tint(YELLOW);
+        assert_eq!(tint.r, 1.0);
+        assert!((tint.g - 0.95).abs() < 1e-6);
+        assert_eq!(tint.b, 0.5);
+        assert_eq!(tint.a, 1.0);
+
+        let clear = multiply_tint(Color { a: 0.0, ..YELLOW });
+        assert_eq!((clear.r, clear.g, clear.b), (1.0, 1.0, 1.0));
+    }
+}
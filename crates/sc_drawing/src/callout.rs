@@ -0,0 +1,189 @@
+//! Callout geometry: a rounded text box with a tail pointing at something.
+//!
+//! A callout keeps its box in `points[0]` and `points[1]`, exactly like a text element, so typing
+//! grows it the same way. `points[2]` is the tip of the tail, which leaves the side of the box
+//! that faces the tip. The screen renderer and the exporters all draw the [`CalloutShape`] built
+//! here.
+
+use crate::arrow::{to_f64, triangle_contains};
+use crate::element::{DrawingElement, defaults};
+
+/// One step along a callout outline, starting where the previous one ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutlineSegment {
+    Line((f64, f64)),
+    /// Quadratic Bézier curve rounding a corner of the box.
+    Curve {
+        control: (f64, f64),
+        to: (f64, f64),
+    },
+}
+
+/// Closed outline of a callout: its rounded box with the tail spliced into one side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalloutShape {
+    pub start: (f64, f64),
+    pub segments: Vec<OutlineSegment>,
+    /// Where the tail leaves the box, its tip, and where it rejoins the box. `None` while the
+    /// tip is inside the box.
+    pub tail: Option<[(f64, f64); 3]>,
+    /// Left, top, right and bottom edges of the box.
+    bounds: (f64, f64, f64, f64),
+}
+
+impl CalloutShape {
+    /// Outline of a callout element, or `None` if it has no box yet.
+    pub fn of(element: &DrawingElement) -> Option<Self> {
+        let [p0, p1, rest @ ..] = element.points.as_slice() else {
+            return None;
+        };
+        let (left, top) = (p0.x.min(p1.x) as f64, p0.y.min(p1.y) as f64);
+        let (right, bottom) = (p0.x.max(p1.x) as f64, p0.y.max(p1.y) as f64);
+        let radius = defaults::CALLOUT_CORNER_RADIUS
+            .min((right - left) / 2.0)
+            .min((bottom - top) / 2.0);
+
+        // Going clockwise from the top-left corner: the straight part of each side, then the
+        // corner that follows it.
+        let sides = [
+            ((left + radius, top), (right - radius, top)),
+            ((right, top + radius), (right, bottom - radius)),
+            ((right - radius, bottom), (left + radius, bottom)),
+            ((left, bottom - radius), (left, top + radius)),
+        ];
+        let corners = [(right, top), (right, bottom), (left, bottom), (left, top)];
+
+        let tip = rest.first().map(|tip| to_f64(*tip));
+        let tail_side = tip.and_then(|tip| facing_side(tip, (left, top, right, bottom)));
+
+        let mut segments = Vec::with_capacity(11);
+        let mut tail = None;
+        for (i, &(from, to)) in sides.iter().enumerate() {
+            if tail_side == Some(i)
+                && let Some(tip) = tip
+            {
+                let triangle = tail_base(from, to, tip);
+                segments.extend(triangle.map(OutlineSegment::Line));
+                tail = Some(triangle);
+            }
+            segments.push(OutlineSegment::Line(to));
+            segments.push(OutlineSegment::Curve {
+                control: corners[i],
+                to: sides[(i + 1) % 4].0,
+            });
+        }
+
+        Some(Self {
+            start: sides[0].0,
+            segments,
+            tail,
+            bounds: (left, top, right, bottom),
+        })
+    }
+
+    /// Whether `(x, y)` is in the box or within `tolerance` of the tail.
+    pub fn contains(&self, x: i32, y: i32, tolerance: f64) -> bool {
+        let (left, top, right, bottom) = self.bounds;
+        let point = (x as f64, y as f64);
+        (point.0 >= left && point.0 <= right && point.1 >= top && point.1 <= bottom)
+            || self
+                .tail
+                .is_some_and(|tail| triangle_contains(tail, point, tolerance))
+    }
+}
+
+/// Index into the clockwise sides, from the top, of the side of the box facing `tip`, or
+/// `None` if the tip is inside the box.
+fn facing_side(tip: (f64, f64), (left, top, right, bottom): (f64, f64, f64, f64)) -> Option<usize> {
+    if tip.0 >= left && tip.0 <= right && tip.1 >= top && tip.1 <= bottom {
+        return None;
+    }
+    // Measured in half-sizes, so that a wide box still sends a tail straight down from its
+    // bottom edge.
+    let dx = (tip.0 - (left + right) / 2.0) / ((right - left) / 2.0).max(1.0);
+    let dy = (tip.1 - (top + bottom) / 2.0) / ((bottom - top) / 2.0).max(1.0);
+    Some(if dx.abs() >= dy.abs() {
+        if dx > 0.0 { 1 } else { 3 }
+    } else if dy > 0.0 {
+        2
+    } else {
+        0
+    })
+}
+
+/// Tail leaving the straight part of a side, from `from` to `to`, as close under `tip` as the
+/// rounded corners allow.
+fn tail_base(from: (f64, f64), to: (f64, f64), tip: (f64, f64)) -> [(f64, f64); 3] {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx.hypot(dy);
+    if length <= f64::EPSILON {
+        return [from, tip, from];
+    }
+    let (ux, uy) = (dx / length, dy / length);
+    let half = (defaults::CALLOUT_TAIL_WIDTH / 2.0).min(length / 2.0);
+    let along = ((tip.0 - from.0) * ux + (tip.1 - from.1) * uy).clamp(half, length - half);
+    let at = |offset: f64| (from.0 + ux * offset, from.1 + uy * offset);
+    [at(along - half), tip, at(along + half)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DrawingTool;
+
+    fn callout(points: &[(i32, i32)]) -> DrawingElement {
+        let mut element = DrawingElement::new(DrawingTool::Callout);
+        for &(x, y) in points {
+            element.add_point(x, y);
+        }
+        element.update_bounding_rect();
+        element
+    }
+
+    fn end_of(segment: &OutlineSegment) -> (f64, f64) {
+        match *segment {
+            OutlineSegment::Line(to) | OutlineSegment::Curve { to, .. } => to,
+        }
+    }
+
+    #[test]
+    fn outline_is_closed_and_tail_leaves_the_facing_side() {
+        let shape = CalloutShape::of(&callout(&[(0, 0), (200, 60), (30, 120)])).unwrap();
+        assert_eq!(end_of(shape.segments.last().unwrap()), shape.start);
+
+        // Below the box: the tail hangs off the bottom edge, under the tip.
+        let [base1, tip, base2] = shape.tail.unwrap();
+        assert_eq!(tip, (30.0, 120.0));
+        assert_eq!((base1.1, base2.1), (60.0, 60.0));
+        assert_eq!((base1.0 - base2.0).abs(), defaults::CALLOUT_TAIL_WIDTH);
+        assert!(base1.0.max(base2.0) > 30.0 && base1.0.min(base2.0) < 30.0);
+
+        // Beside the box, and pressed against the corner when the tip is far past it.
+        let shape = CalloutShape::of(&callout(&[(0, 0), (200, 60), (400, 0)])).unwrap();
+        let [base1, _, base2] = shape.tail.unwrap();
+        assert_eq!((base1.0, base2.0), (200.0, 200.0));
+        assert_eq!(base1.1.min(base2.1), defaults::CALLOUT_CORNER_RADIUS);
+    }
+
+    #[test]
+    fn tail_disappears_inside_the_box() {
+        let element = callout(&[(0, 0), (200, 60), (100, 30)]);
+        let shape = CalloutShape::of(&element).unwrap();
+        assert_eq!(shape.tail, None);
+        assert_eq!(shape.segments.len(), 8);
+        assert!(element.contains_point(100, 30));
+    }
+
+    #[test]
+    fn hit_test_covers_box_and_tail() {
+        let element = callout(&[(0, 0), (200, 60), (30, 160)]);
+        assert!(element.contains_point(150, 40));
+        assert!(element.contains_point(30, 158));
+        assert!(element.contains_point(30, 100));
+        assert!(!element.contains_point(150, 100));
+
+        // The tip stretches the bounds without moving the box that holds the text.
+        assert_eq!(element.rect, crate::Rect::new(0, 0, 200, 60));
+        assert!(element.get_bounding_rect().bottom >= 160);
+    }
+}
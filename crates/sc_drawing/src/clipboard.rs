@@ -0,0 +1,110 @@
+//! Copy and paste of annotations inside the editor.
+//!
+//! This is separate from the system clipboard, which only ever receives the flattened image.
+
+use std::collections::HashMap;
+
+use crate::element::{DrawingElement, new_group_id};
+
+/// How far each paste or duplicate is shifted from the previous copy, in pixels.
+pub const PASTE_OFFSET: i32 = 10;
+
+/// Copies of annotations waiting to be pasted.
+#[derive(Debug, Clone, Default)]
+pub struct ElementClipboard {
+    elements: Vec<DrawingElement>,
+    /// Pastes made since the last copy; every paste lands one offset further away.
+    pastes: i32,
+}
+
+impl ElementClipboard {
+    pub const fn new() -> Self {
+        Self {
+            elements: Vec::new(),
+            pastes: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Replace the contents with copies of `elements`.
+    pub fn copy<'a>(&mut self, elements: impl IntoIterator<Item = &'a DrawingElement>) {
+        self.elements = elements.into_iter().cloned().collect();
+        self.pastes = 0;
+    }
+
+    /// New elements to insert for one paste, each with a fresh id.
+    pub fn paste(&mut self) -> Vec<DrawingElement> {
+        if self.elements.is_empty() {
+            return Vec::new();
+        }
+        self.pastes += 1;
+        duplicate_elements(&self.elements, PASTE_OFFSET * self.pastes)
+    }
+}
+
+/// Copy `elements` under fresh ids, shifted by `offset` on both axes.
+///
+/// Groups among them are copied as new groups, so the copies do not join the originals.
+pub fn duplicate_elements(elements: &[DrawingElement], offset: i32) -> Vec<DrawingElement> {
+    let mut groups = HashMap::new();
+    elements
+        .iter()
+        .map(|element| {
+            let mut copy = element.duplicate();
+            copy.group_id = element
+                .group_id
+                .map(|group| *groups.entry(group).or_insert_with(new_group_id));
+            copy.move_by(offset, offset);
+            copy
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{Point, Rect};
+    use crate::types::DrawingTool;
+
+    fn arrow(group_id: Option<u64>) -> DrawingElement {
+        let mut element = DrawingElement::new(DrawingTool::Arrow);
+        element.points = vec![Point::new(0, 0), Point::new(40, 20)];
+        element.rect = Rect::new(0, 0, 40, 20);
+        element.group_id = group_id;
+        element.selected = true;
+        element
+    }
+
+    #[test]
+    fn pastes_get_fresh_ids_and_step_further_away() {
+        let original = arrow(None);
+        let mut clipboard = ElementClipboard::new();
+        assert!(clipboard.paste().is_empty());
+        clipboard.copy([&original]);
+
+        let first = clipboard.paste();
+        let second = clipboard.paste();
+        assert_ne!(first[0].id, original.id);
+        assert_ne!(first[0].id, second[0].id);
+        assert_eq!(first[0].rect, Rect::new(10, 10, 50, 30));
+        assert_eq!(second[0].points[0], Point::new(20, 20));
+        assert!(!first[0].selected);
+
+        // A new copy starts the offset over.
+        clipboard.copy(&first);
+        assert_eq!(clipboard.paste()[0].rect, Rect::new(20, 20, 60, 40));
+    }
+
+    #[test]
+    fn groups_are_copied_as_new_groups() {
+        let copies = duplicate_elements(&[arrow(Some(7)), arrow(Some(7)), arrow(None)], 0);
+        let group = copies[0].group_id.unwrap();
+        assert_ne!(group, 7);
+        assert_eq!(copies[1].group_id, Some(group));
+        assert_eq!(copies[2].group_id, None);
+        assert_ne!(duplicate_elements(&copies[..1], 0)[0].group_id, Some(group));
+    }
+}
@@ -0,0 +1,168 @@
+//! Mouse cursor compositing for captures.
+//!
+//! Screen grabs do not contain the pointer. When it should appear in a capture, the host asks
+//! a [`CursorSource`] for the cursor as it was at capture time and [`composite_cursor`] blends
+//! it in so that its hotspot lands on the pointer position.
+
+use crate::element::{Point, Rect};
+use crate::raster::{PixelBuffer, clip_rect};
+
+/// The cursor as it was shown on screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorImage {
+    /// Cursor pixels with straight (non-premultiplied) alpha.
+    pub image: PixelBuffer,
+    /// Pixels that invert the screen beneath them instead of covering it, as monochrome
+    /// cursors such as the text I-beam do. Either empty or one entry per pixel.
+    pub invert: Vec<bool>,
+    /// Offset of the pointer's tip inside `image`.
+    pub hotspot: Point,
+    /// Pointer position in screen coordinates.
+    pub position: Point,
+}
+
+impl CursorImage {
+    /// Top-left corner of the image in screen coordinates.
+    #[inline]
+    pub fn origin(&self) -> Point {
+        Point::new(
+            self.position.x - self.hotspot.x,
+            self.position.y - self.hotspot.y,
+        )
+    }
+
+    #[inline]
+    fn inverts(&self, x: u32, y: u32) -> bool {
+        self.invert
+            .get(y as usize * self.image.width as usize + x as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+/// Platform hook that reads the current cursor.
+pub trait CursorSource {
+    /// The visible cursor right now, or `None` if it is hidden or cannot be read.
+    fn current_cursor(&self) -> Option<CursorImage>;
+}
+
+/// Blend `cursor` over `target`, whose top-left pixel sits at `target_origin` on screen.
+///
+/// The target is treated as opaque: colour channels are blended source-over and its alpha
+/// channel is left as it was. Parts of the cursor outside the target are dropped.
+pub fn composite_cursor(target: &mut PixelBuffer, cursor: &CursorImage, target_origin: Point) {
+    let origin = cursor.origin();
+    let (dx, dy) = (origin.x - target_origin.x, origin.y - target_origin.y);
+    let cursor_bounds = Rect::new(
+        dx,
+        dy,
+        dx + cursor.image.width as i32,
+        dy + cursor.image.height as i32,
+    );
+    let Some(area) = clip_rect(cursor_bounds, target.bounds()) else {
+        return;
+    };
+    let swap = cursor.image.order != target.order;
+
+    for y in area.top..area.bottom {
+        for x in area.left..area.right {
+            let (sx, sy) = ((x - dx) as u32, (y - dy) as u32);
+            let mut dst = target.pixel(x as u32, y as u32);
+
+            if cursor.inverts(sx, sy) {
+                for channel in &mut dst[..3] {
+                    *channel = 255 - *channel;
+                }
+            } else {
+                let mut src = cursor.image.pixel(sx, sy);
+                if swap {
+                    src.swap(0, 2);
+                }
+                let alpha = src[3] as u32;
+                if alpha == 0 {
+                    continue;
+                }
+                for (d, s) in dst[..3].iter_mut().zip(&src[..3]) {
+                    *d = ((*s as u32 * alpha + *d as u32 * (255 - alpha) + 127) / 255) as u8;
+                }
+            }
+            target.set_pixel(x as u32, y as u32, dst);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raster::ChannelOrder;
+
+    fn backdrop(order: ChannelOrder) -> PixelBuffer {
+        let mut target = PixelBuffer::new(8, 6, order);
+        for y in 0..6 {
+            for x in 0..8 {
+                target.set_pixel(x, y, [100, 100, 100, 0]);
+            }
+        }
+        target
+    }
+
+    /// 3x3 cursor: opaque red tip at the top-left, half-transparent blue centre, the rest clear.
+    fn cursor(position: Point) -> CursorImage {
+        let mut image = PixelBuffer::new(3, 3, ChannelOrder::Rgba);
+        image.set_pixel(0, 0, [255, 0, 0, 255]);
+        image.set_pixel(1, 1, [0, 0, 255, 128]);
+        CursorImage {
+            image,
+            invert: Vec::new(),
+            hotspot: Point::new(0, 0),
+            position,
+        }
+    }
+
+    #[test]
+    fn hotspot_lands_on_pointer_position() {
+        let mut target = backdrop(ChannelOrder::Rgba);
+        let mut cursor = cursor(Point::new(13, 12));
+        cursor.hotspot = Point::new(1, 1);
+
+        // Screen (10, 10) is the target's top-left, so the pointer is at pixel (3, 2).
+        composite_cursor(&mut target, &cursor, Point::new(10, 10));
+        assert_eq!(target.pixel(2, 1), [255, 0, 0, 0]);
+        assert_eq!(target.pixel(3, 2), [50, 50, 178, 0]);
+        assert_eq!(target.pixel(4, 3), [100, 100, 100, 0]);
+        assert_eq!(target.pixel(1, 1), [100, 100, 100, 0]);
+    }
+
+    #[test]
+    fn channel_order_is_converted() {
+        let mut target = backdrop(ChannelOrder::Bgra);
+        composite_cursor(&mut target, &cursor(Point::new(0, 0)), Point::new(0, 0));
+        assert_eq!(target.pixel(0, 0), [0, 0, 255, 0]);
+        assert_eq!(target.pixel(1, 1), [178, 50, 50, 0]);
+    }
+
+    #[test]
+    fn invert_mask_inverts_the_backdrop() {
+        let mut target = backdrop(ChannelOrder::Rgba);
+        target.set_pixel(1, 0, [10, 20, 30, 255]);
+        let mut cursor = cursor(Point::new(0, 0));
+        cursor.invert = vec![false, true, false, false, false, false, false, false, false];
+
+        composite_cursor(&mut target, &cursor, Point::new(0, 0));
+        assert_eq!(target.pixel(1, 0), [245, 235, 225, 255]);
+        assert_eq!(target.pixel(0, 0), [255, 0, 0, 0]);
+    }
+
+    #[test]
+    fn cursor_is_clipped_at_the_edges() {
+        let mut target = backdrop(ChannelOrder::Rgba);
+        let mut cursor = cursor(Point::new(7, 5));
+        cursor.hotspot = Point::new(1, 1);
+        composite_cursor(&mut target, &cursor, Point::new(0, 0));
+        assert_eq!(target.pixel(7, 5), [50, 50, 178, 0]);
+
+        let mut untouched = backdrop(ChannelOrder::Rgba);
+        composite_cursor(&mut untouched, &cursor, Point::new(100, 100));
+        assert_eq!(untouched, backdrop(ChannelOrder::Rgba));
+    }
+}
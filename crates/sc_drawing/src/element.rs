@@ -1,10 +1,16 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use serde::{Deserialize, Serialize};
+
+use crate::arrow::{ArrowPath, ArrowShape, ArrowStyle, head_length};
+use crate::callout::CalloutShape;
+use crate::redaction::RedactionPatch;
+use crate::stroke::StrokeStyle;
 use crate::types::DrawingTool;
 
 static NEXT_ELEMENT_ID: AtomicU64 = AtomicU64::new(1);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Serialize, Deserialize)]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -17,7 +23,7 @@ impl Point {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Serialize, Deserialize)]
 pub struct Rect {
     pub left: i32,
     pub top: i32,
@@ -46,13 +52,43 @@ impl Rect {
         self.bottom - self.top
     }
 
+    /// The same rect shifted by `(dx, dy)`.
+    #[inline]
+    pub fn offset(&self, dx: i32, dy: i32) -> Self {
+        Self::new(
+            self.left + dx,
+            self.top + dy,
+            self.right + dx,
+            self.bottom + dy,
+        )
+    }
+
     #[inline]
     pub fn contains(&self, x: i32, y: i32) -> bool {
         x >= self.left && x <= self.right && y >= self.top && y <= self.bottom
     }
+
+    /// Centre point, unrounded.
+    #[inline]
+    pub fn center(&self) -> (f64, f64) {
+        (
+            (self.left + self.right) as f64 / 2.0,
+            (self.top + self.bottom) as f64 / 2.0,
+        )
+    }
+}
+
+/// `point` turned `degrees` clockwise on screen (y pointing down) about `center`.
+pub fn rotate_point(point: Point, center: (f64, f64), degrees: f32) -> Point {
+    let (sin, cos) = (degrees as f64).to_radians().sin_cos();
+    let (dx, dy) = (point.x as f64 - center.0, point.y as f64 - center.1);
+    Point::new(
+        (center.0 + dx * cos - dy * sin).round() as i32,
+        (center.1 + dx * sin + dy * cos).round() as i32,
+    )
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -70,6 +106,12 @@ impl Color {
     pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
         Self { r, g, b, a: 1.0 }
     }
+
+    /// The same colour with its opacity replaced.
+    #[inline]
+    pub const fn with_alpha(self, a: f32) -> Self {
+        Self { a, ..self }
+    }
 }
 
 pub mod defaults {
@@ -84,13 +126,37 @@ pub mod defaults {
     pub const ELEMENT_WIDTH: i32 = 50;
     pub const ELEMENT_HEIGHT: i32 = 30;
     pub const CLICK_TOLERANCE: f32 = 5.0;
-    pub const ARROW_HEAD_LENGTH: f64 = 15.0;
+    /// Arrow head length per pixel of stroke thickness.
+    pub const ARROW_HEAD_SCALE: f64 = 5.0;
+    pub const ARROW_HEAD_MIN_LENGTH: f64 = 10.0;
     pub const ARROW_HEAD_ANGLE: f64 = 0.5;
     pub const ARROW_HEAD_MARGIN: i32 = 20;
     pub const ARROW_MIN_LENGTH: f64 = 20.0;
+    pub const MOSAIC_BLOCK_SIZE: u32 = 10;
+    pub const MIN_MOSAIC_BLOCK_SIZE: u32 = 2;
+    pub const MAX_MOSAIC_BLOCK_SIZE: u32 = 64;
+    pub const BLUR_RADIUS: f32 = 12.0;
+    pub const MIN_BLUR_RADIUS: f32 = 1.0;
+    pub const MAX_BLUR_RADIUS: f32 = 48.0;
+    pub const STEP_RADIUS: i32 = 14;
+    pub const HIGHLIGHTER_WIDTH: f32 = 16.0;
+    pub const HIGHLIGHTER_COLOR: (u8, u8, u8) = (255, 235, 59);
+    pub const HIGHLIGHTER_OPACITY: u8 = 110;
+    pub const FILL_COLOR: (u8, u8, u8) = (255, 0, 0);
+    pub const FILL_OPACITY: u8 = 77;
+    /// Background of new callouts, so that their text reads on any screenshot.
+    pub const CALLOUT_FILL: super::Color = super::Color::rgb(1.0, 1.0, 1.0);
+    pub const CALLOUT_CORNER_RADIUS: f64 = 8.0;
+    /// Width of a callout tail where it leaves the box.
+    pub const CALLOUT_TAIL_WIDTH: f64 = 16.0;
+    /// How far below a new callout the tip of its tail starts.
+    pub const CALLOUT_TAIL_LENGTH: i32 = 40;
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// An annotation. Serializes every user-visible property; missing fields fall back to the
+/// defaults of a fresh element so older project files keep loading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DrawingElement {
     pub id: u64,
     pub tool: DrawingTool,
@@ -98,6 +164,14 @@ pub struct DrawingElement {
     pub rect: Rect,
     pub color: Color,
     pub thickness: f32,
+    /// Interior of tools that [`DrawingTool::can_fill`]; its alpha is the fill opacity. `None`
+    /// leaves the shape outline-only.
+    pub fill: Option<Color>,
+    /// Dash pattern, caps and joins of the outline for tools that
+    /// [`DrawingTool::uses_stroke_style`].
+    pub stroke: StrokeStyle,
+    /// Head and tail markers of arrows.
+    pub arrow: ArrowStyle,
     pub text: String,
     pub font_size: f32,
     pub font_name: String,
@@ -105,6 +179,20 @@ pub struct DrawingElement {
     pub font_italic: bool,
     pub font_underline: bool,
     pub font_strikeout: bool,
+    /// Mosaic cell size in element-space pixels.
+    pub block_size: u32,
+    /// Gaussian blur radius in element-space pixels.
+    pub blur_radius: f32,
+    /// Number shown by step markers; kept contiguous by `ElementManager`.
+    pub step_number: u32,
+    /// Group this element belongs to; grouped elements are selected, moved and deleted together.
+    pub group_id: Option<u64>,
+    /// Clockwise rotation in degrees about the centre of `rect`. `points` and `rect` stay in the
+    /// unrotated frame; only tools that [`DrawingTool::can_rotate`] use it.
+    pub rotation: f32,
+    /// Filtered pixels for redaction tools; rebuilt from the source image, never edited.
+    #[serde(skip)]
+    pub redaction: Option<RedactionPatch>,
     pub selected: bool,
 }
 
@@ -114,6 +202,19 @@ impl Default for DrawingElement {
     }
 }
 
+/// Make sure freshly created elements never reuse `id`.
+///
+/// Needed after loading elements that were created in another session.
+pub fn reserve_element_id(id: u64) {
+    NEXT_ELEMENT_ID.fetch_max(id.saturating_add(1), Ordering::Relaxed);
+}
+
+/// Id for a new group. Taken from the element id counter, so it never collides with an
+/// element id or with a group loaded from a project.
+pub fn new_group_id() -> u64 {
+    NEXT_ELEMENT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
 #[inline]
 pub fn default_color() -> Color {
     Color::new(1.0, 0.0, 0.0, 1.0)
@@ -128,6 +229,9 @@ impl DrawingElement {
             rect: Rect::default(),
             color: default_color(),
             thickness: defaults::LINE_THICKNESS,
+            fill: None,
+            stroke: StrokeStyle::default(),
+            arrow: ArrowStyle::default(),
             text: String::new(),
             font_size: defaults::FONT_SIZE,
             font_name: defaults::FONT_NAME.to_string(),
@@ -135,10 +239,25 @@ impl DrawingElement {
             font_italic: false,
             font_underline: false,
             font_strikeout: false,
+            block_size: defaults::MOSAIC_BLOCK_SIZE,
+            blur_radius: defaults::BLUR_RADIUS,
+            step_number: 0,
+            group_id: None,
+            rotation: 0.0,
+            redaction: None,
             selected: false,
         }
     }
 
+    /// Unselected copy of this element under a new id.
+    pub fn duplicate(&self) -> Self {
+        Self {
+            id: NEXT_ELEMENT_ID.fetch_add(1, Ordering::Relaxed),
+            selected: false,
+            ..self.clone()
+        }
+    }
+
     pub fn with_color(tool: DrawingTool, color: Color) -> Self {
         let mut elem = Self::new(tool);
         elem.color = color;
@@ -146,15 +265,26 @@ impl DrawingElement {
     }
 
     pub fn get_effective_font_size(&self) -> f32 {
-        if self.tool == DrawingTool::Text {
+        if self.tool.is_text() {
             self.font_size.max(defaults::MIN_FONT_SIZE)
         } else {
             defaults::FONT_SIZE
         }
     }
 
+    pub fn set_block_size(&mut self, size: u32) {
+        self.block_size = size.clamp(
+            defaults::MIN_MOSAIC_BLOCK_SIZE,
+            defaults::MAX_MOSAIC_BLOCK_SIZE,
+        );
+    }
+
+    pub fn set_blur_radius(&mut self, radius: f32) {
+        self.blur_radius = radius.clamp(defaults::MIN_BLUR_RADIUS, defaults::MAX_BLUR_RADIUS);
+    }
+
     pub fn set_font_size(&mut self, size: f32) {
-        if self.tool == DrawingTool::Text && (self.font_size - size).abs() > 0.001 {
+        if self.tool.is_text() && (self.font_size - size).abs() > 0.001 {
             self.font_size = size.clamp(defaults::MIN_FONT_SIZE, defaults::MAX_FONT_SIZE);
         }
     }
@@ -165,10 +295,14 @@ impl DrawingElement {
         }
 
         match self.tool {
-            DrawingTool::Text => self.update_text_bounds(),
-            DrawingTool::Pen => self.update_pen_bounds(),
-            DrawingTool::Rectangle | DrawingTool::Circle => self.update_shape_bounds(),
+            DrawingTool::Text | DrawingTool::Callout => self.update_text_bounds(),
+            DrawingTool::Pen | DrawingTool::Highlighter => self.update_pen_bounds(),
+            DrawingTool::Rectangle
+            | DrawingTool::Circle
+            | DrawingTool::Mosaic
+            | DrawingTool::Blur => self.update_shape_bounds(),
             DrawingTool::Arrow => self.update_arrow_bounds(),
+            DrawingTool::Step => self.update_step_bounds(),
             _ => self.update_default_bounds(),
         }
     }
@@ -229,17 +363,38 @@ impl DrawingElement {
     }
 
     fn update_arrow_bounds(&mut self) {
-        if self.points.len() >= 2 {
-            let start = &self.points[0];
-            let end = &self.points[1];
-            let margin = defaults::ARROW_HEAD_MARGIN;
-            self.rect = Rect {
-                left: start.x.min(end.x) - margin,
-                top: start.y.min(end.y) - margin,
-                right: start.x.max(end.x) + margin,
-                bottom: start.y.max(end.y) + margin,
-            };
+        let Some(path) = ArrowPath::of(self) else {
+            return;
+        };
+        // Room for whichever marker sits at an end, however the arrow points.
+        let margin = (defaults::ARROW_HEAD_MARGIN as f64)
+            .max(head_length(self.thickness) + self.thickness as f64);
+        let points = path.flatten();
+        let (mut left, mut top) = (f64::MAX, f64::MAX);
+        let (mut right, mut bottom) = (f64::MIN, f64::MIN);
+        for (x, y) in points {
+            left = left.min(x);
+            top = top.min(y);
+            right = right.max(x);
+            bottom = bottom.max(y);
         }
+        self.rect = Rect {
+            left: (left - margin).floor() as i32,
+            top: (top - margin).floor() as i32,
+            right: (right + margin).ceil() as i32,
+            bottom: (bottom + margin).ceil() as i32,
+        };
+    }
+
+    fn update_step_bounds(&mut self) {
+        let center = self.points[0];
+        let radius = defaults::STEP_RADIUS;
+        self.rect = Rect {
+            left: center.x - radius,
+            top: center.y - radius,
+            right: center.x + radius,
+            bottom: center.y + radius,
+        };
     }
 
     fn update_default_bounds(&mut self) {
@@ -253,12 +408,32 @@ impl DrawingElement {
         }
     }
 
+    /// Rotation that actually applies to this element.
+    #[inline]
+    pub fn is_rotated(&self) -> bool {
+        self.rotation != 0.0 && self.tool.can_rotate()
+    }
+
+    /// Screen point `(x, y)` in the element's own unrotated frame.
+    pub fn unrotate(&self, x: i32, y: i32) -> (i32, i32) {
+        if !self.is_rotated() {
+            return (x, y);
+        }
+        let local = rotate_point(Point::new(x, y), self.rect.center(), -self.rotation);
+        (local.x, local.y)
+    }
+
     pub fn contains_point(&self, x: i32, y: i32) -> bool {
+        let (x, y) = self.unrotate(x, y);
         match self.tool {
-            DrawingTool::Pen => self.contains_point_pen(x, y),
-            DrawingTool::Rectangle | DrawingTool::Circle => self.contains_point_shape(x, y),
+            DrawingTool::Pen | DrawingTool::Highlighter => self.contains_point_pen(x, y),
+            DrawingTool::Rectangle => self.contains_point_rectangle(x, y),
+            DrawingTool::Circle => self.contains_point_ellipse(x, y),
+            DrawingTool::Mosaic | DrawingTool::Blur => self.contains_point_shape(x, y),
             DrawingTool::Arrow => self.contains_point_arrow(x, y),
             DrawingTool::Text => self.rect.contains(x, y),
+            DrawingTool::Step => self.contains_point_step(x, y),
+            DrawingTool::Callout => self.contains_point_callout(x, y),
             _ => false,
         }
     }
@@ -295,58 +470,90 @@ impl DrawingElement {
         }
     }
 
-    fn contains_point_arrow(&self, x: i32, y: i32) -> bool {
-        if self.points.len() < 2 {
+    /// Outline-only rectangles are picked by their stroke; filled ones anywhere inside.
+    fn contains_point_rectangle(&self, x: i32, y: i32) -> bool {
+        let Some((left, top, right, bottom)) = self.shape_extent() else {
             return false;
-        }
-
-        let start = &self.points[0];
-        let end = &self.points[1];
-
-        let distance = point_to_line_distance(x, y, start.x, start.y, end.x, end.y);
-        if distance <= (self.thickness + defaults::CLICK_TOLERANCE) as f64 {
-            return true;
-        }
-
-        let dx = end.x - start.x;
-        let dy = end.y - start.y;
-        let length = ((dx * dx + dy * dy) as f64).sqrt();
-
-        if length > defaults::ARROW_MIN_LENGTH {
-            let arrow_length = defaults::ARROW_HEAD_LENGTH;
-            let arrow_angle = defaults::ARROW_HEAD_ANGLE;
-            let unit_x = dx as f64 / length;
-            let unit_y = dy as f64 / length;
+        };
+        let margin = (self.thickness + defaults::CLICK_TOLERANCE) as f64;
+        let (x, y) = (x as f64, y as f64);
+        let within = |inset: f64| {
+            x >= left + inset && x <= right - inset && y >= top + inset && y <= bottom - inset
+        };
+        within(-margin) && (self.fill.is_some() || !within(margin))
+    }
 
-            let wing1_x = end.x
-                - (arrow_length * (unit_x * arrow_angle.cos() + unit_y * arrow_angle.sin())) as i32;
-            let wing1_y = end.y
-                - (arrow_length * (unit_y * arrow_angle.cos() - unit_x * arrow_angle.sin())) as i32;
+    /// Same rule as [`Self::contains_point_rectangle`] for the ellipse inscribed in the shape.
+    fn contains_point_ellipse(&self, x: i32, y: i32) -> bool {
+        let Some((left, top, right, bottom)) = self.shape_extent() else {
+            return false;
+        };
+        let margin = (self.thickness + defaults::CLICK_TOLERANCE) as f64;
+        let (cx, cy) = ((left + right) / 2.0, (top + bottom) / 2.0);
+        let (rx, ry) = ((right - left) / 2.0, (bottom - top) / 2.0);
+        let (dx, dy) = (x as f64 - cx, y as f64 - cy);
+        let within = |inset: f64| {
+            let (a, b) = (rx - inset, ry - inset);
+            a > 0.0 && b > 0.0 && (dx / a).powi(2) + (dy / b).powi(2) <= 1.0
+        };
+        within(-margin) && (self.fill.is_some() || !within(margin))
+    }
 
-            let wing2_x = end.x
-                - (arrow_length * (unit_x * arrow_angle.cos() - unit_y * arrow_angle.sin())) as i32;
-            let wing2_y = end.y
-                - (arrow_length * (unit_y * arrow_angle.cos() + unit_x * arrow_angle.sin())) as i32;
+    /// Edges of the box spanned by the first two points.
+    fn shape_extent(&self) -> Option<(f64, f64, f64, f64)> {
+        let [start, end, ..] = self.points.as_slice() else {
+            return None;
+        };
+        Some((
+            start.x.min(end.x) as f64,
+            start.y.min(end.y) as f64,
+            start.x.max(end.x) as f64,
+            start.y.max(end.y) as f64,
+        ))
+    }
 
-            let d1 = point_to_line_distance(x, y, end.x, end.y, wing1_x, wing1_y);
-            let d2 = point_to_line_distance(x, y, end.x, end.y, wing2_x, wing2_y);
+    fn contains_point_step(&self, x: i32, y: i32) -> bool {
+        let Some(center) = self.points.first() else {
+            return false;
+        };
+        let dx = (x - center.x) as f64;
+        let dy = (y - center.y) as f64;
+        (dx * dx + dy * dy).sqrt() <= defaults::STEP_RADIUS as f64
+    }
 
-            if d1 <= (self.thickness + defaults::CLICK_TOLERANCE) as f64
-                || d2 <= (self.thickness + defaults::CLICK_TOLERANCE) as f64
-            {
-                return true;
-            }
-        }
+    fn contains_point_arrow(&self, x: i32, y: i32) -> bool {
+        ArrowShape::of(self).is_some_and(|shape| {
+            shape.contains(x, y, (self.thickness + defaults::CLICK_TOLERANCE) as f64)
+        })
+    }
 
-        false
+    fn contains_point_callout(&self, x: i32, y: i32) -> bool {
+        CalloutShape::of(self).is_some_and(|shape| {
+            shape.contains(x, y, (self.thickness + defaults::CLICK_TOLERANCE) as f64)
+        })
     }
 
     pub fn resize(&mut self, new_rect: Rect) {
         match self.tool {
-            DrawingTool::Rectangle | DrawingTool::Circle => self.resize_two_point_shape(new_rect),
+            DrawingTool::Rectangle
+            | DrawingTool::Circle
+            | DrawingTool::Mosaic
+            | DrawingTool::Blur => self.resize_two_point_shape(new_rect),
             DrawingTool::Arrow => self.resize_arrow(new_rect),
-            DrawingTool::Pen => self.resize_freeform(new_rect),
-            DrawingTool::Text => self.resize_text(new_rect),
+            DrawingTool::Pen | DrawingTool::Highlighter => self.resize_freeform(new_rect),
+            // A callout's tail keeps pointing where it was.
+            DrawingTool::Text | DrawingTool::Callout => self.resize_text(new_rect),
+            DrawingTool::Step => {
+                // Markers keep their size; only the centre follows the new rect.
+                if let Some(center) = self.points.first_mut() {
+                    *center = Point::new(
+                        (new_rect.left + new_rect.right) / 2,
+                        (new_rect.top + new_rect.bottom) / 2,
+                    );
+                    self.update_step_bounds();
+                }
+                return;
+            }
             _ => {}
         }
         self.rect = new_rect;
@@ -364,27 +571,20 @@ impl DrawingElement {
             return;
         }
 
-        let old_width = self.rect.width().max(1);
-        let old_height = self.rect.height().max(1);
-        let new_width = new_rect.width();
-        let new_height = new_rect.height();
-
-        let old_start = self.points[0];
-        let old_end = self.points[1];
-
-        let start_rel_x = (old_start.x - self.rect.left) as f64 / old_width as f64;
-        let start_rel_y = (old_start.y - self.rect.top) as f64 / old_height as f64;
-        let end_rel_x = (old_end.x - self.rect.left) as f64 / old_width as f64;
-        let end_rel_y = (old_end.y - self.rect.top) as f64 / old_height as f64;
+        let old_width = self.rect.width().max(1) as f64;
+        let old_height = self.rect.height().max(1) as f64;
+        let new_width = new_rect.width() as f64;
+        let new_height = new_rect.height() as f64;
 
-        self.points[0] = Point::new(
-            new_rect.left + (start_rel_x * new_width as f64) as i32,
-            new_rect.top + (start_rel_y * new_height as f64) as i32,
-        );
-        self.points[1] = Point::new(
-            new_rect.left + (end_rel_x * new_width as f64) as i32,
-            new_rect.top + (end_rel_y * new_height as f64) as i32,
-        );
+        // End points and the bend point keep their place relative to the bounds.
+        for point in &mut self.points {
+            let rel_x = (point.x - self.rect.left) as f64 / old_width;
+            let rel_y = (point.y - self.rect.top) as f64 / old_height;
+            *point = Point::new(
+                new_rect.left + (rel_x * new_width) as i32,
+                new_rect.top + (rel_y * new_height) as i32,
+            );
+        }
     }
 
     fn resize_freeform(&mut self, new_rect: Rect) {
@@ -426,12 +626,33 @@ impl DrawingElement {
     }
 
     pub fn get_bounding_rect(&self) -> Rect {
+        if self.is_rotated() {
+            let center = self.rect.center();
+            let Rect {
+                left,
+                top,
+                right,
+                bottom,
+            } = self.rect;
+            let corners = [(left, top), (right, top), (right, bottom), (left, bottom)]
+                .map(|(x, y)| rotate_point(Point::new(x, y), center, self.rotation));
+            return Rect::new(
+                corners.iter().map(|p| p.x).min().unwrap_or(left),
+                corners.iter().map(|p| p.y).min().unwrap_or(top),
+                corners.iter().map(|p| p.x).max().unwrap_or(right),
+                corners.iter().map(|p| p.y).max().unwrap_or(bottom),
+            );
+        }
         match self.tool {
             DrawingTool::Rectangle
             | DrawingTool::Circle
             | DrawingTool::Arrow
-            | DrawingTool::Text => self.rect,
-            DrawingTool::Pen => {
+            | DrawingTool::Text
+            | DrawingTool::Mosaic
+            | DrawingTool::Blur
+            | DrawingTool::Step => self.rect,
+            DrawingTool::Callout => self.callout_bounds(),
+            DrawingTool::Pen | DrawingTool::Highlighter => {
                 if self.points.is_empty() {
                     return Rect::default();
                 }
@@ -453,6 +674,21 @@ impl DrawingElement {
         }
     }
 
+    /// Box of a callout together with the tip of its tail.
+    fn callout_bounds(&self) -> Rect {
+        let Some(tip) = self.points.get(2) else {
+            return self.rect;
+        };
+        // The outline meets at a sharp point, so its stroke reaches past the tip.
+        let margin = self.thickness.ceil() as i32 * 2;
+        Rect::new(
+            self.rect.left.min(tip.x - margin),
+            self.rect.top.min(tip.y - margin),
+            self.rect.right.max(tip.x + margin),
+            self.rect.bottom.max(tip.y + margin),
+        )
+    }
+
     pub fn add_point(&mut self, x: i32, y: i32) {
         self.points.push(Point::new(x, y));
     }
@@ -508,13 +744,36 @@ mod tests {
         element.points = vec![super::Point::new(10, 10), super::Point::new(100, 100)];
         element.update_bounding_rect();
 
-        assert!(element.contains_point(50, 50));
+        // Outline-only: the stroke is hit, the hollow middle is not.
         assert!(element.contains_point(10, 10));
         assert!(element.contains_point(100, 100));
-        assert!(!element.contains_point(5, 5));
+        assert!(element.contains_point(55, 104));
+        assert!(!element.contains_point(50, 50));
+        assert!(!element.contains_point(0, 0));
+        assert!(!element.contains_point(150, 150));
+
+        element.fill = Some(super::Color::rgb(0.0, 0.0, 1.0));
+        assert!(element.contains_point(50, 50));
         assert!(!element.contains_point(150, 150));
     }
 
+    #[test]
+    fn test_element_contains_point_ellipse() {
+        let mut element = super::DrawingElement::new(super::DrawingTool::Circle);
+        element.points = vec![super::Point::new(0, 0), super::Point::new(200, 100)];
+        element.update_bounding_rect();
+
+        assert!(element.contains_point(200, 50));
+        assert!(element.contains_point(100, 2));
+        assert!(!element.contains_point(100, 50));
+        // The box corner is well outside the ellipse.
+        assert!(!element.contains_point(5, 5));
+
+        element.fill = Some(super::Color::rgb(0.0, 0.0, 1.0).with_alpha(0.3));
+        assert!(element.contains_point(100, 50));
+        assert!(!element.contains_point(5, 5));
+    }
+
     #[test]
     fn test_element_move_by() {
         let mut element = super::DrawingElement::new(super::DrawingTool::Rectangle);
@@ -559,6 +818,56 @@ mod tests {
         assert_eq!(element.font_size, super::defaults::MIN_FONT_SIZE);
     }
 
+    #[test]
+    fn test_mosaic_behaves_like_filled_rect() {
+        let mut element = super::DrawingElement::new(super::DrawingTool::Mosaic);
+        element.points = vec![super::Point::new(40, 40), super::Point::new(10, 10)];
+        element.update_bounding_rect();
+
+        assert_eq!(
+            element.get_bounding_rect(),
+            super::Rect::new(10, 10, 40, 40)
+        );
+        assert!(element.contains_point(25, 25));
+        assert!(!element.contains_point(45, 25));
+
+        element.resize(super::Rect::new(0, 0, 20, 20));
+        assert_eq!(element.points[1], super::Point::new(20, 20));
+
+        element.set_block_size(1000);
+        assert_eq!(element.block_size, super::defaults::MAX_MOSAIC_BLOCK_SIZE);
+    }
+
+    #[test]
+    fn test_step_marker_is_a_fixed_circle() {
+        let mut element = super::DrawingElement::new(super::DrawingTool::Step);
+        element.add_point(100, 100);
+        element.update_bounding_rect();
+
+        let r = super::defaults::STEP_RADIUS;
+        assert_eq!(
+            element.get_bounding_rect(),
+            super::Rect::new(100 - r, 100 - r, 100 + r, 100 + r)
+        );
+        assert!(element.contains_point(100, 100));
+        assert!(element.contains_point(100 + r, 100));
+        // Bounding-box corners lie outside the circle.
+        assert!(!element.contains_point(100 + r, 100 + r));
+    }
+
+    #[test]
+    fn test_blur_radius_is_clamped() {
+        let mut element = super::DrawingElement::new(super::DrawingTool::Blur);
+        element.points = vec![super::Point::new(0, 0), super::Point::new(30, 30)];
+        element.update_bounding_rect();
+        assert!(element.contains_point(15, 15));
+
+        element.set_blur_radius(0.0);
+        assert_eq!(element.blur_radius, super::defaults::MIN_BLUR_RADIUS);
+        element.set_blur_radius(500.0);
+        assert_eq!(element.blur_radius, super::defaults::MAX_BLUR_RADIUS);
+    }
+
     #[test]
     fn test_pen_bounds() {
         let mut element = super::DrawingElement::new(super::DrawingTool::Pen);
@@ -1,33 +1,44 @@
+use crate::arrow::ArrowStyle;
 use crate::element::{Color, DrawingElement, Point, Rect};
+use crate::stroke::StrokeStyle;
 
+/// One undoable edit.
+///
+/// Elements are referred to by [`DrawingElement::id`] rather than by their position in the
+/// element list, so an entry stays valid when other elements are inserted, removed or
+/// restacked before it is undone or redone.
 #[derive(Clone, Debug)]
 pub enum DrawingAction {
     AddElement {
         element: DrawingElement,
-        index: usize,
+        /// Stacking position the element was inserted at.
+        position: usize,
     },
     RemoveElement {
         element: DrawingElement,
-        index: usize,
+        /// Stacking position the element was removed from.
+        position: usize,
     },
     MoveElement {
-        index: usize,
+        id: u64,
         dx: i32,
         dy: i32,
         old_points: Vec<Point>,
         old_rect: Rect,
     },
     ResizeElement {
-        index: usize,
+        id: u64,
         old_points: Vec<Point>,
         old_rect: Rect,
         old_font_size: f32,
+        old_blur_radius: f32,
         new_points: Vec<Point>,
         new_rect: Rect,
         new_font_size: f32,
+        new_blur_radius: f32,
     },
     ModifyText {
-        index: usize,
+        id: u64,
         old_text: String,
         new_text: String,
         old_points: Vec<Point>,
@@ -36,11 +47,35 @@ pub enum DrawingAction {
         new_rect: Rect,
     },
     ModifyProperty {
-        index: usize,
+        id: u64,
         old_color: Color,
         old_thickness: f32,
+        old_fill: Option<Color>,
+        old_arrow: ArrowStyle,
+        old_stroke: StrokeStyle,
         new_color: Color,
         new_thickness: f32,
+        new_fill: Option<Color>,
+        new_arrow: ArrowStyle,
+        new_stroke: StrokeStyle,
+    },
+    /// The element was turned about its centre; angles in degrees.
+    RotateElement {
+        id: u64,
+        old_rotation: f32,
+        new_rotation: f32,
+    },
+    /// Elements joined a group (`new_group` is `Some`) or left their groups (`None`).
+    SetGroup {
+        ids: Vec<u64>,
+        /// Group of each element in `ids` before the change.
+        old_groups: Vec<Option<u64>>,
+        new_group: Option<u64>,
+    },
+    /// The stacking order changed. Both lists hold every element id, bottom first.
+    Restack {
+        old_order: Vec<u64>,
+        new_order: Vec<u64>,
     },
     Compound {
         actions: Vec<DrawingAction>,
@@ -48,33 +83,44 @@ pub enum DrawingAction {
 }
 
 impl DrawingAction {
-    pub fn affected_indices(&self) -> Vec<usize> {
+    pub fn affected_ids(&self) -> Vec<u64> {
         match self {
-            DrawingAction::AddElement { index, .. } => vec![*index],
-            DrawingAction::RemoveElement { index, .. } => vec![*index],
-            DrawingAction::MoveElement { index, .. } => vec![*index],
-            DrawingAction::ResizeElement { index, .. } => vec![*index],
-            DrawingAction::ModifyText { index, .. } => vec![*index],
-            DrawingAction::ModifyProperty { index, .. } => vec![*index],
+            DrawingAction::AddElement { element, .. } => vec![element.id],
+            DrawingAction::RemoveElement { element, .. } => vec![element.id],
+            DrawingAction::MoveElement { id, .. } => vec![*id],
+            DrawingAction::ResizeElement { id, .. } => vec![*id],
+            DrawingAction::ModifyText { id, .. } => vec![*id],
+            DrawingAction::ModifyProperty { id, .. } => vec![*id],
+            DrawingAction::RotateElement { id, .. } => vec![*id],
+            DrawingAction::SetGroup { ids, .. } => ids.clone(),
+            DrawingAction::Restack {
+                old_order,
+                new_order,
+            } => new_order
+                .iter()
+                .zip(old_order)
+                .filter(|(new, old)| new != old)
+                .map(|(new, _)| *new)
+                .collect(),
             DrawingAction::Compound { actions } => {
-                actions.iter().flat_map(|a| a.affected_indices()).collect()
+                actions.iter().flat_map(|a| a.affected_ids()).collect()
             }
         }
     }
 }
 
+/// A recorded action together with the ids of the elements selected around it.
 #[derive(Clone, Debug)]
 pub struct HistoryEntry {
     pub action: DrawingAction,
-    pub selected_before: Option<usize>,
-    pub selected_after: Option<usize>,
+    pub selected_before: Vec<u64>,
+    pub selected_after: Vec<u64>,
 }
 
 #[derive(Clone, Debug)]
 pub struct HistoryState {
     pub elements: Vec<DrawingElement>,
-    pub selected_element: Option<usize>,
-    pub changed_indices: Vec<usize>,
+    pub changed_ids: Vec<u64>,
 }
 
 pub struct ActionHistory {
@@ -103,8 +149,8 @@ impl ActionHistory {
     pub fn record_action(
         &mut self,
         action: DrawingAction,
-        selected_before: Option<usize>,
-        selected_after: Option<usize>,
+        selected_before: Vec<u64>,
+        selected_after: Vec<u64>,
     ) {
         if self.current_position < self.action_stack.len() {
             self.action_stack.truncate(self.current_position);
@@ -125,32 +171,31 @@ impl ActionHistory {
         }
     }
 
-    pub fn undo_action(&mut self) -> Option<(DrawingAction, Option<usize>)> {
+    pub fn undo_action(&mut self) -> Option<(DrawingAction, Vec<u64>)> {
         if self.current_position == 0 {
             return None;
         }
 
         self.current_position -= 1;
         let entry = &self.action_stack[self.current_position];
-        Some((entry.action.clone(), entry.selected_before))
+        Some((entry.action.clone(), entry.selected_before.clone()))
     }
 
-    pub fn redo_action(&mut self) -> Option<(DrawingAction, Option<usize>)> {
+    pub fn redo_action(&mut self) -> Option<(DrawingAction, Vec<u64>)> {
         if self.current_position >= self.action_stack.len() {
             return None;
         }
 
         let entry = &self.action_stack[self.current_position];
         self.current_position += 1;
-        Some((entry.action.clone(), entry.selected_after))
+        Some((entry.action.clone(), entry.selected_after.clone()))
     }
 
-    pub fn save_state(&mut self, elements: &[DrawingElement], selected_element: Option<usize>) {
+    pub fn save_state(&mut self, elements: &[DrawingElement]) {
         if self.base_state.is_none() {
             self.base_state = Some(HistoryState {
                 elements: elements.to_vec(),
-                selected_element,
-                changed_indices: vec![],
+                changed_ids: vec![],
             });
         }
     }
@@ -169,11 +214,11 @@ impl ActionHistory {
         self.current_position = 0;
     }
 
-    pub fn get_last_changed_indices(&self) -> Vec<usize> {
+    pub fn get_last_changed_ids(&self) -> Vec<u64> {
         if self.current_position > 0 && self.current_position <= self.action_stack.len() {
             self.action_stack[self.current_position - 1]
                 .action
-                .affected_indices()
+                .affected_ids()
         } else {
             vec![]
         }
@@ -183,11 +228,10 @@ impl ActionHistory {
         self.base_state.as_ref()
     }
 
-    pub fn set_base_state(&mut self, elements: Vec<DrawingElement>, selected: Option<usize>) {
+    pub fn set_base_state(&mut self, elements: Vec<DrawingElement>) {
         self.base_state = Some(HistoryState {
             elements,
-            selected_element: selected,
-            changed_indices: vec![],
+            changed_ids: vec![],
         });
     }
 }
@@ -1,4 +1,6 @@
-use crate::{DragMode, DrawingElement, DrawingTool, Point, Rect, defaults};
+use crate::element::rotate_point;
+use crate::{Arrangement, DragMode, DrawingElement, DrawingTool, Point, Rect, defaults};
+use sc_rendering::{Color as RenderColor, DrawStyle, Point as RenderPoint, RenderItem, z_order};
 
 pub const DRAG_THRESHOLD: i32 = 3;
 /// Explicit alias for drawing drag threshold (in pixels).
@@ -38,8 +40,8 @@ impl HandleConfig {
     pub fn for_tool(tool: DrawingTool) -> Self {
         match tool {
             DrawingTool::Arrow => HandleConfig::Endpoints,
-            DrawingTool::Text => HandleConfig::Corners,
-            DrawingTool::Pen => HandleConfig::None,
+            DrawingTool::Text | DrawingTool::Callout => HandleConfig::Corners,
+            DrawingTool::Pen | DrawingTool::Highlighter | DrawingTool::Step => HandleConfig::None,
             _ => HandleConfig::Full,
         }
     }
@@ -122,6 +124,7 @@ pub fn detect_handle_at_position(x: i32, y: i32, rect: &Rect, config: HandleConf
     detect_handle_at_position_with_radius(x, y, rect, config, HANDLE_DETECTION_RADIUS)
 }
 
+/// Handle of an arrow under `(x, y)`: either end, or the bend handle halfway along the shaft.
 pub fn detect_arrow_handle(x: i32, y: i32, points: &[Point]) -> DragMode {
     if points.len() < 2 {
         return DragMode::None;
@@ -144,9 +147,34 @@ pub fn detect_arrow_handle(x: i32, y: i32, points: &[Point]) -> DragMode {
         return DragMode::ResizingBottomRight;
     }
 
+    // A bent arrow passes through its bend point halfway; a straight one through the middle.
+    let bend = points
+        .get(2)
+        .copied()
+        .unwrap_or(Point::new((start.x + end.x) / 2, (start.y + end.y) / 2));
+    let dx3 = x - bend.x;
+    let dy3 = y - bend.y;
+    if dx3 * dx3 + dy3 * dy3 <= radius_sq {
+        return DragMode::BendingArrow;
+    }
+
     DragMode::None
 }
 
+/// Tail handle of a callout under `(x, y)`. The corner handles of its box are found like those
+/// of a text box.
+pub fn detect_callout_tail(x: i32, y: i32, points: &[Point]) -> DragMode {
+    let Some(tip) = points.get(2) else {
+        return DragMode::None;
+    };
+    let (dx, dy) = (x - tip.x, y - tip.y);
+    if dx * dx + dy * dy <= HANDLE_DETECTION_RADIUS * HANDLE_DETECTION_RADIUS {
+        DragMode::MovingCalloutTail
+    } else {
+        DragMode::None
+    }
+}
+
 /// # Arguments
 pub fn calculate_resized_rect(start_rect: Rect, mode: DragMode, dx: i32, dy: i32) -> Rect {
     let mut new_rect = start_rect;
@@ -186,6 +214,124 @@ pub fn calculate_resized_rect(start_rect: Rect, mode: DragMode, dx: i32, dy: i32
     new_rect
 }
 
+/// [`calculate_resized_rect`] that keeps the aspect ratio of `start_rect`, for resizing with
+/// Shift held.
+///
+/// Corner handles scale by whichever side grew more and stay anchored at the opposite corner;
+/// edge handles scale the other side about the centre.
+pub fn calculate_resized_rect_keep_aspect(
+    start_rect: Rect,
+    mode: DragMode,
+    dx: i32,
+    dy: i32,
+) -> Rect {
+    let resized = calculate_resized_rect(start_rect, mode, dx, dy);
+    let (width, height) = (start_rect.width(), start_rect.height());
+    if width <= 0 || height <= 0 {
+        return resized;
+    }
+    let scale_x = resized.width() as f64 / width as f64;
+    let scale_y = resized.height() as f64 / height as f64;
+    let scale = match mode {
+        DragMode::ResizingTopCenter | DragMode::ResizingBottomCenter => scale_y,
+        DragMode::ResizingMiddleLeft | DragMode::ResizingMiddleRight => scale_x,
+        DragMode::ResizingTopLeft
+        | DragMode::ResizingTopRight
+        | DragMode::ResizingBottomRight
+        | DragMode::ResizingBottomLeft => scale_x.max(scale_y),
+        _ => return resized,
+    }
+    .max(0.0);
+
+    let new_width = (width as f64 * scale).round() as i32;
+    let new_height = (height as f64 * scale).round() as i32;
+    let Rect {
+        left,
+        top,
+        right,
+        bottom,
+    } = start_rect;
+    let centered_left = (left + right - new_width) / 2;
+    let centered_top = (top + bottom - new_height) / 2;
+    let (left, top) = match mode {
+        DragMode::ResizingTopLeft => (right - new_width, bottom - new_height),
+        DragMode::ResizingTopRight => (left, bottom - new_height),
+        DragMode::ResizingBottomLeft => (right - new_width, top),
+        DragMode::ResizingTopCenter => (centered_left, bottom - new_height),
+        DragMode::ResizingBottomCenter => (centered_left, top),
+        DragMode::ResizingMiddleLeft => (right - new_width, centered_top),
+        DragMode::ResizingMiddleRight => (left, centered_top),
+        _ => (left, top),
+    };
+    Rect::new(left, top, left + new_width, top + new_height)
+}
+
+/// Offsets that arrange `rects` as asked, one `(dx, dy)` per rect.
+///
+/// Alignment lines the rects up with the matching edge or centre line of their combined bounds.
+/// Distribution keeps the rects in their current order along the axis and spreads them across
+/// the combined bounds with equal gaps, so the outermost ones stay put; it needs at least three.
+pub fn arrange_rects(rects: &[Rect], arrangement: Arrangement) -> Vec<(i32, i32)> {
+    let Some(bounds) = rects.iter().copied().reduce(|a, b| {
+        Rect::new(
+            a.left.min(b.left),
+            a.top.min(b.top),
+            a.right.max(b.right),
+            a.bottom.max(b.bottom),
+        )
+    }) else {
+        return Vec::new();
+    };
+    let center_x = (bounds.left + bounds.right) / 2;
+    let center_y = (bounds.top + bounds.bottom) / 2;
+
+    let distribute = |start: fn(&Rect) -> i32, size: fn(&Rect) -> i32, low: i32, high: i32| {
+        let mut offsets = vec![0; rects.len()];
+        if rects.len() < 3 {
+            return offsets;
+        }
+        let mut order: Vec<usize> = (0..rects.len()).collect();
+        order.sort_by_key(|&i| (start(&rects[i]), start(&rects[i]) + size(&rects[i])));
+        let gaps = (high - low) - rects.iter().map(size).sum::<i32>();
+        let intervals = rects.len() as i32 - 1;
+        let mut position = low;
+        for (step, &i) in order.iter().enumerate() {
+            // Spread the rounding over the gaps instead of piling it into the last one.
+            let gap_before = gaps * step as i32 / intervals;
+            offsets[i] = position + gap_before - start(&rects[i]);
+            position += size(&rects[i]);
+        }
+        offsets
+    };
+
+    match arrangement {
+        Arrangement::DistributeHorizontally => {
+            distribute(|r| r.left, Rect::width, bounds.left, bounds.right)
+                .into_iter()
+                .map(|dx| (dx, 0))
+                .collect()
+        }
+        Arrangement::DistributeVertically => {
+            distribute(|r| r.top, Rect::height, bounds.top, bounds.bottom)
+                .into_iter()
+                .map(|dy| (0, dy))
+                .collect()
+        }
+        _ => rects
+            .iter()
+            .map(|r| match arrangement {
+                Arrangement::AlignLeft => (bounds.left - r.left, 0),
+                Arrangement::AlignCenter => (center_x - (r.left + r.right) / 2, 0),
+                Arrangement::AlignRight => (bounds.right - r.right, 0),
+                Arrangement::AlignTop => (0, bounds.top - r.top),
+                Arrangement::AlignMiddle => (0, center_y - (r.top + r.bottom) / 2),
+                Arrangement::AlignBottom => (0, bounds.bottom - r.bottom),
+                _ => (0, 0),
+            })
+            .collect(),
+    }
+}
+
 /// # Arguments
 /// # Returns
 pub fn calculate_text_proportional_resize(
@@ -280,24 +426,36 @@ pub fn calculate_text_proportional_resize_with_min_font(
     (new_rect, new_font_size)
 }
 
+/// Blur radius after a handle drag turned `start_rect` into `new_rect`.
+///
+/// Corner handles scale the radius with the region (by the mean of the width and height
+/// ratios), the same way they scale text; edge handles only reshape the region.
+pub fn calculate_blur_radius_for_resize(
+    start_rect: Rect,
+    new_rect: Rect,
+    mode: DragMode,
+    start_radius: f32,
+) -> f32 {
+    let is_corner = matches!(
+        mode,
+        DragMode::ResizingTopLeft
+            | DragMode::ResizingTopRight
+            | DragMode::ResizingBottomLeft
+            | DragMode::ResizingBottomRight
+    );
+    if !is_corner {
+        return start_radius;
+    }
+
+    let scale_x = new_rect.width().abs() as f32 / start_rect.width().abs().max(1) as f32;
+    let scale_y = new_rect.height().abs() as f32 / start_rect.height().abs().max(1) as f32;
+    (start_radius * (scale_x + scale_y) / 2.0)
+        .clamp(defaults::MIN_BLUR_RADIUS, defaults::MAX_BLUR_RADIUS)
+}
+
 pub fn point_in_element(x: i32, y: i32, element: &DrawingElement) -> bool {
     match element.tool {
-        DrawingTool::Arrow => {
-            if element.points.len() >= 2 {
-                point_near_line_segment(
-                    x,
-                    y,
-                    element.points[0].x,
-                    element.points[0].y,
-                    element.points[1].x,
-                    element.points[1].y,
-                    (element.thickness + 5.0) as i32,
-                )
-            } else {
-                false
-            }
-        }
-        DrawingTool::Pen => {
+        DrawingTool::Pen | DrawingTool::Highlighter => {
             for i in 0..element.points.len().saturating_sub(1) {
                 if point_near_line_segment(
                     x,
@@ -313,11 +471,13 @@ pub fn point_in_element(x: i32, y: i32, element: &DrawingElement) -> bool {
             }
             false
         }
+        DrawingTool::Arrow
+        | DrawingTool::Rectangle
+        | DrawingTool::Circle
+        | DrawingTool::Callout => element.contains_point(x, y),
         _ => {
-            x >= element.rect.left
-                && x <= element.rect.right
-                && y >= element.rect.top
-                && y <= element.rect.bottom
+            let (x, y) = element.unrotate(x, y);
+            element.rect.contains(x, y)
         }
     }
 }
@@ -416,6 +576,399 @@ pub fn get_handle_positions(rect: &Rect) -> [(i32, i32); 8] {
     ]
 }
 
+/// How far above the top edge the rotation handle sits, in the element's own frame.
+pub const ROTATION_HANDLE_DISTANCE: i32 = 24;
+
+/// Rotation handle of a box turned `rotation` degrees, in screen coordinates.
+pub fn rotation_handle_position(rect: &Rect, rotation: f32) -> Point {
+    let handle = Point::new(
+        (rect.left + rect.right) / 2,
+        rect.top - ROTATION_HANDLE_DISTANCE,
+    );
+    rotate_point(handle, rect.center(), rotation)
+}
+
+/// Handle of `element` under `(x, y)`, taking its rotation into account.
+///
+/// Rotatable elements get [`DragMode::Rotating`] on their rotation handle; resize handles are
+/// looked up in the element's unrotated frame.
+pub fn detect_element_handle(
+    x: i32,
+    y: i32,
+    element: &DrawingElement,
+    config: HandleConfig,
+    radius: i32,
+) -> DragMode {
+    if element.tool.can_rotate() && config != HandleConfig::None {
+        let handle = rotation_handle_position(&element.rect, element.rotation);
+        let (dx, dy) = (x - handle.x, y - handle.y);
+        if dx * dx + dy * dy <= radius * radius {
+            return DragMode::Rotating;
+        }
+    }
+    let (x, y) = element.unrotate(x, y);
+    detect_handle_at_position_with_radius(x, y, &element.rect, config, radius)
+}
+
+/// Rotation after dragging the rotation handle of `rect` from `from` to `to`, starting at
+/// `start_rotation` degrees. With `snap` the result lands on [`ANGLE_SNAP_DEGREES`] steps.
+///
+/// The result is normalised to `-180.0..180.0`.
+pub fn rotation_for_drag(
+    rect: &Rect,
+    start_rotation: f32,
+    from: Point,
+    to: Point,
+    snap: bool,
+) -> f32 {
+    let (cx, cy) = rect.center();
+    let angle = |p: Point| (p.y as f64 - cy).atan2(p.x as f64 - cx).to_degrees();
+    let mut rotation = start_rotation as f64 + angle(to) - angle(from);
+    if snap {
+        rotation = (rotation / ANGLE_SNAP_DEGREES).round() * ANGLE_SNAP_DEGREES;
+    }
+    ((rotation + 180.0).rem_euclid(360.0) - 180.0) as f32
+}
+
+/// Shift to apply after resizing a rotated element from `old` to `new` in its own frame, so
+/// that the edges the handle did not touch stay where they are on screen.
+///
+/// Resizing moves the centre the element turns about; without this the whole element would
+/// swing away from the fixed corner.
+pub fn rotated_resize_offset(old: Rect, new: Rect, rotation: f32) -> (i32, i32) {
+    let (old_x, old_y) = old.center();
+    let (new_x, new_y) = new.center();
+    let (dx, dy) = (new_x - old_x, new_y - old_y);
+    let (sin, cos) = (rotation as f64).to_radians().sin_cos();
+    (
+        (dx * cos - dy * sin - dx).round() as i32,
+        (dx * sin + dy * cos - dy).round() as i32,
+    )
+}
+
+/// Angle step, in degrees, that Shift-constrained lines snap to.
+pub const ANGLE_SNAP_DEGREES: f64 = 15.0;
+
+/// `end` rotated about `start` onto the nearest multiple of `step_degrees`, keeping the length.
+pub fn snap_angle(start: Point, end: Point, step_degrees: f64) -> Point {
+    let dx = (end.x - start.x) as f64;
+    let dy = (end.y - start.y) as f64;
+    let length = dx.hypot(dy);
+    if length == 0.0 {
+        return end;
+    }
+    let step = step_degrees.to_radians();
+    let angle = (dy.atan2(dx) / step).round() * step;
+    Point::new(
+        start.x + (length * angle.cos()).round() as i32,
+        start.y + (length * angle.sin()).round() as i32,
+    )
+}
+
+/// `end` moved so that the drag from `start` spans a square as large as its longer side.
+pub fn square_end(start: Point, end: Point) -> Point {
+    let dx = end.x - start.x;
+    let dy = end.y - start.y;
+    let side = dx.abs().max(dy.abs());
+    let towards = |delta: i32| if delta < 0 { -side } else { side };
+    Point::new(start.x + towards(dx), start.y + towards(dy))
+}
+
+/// End point of a Shift-constrained drag from `start` to `end` with `tool`.
+///
+/// Rectangles and ellipses become square, arrows and freehand lines snap to
+/// [`ANGLE_SNAP_DEGREES`]. With `bounds`, the shape is shrunk rather than bent to stay inside.
+pub fn constrain_drag_end(
+    tool: DrawingTool,
+    start: Point,
+    end: Point,
+    bounds: Option<&Rect>,
+) -> Point {
+    let constrained = match tool {
+        DrawingTool::Rectangle | DrawingTool::Circle => square_end(start, end),
+        DrawingTool::Arrow | DrawingTool::Pen | DrawingTool::Highlighter => {
+            snap_angle(start, end, ANGLE_SNAP_DEGREES)
+        }
+        _ => return end,
+    };
+    let Some(bounds) = bounds else {
+        return constrained;
+    };
+
+    let dx = constrained.x - start.x;
+    let dy = constrained.y - start.y;
+    let room = |delta: i32, low: i32, high: i32, from: i32| match delta {
+        0 => 1.0,
+        d if d > 0 => (high - from) as f64 / d as f64,
+        d => (low - from) as f64 / d as f64,
+    };
+    let t = room(dx, bounds.left, bounds.right, start.x)
+        .min(room(dy, bounds.top, bounds.bottom, start.y))
+        .clamp(0.0, 1.0);
+    Point::new(
+        start.x + (dx as f64 * t) as i32,
+        start.y + (dy as f64 * t) as i32,
+    )
+}
+
+/// Rectangle spanned by a rubber-band drag from `start` to `end`, whichever way it was dragged.
+pub fn rubber_band_rect(start: Point, end: Point) -> Rect {
+    Rect::new(
+        start.x.min(end.x),
+        start.y.min(end.y),
+        start.x.max(end.x),
+        start.y.max(end.y),
+    )
+}
+
+/// Ids of the elements lying entirely inside a rubber band, in stacking order.
+pub fn elements_in_rubber_band(elements: &[DrawingElement], band: &Rect) -> Vec<u64> {
+    elements
+        .iter()
+        .filter(|element| {
+            let bounds = element.get_bounding_rect();
+            bounds.left >= band.left
+                && bounds.top >= band.top
+                && bounds.right <= band.right
+                && bounds.bottom <= band.bottom
+        })
+        .map(|element| element.id)
+        .collect()
+}
+
+/// Shift/Ctrl-click on `id`: add it to the selection, or drop it if it is already selected.
+pub fn toggle_selection(selection: &mut Vec<u64>, id: u64) {
+    if let Some(pos) = selection.iter().position(|&selected| selected == id) {
+        selection.remove(pos);
+    } else {
+        selection.push(id);
+    }
+}
+
+/// Box around all `elements`; the multi-selection handles sit on it.
+pub fn combined_bounds<'a>(elements: impl IntoIterator<Item = &'a DrawingElement>) -> Option<Rect> {
+    elements
+        .into_iter()
+        .map(DrawingElement::get_bounding_rect)
+        .reduce(|a, b| {
+            Rect::new(
+                a.left.min(b.left),
+                a.top.min(b.top),
+                a.right.max(b.right),
+                a.bottom.max(b.bottom),
+            )
+        })
+}
+
+/// Fit `element` into `to` the way it sat in `from`, for resizing several elements at once.
+///
+/// `element` must be in the state it had when `from` was measured. Text also scales its font
+/// by the mean of the width and height ratios, and callouts move the tip of their tail along.
+pub fn scale_element_between(element: &mut DrawingElement, from: Rect, to: Rect) {
+    let scale_x = to.width() as f32 / from.width().max(1) as f32;
+    let scale_y = to.height() as f32 / from.height().max(1) as f32;
+    let map_x = |x: i32| to.left + ((x - from.left) as f32 * scale_x).round() as i32;
+    let map_y = |y: i32| to.top + ((y - from.top) as f32 * scale_y).round() as i32;
+
+    let rect = element.rect;
+    element.resize(Rect::new(
+        map_x(rect.left),
+        map_y(rect.top),
+        map_x(rect.right),
+        map_y(rect.bottom),
+    ));
+    if element.tool == DrawingTool::Callout
+        && let Some(tip) = element.points.get_mut(2)
+    {
+        *tip = Point::new(map_x(tip.x), map_y(tip.y));
+    }
+    if element.tool.is_text() {
+        element.set_font_size(element.font_size * (scale_x + scale_y) / 2.0);
+    }
+}
+
+/// How close, in pixels, an edge or centre line has to come to another one to snap onto it.
+pub const SNAP_TOLERANCE: i32 = 6;
+
+/// Guide line drawn where a dragged element lines up with something.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapGuide {
+    /// Vertical line at `x`, running from `top` to `bottom`.
+    Vertical { x: i32, top: i32, bottom: i32 },
+    /// Horizontal line at `y`, running from `left` to `right`.
+    Horizontal { y: i32, left: i32, right: i32 },
+}
+
+/// Edge or centre line of a snap target, with the span it covers on the other axis.
+struct SnapLine {
+    position: i32,
+    start: i32,
+    end: i32,
+}
+
+fn snap_lines(targets: &[Rect], vertical: bool) -> Vec<SnapLine> {
+    targets
+        .iter()
+        .flat_map(|rect| {
+            let (low, high, start, end) = if vertical {
+                (rect.left, rect.right, rect.top, rect.bottom)
+            } else {
+                (rect.top, rect.bottom, rect.left, rect.right)
+            };
+            [low, (low + high) / 2, high].map(|position| SnapLine {
+                position,
+                start,
+                end,
+            })
+        })
+        .collect()
+}
+
+/// Smallest shift (within `tolerance`) that puts one of `values` on one of `lines`.
+fn snap_offset(values: &[i32], lines: &[SnapLine], tolerance: i32) -> Option<i32> {
+    values
+        .iter()
+        .flat_map(|value| lines.iter().map(move |line| line.position - value))
+        .filter(|offset| offset.abs() <= tolerance)
+        .min_by_key(|offset| offset.abs())
+}
+
+/// Guides for every line that one of `values` sits on, stretched over `start..end` too.
+fn snap_guides(
+    values: &[i32],
+    lines: &[SnapLine],
+    start: i32,
+    end: i32,
+    vertical: bool,
+) -> Vec<SnapGuide> {
+    let mut guides = Vec::new();
+    for value in values {
+        let matching = lines.iter().filter(|line| line.position == *value);
+        let Some((low, high)) = matching
+            .map(|line| (line.start.min(start), line.end.max(end)))
+            .reduce(|(a, b), (c, d)| (a.min(c), b.max(d)))
+        else {
+            continue;
+        };
+        let guide = if vertical {
+            SnapGuide::Vertical {
+                x: *value,
+                top: low,
+                bottom: high,
+            }
+        } else {
+            SnapGuide::Horizontal {
+                y: *value,
+                left: low,
+                right: high,
+            }
+        };
+        if !guides.contains(&guide) {
+            guides.push(guide);
+        }
+    }
+    guides
+}
+
+/// Snap `rect`, being moved or resized with `mode`, onto the edges and centres of `targets`.
+///
+/// A move shifts the whole rect so that one of its edges or its centre lands on a target line;
+/// a resize only pulls the edges that the handle drags. Each axis snaps independently, to the
+/// nearest line within `tolerance`. Returns the adjusted rect and the guides to show.
+pub fn snap_rect(
+    rect: Rect,
+    mode: DragMode,
+    targets: &[Rect],
+    tolerance: i32,
+) -> (Rect, Vec<SnapGuide>) {
+    let vertical_lines = snap_lines(targets, true);
+    let horizontal_lines = snap_lines(targets, false);
+    let center = |low: i32, high: i32| (low + high) / 2;
+
+    let mut snapped = rect;
+    match mode {
+        DragMode::Moving | DragMode::MovingElement => {
+            let xs = [rect.left, center(rect.left, rect.right), rect.right];
+            let ys = [rect.top, center(rect.top, rect.bottom), rect.bottom];
+            let dx = snap_offset(&xs, &vertical_lines, tolerance).unwrap_or(0);
+            let dy = snap_offset(&ys, &horizontal_lines, tolerance).unwrap_or(0);
+            snapped = rect.offset(dx, dy);
+        }
+        _ => {
+            let (moves_left, moves_right, moves_top, moves_bottom) = match mode {
+                DragMode::ResizingTopLeft => (true, false, true, false),
+                DragMode::ResizingTopCenter => (false, false, true, false),
+                DragMode::ResizingTopRight => (false, true, true, false),
+                DragMode::ResizingMiddleRight => (false, true, false, false),
+                DragMode::ResizingBottomRight => (false, true, false, true),
+                DragMode::ResizingBottomCenter => (false, false, false, true),
+                DragMode::ResizingBottomLeft => (true, false, false, true),
+                DragMode::ResizingMiddleLeft => (true, false, false, false),
+                _ => return (rect, Vec::new()),
+            };
+            let pull = |edge: &mut i32, moves: bool, lines: &[SnapLine]| {
+                if moves {
+                    *edge += snap_offset(&[*edge], lines, tolerance).unwrap_or(0);
+                }
+            };
+            pull(&mut snapped.left, moves_left, &vertical_lines);
+            pull(&mut snapped.right, moves_right, &vertical_lines);
+            pull(&mut snapped.top, moves_top, &horizontal_lines);
+            pull(&mut snapped.bottom, moves_bottom, &horizontal_lines);
+        }
+    }
+
+    let xs = [
+        snapped.left,
+        center(snapped.left, snapped.right),
+        snapped.right,
+    ];
+    let ys = [
+        snapped.top,
+        center(snapped.top, snapped.bottom),
+        snapped.bottom,
+    ];
+    let mut guides = snap_guides(&xs, &vertical_lines, snapped.top, snapped.bottom, true);
+    guides.extend(snap_guides(
+        &ys,
+        &horizontal_lines,
+        snapped.left,
+        snapped.right,
+        false,
+    ));
+    (snapped, guides)
+}
+
+/// Guide lines as render items, drawn above the elements and their handles.
+pub fn snap_guide_render_items(guides: &[SnapGuide]) -> Vec<RenderItem> {
+    let style = DrawStyle {
+        stroke_color: RenderColor::rgb(1.0, 0.0, 0.6),
+        fill_color: None,
+        stroke_width: 1.0,
+    };
+    guides
+        .iter()
+        .map(|guide| {
+            let (start, end) = match *guide {
+                SnapGuide::Vertical { x, top, bottom } => (
+                    RenderPoint::new(x as f32 + 0.5, top as f32),
+                    RenderPoint::new(x as f32 + 0.5, bottom as f32),
+                ),
+                SnapGuide::Horizontal { y, left, right } => (
+                    RenderPoint::new(left as f32, y as f32 + 0.5),
+                    RenderPoint::new(right as f32, y as f32 + 0.5),
+                ),
+            };
+            RenderItem::Line {
+                start,
+                end,
+                style: style.clone(),
+                z_order: z_order::ELEMENT_HANDLES,
+            }
+        })
+        .collect()
+}
+
 pub fn detect_handle_with_moving_with_radius(
     x: i32,
     y: i32,
@@ -492,6 +1045,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_callout_tail() {
+        use super::{DragMode, Point, detect_callout_tail};
+
+        let points = [Point::new(0, 0), Point::new(120, 40), Point::new(20, 90)];
+        assert_eq!(
+            detect_callout_tail(22, 87, &points),
+            DragMode::MovingCalloutTail
+        );
+        assert_eq!(detect_callout_tail(0, 0, &points), DragMode::None);
+        assert_eq!(detect_callout_tail(20, 90, &points[..2]), DragMode::None);
+    }
+
+    #[test]
+    fn test_detect_arrow_handle_finds_bend_handle() {
+        use super::{DragMode, Point, detect_arrow_handle};
+
+        let straight = [Point::new(0, 0), Point::new(100, 40)];
+        assert_eq!(
+            detect_arrow_handle(1, 1, &straight),
+            DragMode::ResizingTopLeft
+        );
+        assert_eq!(
+            detect_arrow_handle(52, 18, &straight),
+            DragMode::BendingArrow
+        );
+
+        let bent = [Point::new(0, 0), Point::new(100, 40), Point::new(30, 60)];
+        assert_eq!(detect_arrow_handle(50, 20, &bent), DragMode::None);
+        assert_eq!(detect_arrow_handle(32, 58, &bent), DragMode::BendingArrow);
+        assert_eq!(
+            detect_arrow_handle(99, 41, &bent),
+            DragMode::ResizingBottomRight
+        );
+    }
+
     #[test]
     fn test_update_rect_by_drag() {
         let rect = super::Rect::new(10, 10, 100, 100);
@@ -503,4 +1092,297 @@ mod tests {
             super::update_rect_by_drag(super::DragMode::ResizingBottomRight, 10, 10, rect);
         assert_eq!(resized, super::Rect::new(10, 10, 110, 110));
     }
+
+    #[test]
+    fn test_blur_radius_follows_corner_handles_only() {
+        let start = super::Rect::new(0, 0, 100, 50);
+
+        let grown = super::Rect::new(0, 0, 200, 100);
+        let radius = super::calculate_blur_radius_for_resize(
+            start,
+            grown,
+            super::DragMode::ResizingBottomRight,
+            10.0,
+        );
+        assert_eq!(radius, 20.0);
+
+        let widened = super::Rect::new(0, 0, 200, 50);
+        let radius = super::calculate_blur_radius_for_resize(
+            start,
+            widened,
+            super::DragMode::ResizingMiddleRight,
+            10.0,
+        );
+        assert_eq!(radius, 10.0);
+
+        let collapsed = super::Rect::new(0, 0, 0, 0);
+        let radius = super::calculate_blur_radius_for_resize(
+            start,
+            collapsed,
+            super::DragMode::ResizingTopLeft,
+            10.0,
+        );
+        assert_eq!(radius, super::defaults::MIN_BLUR_RADIUS);
+    }
+
+    fn rectangle(left: i32, top: i32, right: i32, bottom: i32) -> super::DrawingElement {
+        let mut element = super::DrawingElement::new(super::DrawingTool::Rectangle);
+        element.add_point(left, top);
+        element.add_point(right, bottom);
+        element.update_bounding_rect();
+        element
+    }
+
+    #[test]
+    fn test_rubber_band_picks_fully_enclosed_elements() {
+        let inside = rectangle(10, 10, 20, 20);
+        let straddling = rectangle(40, 40, 80, 80);
+        let elements = vec![inside.clone(), straddling];
+
+        let band = super::rubber_band_rect(super::Point::new(50, 50), super::Point::new(0, 0));
+        assert_eq!(band, super::Rect::new(0, 0, 50, 50));
+        assert_eq!(
+            super::elements_in_rubber_band(&elements, &band),
+            vec![inside.id]
+        );
+    }
+
+    #[test]
+    fn test_toggle_selection() {
+        let mut selection = vec![1, 2];
+        super::toggle_selection(&mut selection, 3);
+        assert_eq!(selection, vec![1, 2, 3]);
+        super::toggle_selection(&mut selection, 1);
+        assert_eq!(selection, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_group_scale_keeps_relative_layout() {
+        let a = rectangle(0, 0, 10, 10);
+        let b = rectangle(30, 20, 40, 40);
+        let from = super::combined_bounds([&a, &b]).unwrap();
+        assert_eq!(from, super::Rect::new(0, 0, 40, 40));
+
+        let to = super::calculate_resized_rect(from, super::DragMode::ResizingBottomRight, 40, 0);
+        let (mut a2, mut b2) = (a.clone(), b.clone());
+        super::scale_element_between(&mut a2, from, to);
+        super::scale_element_between(&mut b2, from, to);
+
+        assert_eq!(a2.rect, super::Rect::new(0, 0, 20, 10));
+        assert_eq!(b2.rect, super::Rect::new(60, 20, 80, 40));
+        assert_eq!(super::combined_bounds([&a2, &b2]), Some(to));
+    }
+
+    #[test]
+    fn test_shift_constrained_drag_ends() {
+        use super::{DrawingTool, Point, Rect, constrain_drag_end};
+        let start = Point::new(100, 100);
+
+        assert_eq!(
+            constrain_drag_end(DrawingTool::Rectangle, start, Point::new(40, 130), None),
+            Point::new(40, 160)
+        );
+        // A nearly horizontal arrow lies flat; a nearly diagonal one goes to 45 degrees.
+        assert_eq!(
+            constrain_drag_end(DrawingTool::Arrow, start, Point::new(200, 104), None),
+            Point::new(200, 100)
+        );
+        assert_eq!(
+            constrain_drag_end(DrawingTool::Pen, start, Point::new(170, 172), None),
+            Point::new(171, 171)
+        );
+        assert_eq!(
+            constrain_drag_end(DrawingTool::Text, start, Point::new(7, 9), None),
+            Point::new(7, 9)
+        );
+
+        // Near the edge of the capture the square shrinks instead of getting clipped.
+        let bounds = Rect::new(0, 0, 130, 300);
+        assert_eq!(
+            constrain_drag_end(
+                DrawingTool::Circle,
+                start,
+                Point::new(180, 160),
+                Some(&bounds)
+            ),
+            Point::new(130, 130)
+        );
+    }
+
+    #[test]
+    fn test_resize_keeps_aspect_ratio() {
+        use super::{DragMode, Rect, calculate_resized_rect_keep_aspect};
+        let start = Rect::new(0, 0, 100, 50);
+
+        assert_eq!(
+            calculate_resized_rect_keep_aspect(start, DragMode::ResizingBottomRight, 100, 10),
+            Rect::new(0, 0, 200, 100)
+        );
+        assert_eq!(
+            calculate_resized_rect_keep_aspect(start, DragMode::ResizingTopLeft, 10, -50),
+            Rect::new(-100, -50, 100, 50)
+        );
+        assert_eq!(
+            calculate_resized_rect_keep_aspect(start, DragMode::ResizingBottomCenter, 0, 50),
+            Rect::new(-50, 0, 150, 100)
+        );
+        assert_eq!(
+            calculate_resized_rect_keep_aspect(start, DragMode::ResizingMiddleLeft, 50, 0),
+            Rect::new(50, 12, 100, 37)
+        );
+    }
+
+    #[test]
+    fn test_snap_move_lines_up_edges_and_centres() {
+        use super::{DragMode, Rect, SNAP_TOLERANCE, SnapGuide, snap_rect};
+        let bounds = Rect::new(0, 0, 500, 300);
+        let other = Rect::new(100, 100, 200, 150);
+
+        // Left edge 4px right of the other element's right edge; centre 2px below the capture
+        // centre line.
+        let dragged = Rect::new(204, 142, 244, 162);
+        let (snapped, guides) = snap_rect(
+            dragged,
+            DragMode::MovingElement,
+            &[bounds, other],
+            SNAP_TOLERANCE,
+        );
+        assert_eq!(snapped, Rect::new(200, 140, 240, 160));
+        assert_eq!(
+            guides,
+            vec![
+                SnapGuide::Vertical {
+                    x: 200,
+                    top: 100,
+                    bottom: 160
+                },
+                SnapGuide::Horizontal {
+                    y: 150,
+                    left: 0,
+                    right: 500
+                },
+            ]
+        );
+
+        // Out of reach: nothing moves, nothing is shown.
+        let far = Rect::new(250, 40, 290, 60);
+        assert_eq!(
+            snap_rect(far, DragMode::MovingElement, &[other], SNAP_TOLERANCE),
+            (far, vec![])
+        );
+    }
+
+    #[test]
+    fn test_snap_resize_only_pulls_dragged_edges() {
+        use super::{DragMode, Rect, SNAP_TOLERANCE, snap_rect};
+        let other = Rect::new(100, 100, 200, 150);
+
+        let (snapped, guides) = snap_rect(
+            Rect::new(10, 97, 197, 120),
+            DragMode::ResizingMiddleRight,
+            &[other],
+            SNAP_TOLERANCE,
+        );
+        // The top edge is near 100 too, but a right-edge handle only moves the right edge.
+        assert_eq!(snapped, Rect::new(10, 97, 200, 120));
+        assert_eq!(guides.len(), 1);
+
+        let items = super::snap_guide_render_items(&guides);
+        assert!(matches!(
+            items.as_slice(),
+            [super::RenderItem::Line { start, end, .. }]
+                if start.x == 200.5 && start.y == 97.0 && end.y == 150.0
+        ));
+    }
+
+    #[test]
+    fn test_arrange_rects() {
+        use super::{Arrangement, Rect, arrange_rects};
+        let rects = [
+            Rect::new(10, 0, 30, 10),
+            Rect::new(100, 20, 110, 60),
+            Rect::new(40, 50, 80, 54),
+        ];
+
+        assert_eq!(
+            arrange_rects(&rects, Arrangement::AlignLeft),
+            vec![(0, 0), (-90, 0), (-30, 0)]
+        );
+        assert_eq!(
+            arrange_rects(&rects, Arrangement::AlignBottom),
+            vec![(0, 50), (0, 0), (0, 6)]
+        );
+        // Combined bounds 10..110: centre 60.
+        assert_eq!(
+            arrange_rects(&rects, Arrangement::AlignCenter),
+            vec![(40, 0), (-45, 0), (0, 0)]
+        );
+
+        // Widths 20 + 40 + 10 leave 30 px of gaps across 10..110: 15 each, order kept.
+        assert_eq!(
+            arrange_rects(&rects, Arrangement::DistributeHorizontally),
+            vec![(0, 0), (0, 0), (5, 0)]
+        );
+        // Heights 10 + 40 + 4 in 0..60 leave 6 px: 3 px gaps, ordered by top edge.
+        assert_eq!(
+            arrange_rects(&rects, Arrangement::DistributeVertically),
+            vec![(0, 0), (0, -7), (0, 6)]
+        );
+
+        // Two rects have nothing to distribute between them.
+        assert_eq!(
+            arrange_rects(&rects[..2], Arrangement::DistributeVertically),
+            vec![(0, 0), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_rotated_handles_and_hit_testing() {
+        use super::{
+            DragMode, HANDLE_DETECTION_RADIUS, HandleConfig, ROTATION_HANDLE_DISTANCE,
+            detect_element_handle, point_in_element, rotated_resize_offset, rotation_for_drag,
+            rotation_handle_position,
+        };
+        use crate::{DrawingElement, DrawingTool, Point, Rect};
+
+        let mut element = DrawingElement::new(DrawingTool::Rectangle);
+        element.add_point(0, 40);
+        element.add_point(100, 60);
+        element.update_bounding_rect();
+        element.rotation = 90.0;
+        let detect = |x, y, element: &DrawingElement| {
+            detect_element_handle(x, y, element, HandleConfig::Full, HANDLE_DETECTION_RADIUS)
+        };
+
+        // A quarter turn stands the 100x20 bar up around (50, 50).
+        assert!(point_in_element(50, 5, &element));
+        assert!(element.contains_point(52, 95));
+        assert!(!point_in_element(10, 50, &element));
+        assert_eq!(element.get_bounding_rect(), Rect::new(40, 0, 60, 100));
+
+        // The handle above the top edge now points right.
+        let handle = rotation_handle_position(&element.rect, element.rotation);
+        assert_eq!(handle, Point::new(60 + ROTATION_HANDLE_DISTANCE, 50));
+        assert_eq!(detect(handle.x, handle.y, &element), DragMode::Rotating);
+        // The old top-left corner is now at the top right.
+        assert_eq!(detect(60, 0, &element), DragMode::ResizingTopLeft);
+        assert_eq!(detect(0, 40, &element), DragMode::None);
+
+        // Dragging the handle a quarter turn further, and snapping to 15 degree steps.
+        let center = Point::new(50, 50);
+        let below = Point::new(center.x, center.y + 30);
+        assert_eq!(
+            rotation_for_drag(&element.rect, 90.0, handle, below, false),
+            180.0 - 360.0
+        );
+        let near = Point::new(center.x + 30, center.y + 3);
+        assert_eq!(
+            rotation_for_drag(&element.rect, 90.0, handle, near, true),
+            90.0
+        );
+
+        // Widening to the right keeps the left edge in place: the centre moves down instead.
+        let (dx, dy) = rotated_resize_offset(element.rect, Rect::new(0, 40, 140, 60), 90.0);
+        assert_eq!((dx, dy), (-20, 20));
+    }
 }
@@ -1,7 +1,15 @@
+pub mod arrow;
+pub mod blend;
+pub mod callout;
+pub mod clipboard;
+pub mod cursor;
 pub mod element;
 pub mod history;
 pub mod interaction;
 pub mod manager;
+pub mod raster;
+pub mod redaction;
+pub mod stroke;
 pub mod types;
 
 #[cfg(feature = "windows")]
@@ -9,16 +17,29 @@ pub mod windows;
 
 pub use sc_rendering;
 
-pub use element::{Color, DrawingElement, Point, Rect, defaults};
+pub use arrow::{ArrowHead, ArrowMarker, ArrowPath, ArrowShape, ArrowStyle, head_length};
+pub use callout::{CalloutShape, OutlineSegment};
+pub use clipboard::{ElementClipboard, PASTE_OFFSET, duplicate_elements};
+pub use cursor::{CursorImage, CursorSource, composite_cursor};
+pub use element::{
+    Color, DrawingElement, Point, Rect, defaults, new_group_id, reserve_element_id, rotate_point,
+};
 pub use history::ActionHistory;
 pub use history::DrawingAction;
 pub use interaction::{
-    DRAG_THRESHOLD, DRAWING_DRAG_THRESHOLD, HANDLE_DETECTION_RADIUS, HandleConfig,
-    calculate_resized_rect, calculate_text_proportional_resize,
-    calculate_text_proportional_resize_with_min_font, clamp_to_rect, detect_arrow_handle,
+    ANGLE_SNAP_DEGREES, DRAG_THRESHOLD, DRAWING_DRAG_THRESHOLD, HANDLE_DETECTION_RADIUS,
+    HandleConfig, SNAP_TOLERANCE, SnapGuide, arrange_rects, calculate_blur_radius_for_resize,
+    calculate_resized_rect, calculate_resized_rect_keep_aspect, calculate_text_proportional_resize,
+    calculate_text_proportional_resize_with_min_font, clamp_to_rect, combined_bounds,
+    constrain_drag_end, detect_arrow_handle, detect_callout_tail, detect_element_handle,
     detect_handle_at_position, detect_handle_at_position_with_radius, detect_handle_with_moving,
-    detect_handle_with_moving_with_radius, get_handle_positions, is_drag_threshold_exceeded,
-    is_rect_valid, point_in_element, update_rect_by_drag,
+    detect_handle_with_moving_with_radius, elements_in_rubber_band, get_handle_positions,
+    is_drag_threshold_exceeded, is_rect_valid, point_in_element, rotated_resize_offset,
+    rotation_for_drag, rubber_band_rect, scale_element_between, snap_angle,
+    snap_guide_render_items, snap_rect, square_end, toggle_selection, update_rect_by_drag,
 };
 pub use manager::ElementManager;
-pub use types::{DragMode, DrawingTool, ElementInteractionMode};
+pub use raster::{ChannelOrder, PixelBuffer};
+pub use redaction::{RedactionPatch, RedactionSource};
+pub use stroke::{DashStyle, LineCap, LineJoin, StrokeStyle};
+pub use types::{Arrangement, DragMode, DrawingTool, ElementInteractionMode, ZOrder};
@@ -1,6 +1,10 @@
-use crate::element::{DrawingElement, Rect};
+use crate::arrow::ArrowStyle;
+use crate::element::{self, Color, DrawingElement, Rect};
 use crate::history::DrawingAction;
-use crate::types::DrawingTool;
+use crate::interaction::{arrange_rects, combined_bounds};
+use crate::redaction::{self, RedactionSource};
+use crate::stroke::StrokeStyle;
+use crate::types::{Arrangement, DrawingTool, ZOrder};
 
 pub struct ElementManager {
     elements: Vec<DrawingElement>,
@@ -40,28 +44,47 @@ impl ElementManager {
 
     pub fn add_element(&mut self, element: DrawingElement) {
         if self.elements.len() >= self.max_elements {
-            if let Some(pos) = self
-                .elements
-                .iter()
-                .position(|e| e.tool != DrawingTool::Text)
-            {
+            if let Some(pos) = self.elements.iter().position(|e| !e.tool.is_text()) {
                 self.elements.remove(pos);
             } else {
                 self.elements.remove(0);
             }
         }
         self.elements.push(element);
+        self.renumber_steps();
     }
 
     pub fn remove_element(&mut self, index: usize) -> bool {
         if index < self.elements.len() {
             self.elements.remove(index);
+            self.renumber_steps();
             true
         } else {
             false
         }
     }
 
+    /// Remove the element with the given id, returning it if it was present.
+    pub fn remove_element_by_id(&mut self, id: u64) -> Option<DrawingElement> {
+        let index = self.index_of(id)?;
+        let element = self.elements.remove(index);
+        self.renumber_steps();
+        Some(element)
+    }
+
+    /// Current stacking position of the element with the given id.
+    pub fn index_of(&self, id: u64) -> Option<usize> {
+        self.elements.iter().position(|e| e.id == id)
+    }
+
+    pub fn get_element_by_id(&self, id: u64) -> Option<&DrawingElement> {
+        self.elements.iter().find(|e| e.id == id)
+    }
+
+    pub fn get_element_by_id_mut(&mut self, id: u64) -> Option<&mut DrawingElement> {
+        self.elements.iter_mut().find(|e| e.id == id)
+    }
+
     pub fn get_element_at_position(&self, x: i32, y: i32) -> Option<usize> {
         for (index, element) in self.elements.iter().enumerate().rev() {
             if element.contains_point(x, y) {
@@ -78,7 +101,8 @@ impl ElementManager {
         selection_rect: Option<Rect>,
     ) -> Option<usize> {
         for (index, element) in self.elements.iter().enumerate().rev() {
-            if element.tool == DrawingTool::Pen {
+            // Loose freehand strokes are not clickable, but a grouped one picks its group.
+            if element.tool.is_freeform() && element.group_id.is_none() {
                 continue;
             }
             if element.contains_point(x, y) {
@@ -113,6 +137,22 @@ impl ElementManager {
         }
     }
 
+    /// Select exactly the elements with the given ids.
+    pub fn set_selected_ids(&mut self, ids: &[u64]) {
+        for element in &mut self.elements {
+            element.selected = ids.contains(&element.id);
+        }
+    }
+
+    /// Ids of the selected elements, in stacking order.
+    pub fn selected_ids(&self) -> Vec<u64> {
+        self.elements
+            .iter()
+            .filter(|e| e.selected)
+            .map(|e| e.id)
+            .collect()
+    }
+
     pub fn get_elements(&self) -> &Vec<DrawingElement> {
         &self.elements
     }
@@ -130,8 +170,18 @@ impl ElementManager {
         }
     }
 
+    /// Replace all elements, e.g. from history or a loaded project.
     pub fn restore_state(&mut self, elements: Vec<DrawingElement>) {
+        if let Some(max_id) = elements
+            .iter()
+            .flat_map(|e| [Some(e.id), e.group_id])
+            .flatten()
+            .max()
+        {
+            element::reserve_element_id(max_id);
+        }
         self.elements = elements;
+        self.renumber_steps();
     }
 
     pub fn clear(&mut self) {
@@ -145,138 +195,497 @@ impl ElementManager {
     pub fn insert_element(&mut self, index: usize, element: DrawingElement) {
         if index <= self.elements.len() {
             self.elements.insert(index, element);
+            self.renumber_steps();
         }
     }
 
-    pub fn apply_undo(&mut self, action: &DrawingAction) {
-        match action {
-            DrawingAction::AddElement { index, .. } => {
-                if *index < self.elements.len() {
-                    self.elements.remove(*index);
+    /// Ids of the elements that act together with `id`: its whole group, or just `id` itself.
+    pub fn group_members(&self, id: u64) -> Vec<u64> {
+        match self.get_element_by_id(id).and_then(|e| e.group_id) {
+            Some(group) => self
+                .elements
+                .iter()
+                .filter(|e| e.group_id == Some(group))
+                .map(|e| e.id)
+                .collect(),
+            None => vec![id],
+        }
+    }
+
+    /// `ids` plus every element grouped with one of them, in stacking order.
+    pub fn expand_to_groups(&self, ids: &[u64]) -> Vec<u64> {
+        let groups: Vec<u64> = ids
+            .iter()
+            .filter_map(|&id| self.get_element_by_id(id)?.group_id)
+            .collect();
+        self.elements
+            .iter()
+            .filter(|e| ids.contains(&e.id) || e.group_id.is_some_and(|g| groups.contains(&g)))
+            .map(|e| e.id)
+            .collect()
+    }
+
+    /// Put the elements (and the groups they are in) into one new group.
+    ///
+    /// Returns the action to record, or `None` if that would leave fewer than two elements.
+    pub fn group_elements(&mut self, ids: &[u64]) -> Option<DrawingAction> {
+        let ids = self.expand_to_groups(ids);
+        if ids.len() < 2 {
+            return None;
+        }
+        let action = DrawingAction::SetGroup {
+            old_groups: ids.iter().map(|&id| self.group_of(id)).collect(),
+            ids,
+            new_group: Some(element::new_group_id()),
+        };
+        self.replay(&action);
+        Some(action)
+    }
+
+    /// Dissolve every group one of the elements belongs to.
+    ///
+    /// Returns the action to record, or `None` if none of them was grouped.
+    pub fn ungroup_elements(&mut self, ids: &[u64]) -> Option<DrawingAction> {
+        let ids: Vec<u64> = self
+            .expand_to_groups(ids)
+            .into_iter()
+            .filter(|&id| self.group_of(id).is_some())
+            .collect();
+        if ids.is_empty() {
+            return None;
+        }
+        let action = DrawingAction::SetGroup {
+            old_groups: ids.iter().map(|&id| self.group_of(id)).collect(),
+            ids,
+            new_group: None,
+        };
+        self.replay(&action);
+        Some(action)
+    }
+
+    /// Move the elements (and the groups they are in) in the stacking order.
+    ///
+    /// Returns the action to record, or `None` if nothing moved.
+    pub fn restack_elements(&mut self, ids: &[u64], z_order: ZOrder) -> Option<DrawingAction> {
+        let ids = self.expand_to_groups(ids);
+        let old_order: Vec<u64> = self.elements.iter().map(|e| e.id).collect();
+        let mut new_order = old_order.clone();
+        let moving = |id: &u64| ids.contains(id);
+        match z_order {
+            ZOrder::ToFront => new_order.sort_by_key(moving),
+            ZOrder::ToBack => new_order.sort_by_key(|id| !moving(id)),
+            // Swapping from the far end lets a run of moving elements advance as a block.
+            ZOrder::Forward => {
+                for i in (0..new_order.len().saturating_sub(1)).rev() {
+                    if moving(&new_order[i]) && !moving(&new_order[i + 1]) {
+                        new_order.swap(i, i + 1);
+                    }
                 }
             }
-            DrawingAction::RemoveElement { element, index } => {
-                if *index <= self.elements.len() {
-                    self.elements.insert(*index, element.clone());
+            ZOrder::Backward => {
+                for i in 1..new_order.len() {
+                    if moving(&new_order[i]) && !moving(&new_order[i - 1]) {
+                        new_order.swap(i, i - 1);
+                    }
                 }
             }
+        }
+        if new_order == old_order {
+            return None;
+        }
+        let action = DrawingAction::Restack {
+            old_order,
+            new_order,
+        };
+        self.replay(&action);
+        Some(action)
+    }
+
+    /// Give every fillable element in `ids` the fill chosen by `fill`, as one action.
+    ///
+    /// Returns the action to record, or `None` if no fill changed.
+    pub fn set_fill(
+        &mut self,
+        ids: &[u64],
+        fill: impl Fn(&DrawingElement) -> Option<Color>,
+    ) -> Option<DrawingAction> {
+        self.modify_properties(ids, |element| {
+            if element.tool.can_fill() {
+                element.fill = fill(element);
+            }
+        })
+    }
+
+    /// Give every arrow in `ids` the markers chosen by `style`, as one action.
+    ///
+    /// Returns the action to record, or `None` if no arrow changed.
+    pub fn set_arrow_style(
+        &mut self,
+        ids: &[u64],
+        style: impl Fn(&DrawingElement) -> ArrowStyle,
+    ) -> Option<DrawingAction> {
+        self.modify_properties(ids, |element| {
+            if element.tool == DrawingTool::Arrow {
+                element.arrow = style(element);
+            }
+        })
+    }
+
+    /// Give every element in `ids` that draws an outline the style chosen by `style`, as one
+    /// action.
+    ///
+    /// Returns the action to record, or `None` if no style changed.
+    pub fn set_stroke_style(
+        &mut self,
+        ids: &[u64],
+        style: impl Fn(&DrawingElement) -> StrokeStyle,
+    ) -> Option<DrawingAction> {
+        self.modify_properties(ids, |element| {
+            if element.tool.uses_stroke_style() {
+                element.stroke = style(element);
+            }
+        })
+    }
+
+    /// Apply `modify` to copies of the elements in `ids` and turn the changed ones into a
+    /// single property action, which is then applied.
+    fn modify_properties(
+        &mut self,
+        ids: &[u64],
+        modify: impl Fn(&mut DrawingElement),
+    ) -> Option<DrawingAction> {
+        let mut actions = Vec::new();
+        for &id in ids {
+            let Some(old) = self.get_element_by_id(id) else {
+                continue;
+            };
+            let mut new = old.clone();
+            modify(&mut new);
+            if new.color == old.color
+                && new.thickness == old.thickness
+                && new.fill == old.fill
+                && new.arrow == old.arrow
+                && new.stroke == old.stroke
+            {
+                continue;
+            }
+            actions.push(DrawingAction::ModifyProperty {
+                id,
+                old_color: old.color,
+                old_thickness: old.thickness,
+                old_fill: old.fill,
+                old_arrow: old.arrow,
+                old_stroke: old.stroke.clone(),
+                new_color: new.color,
+                new_thickness: new.thickness,
+                new_fill: new.fill,
+                new_arrow: new.arrow,
+                new_stroke: new.stroke,
+            });
+        }
+        let action = match actions.len() {
+            0 => return None,
+            1 => actions.pop()?,
+            _ => DrawingAction::Compound { actions },
+        };
+        self.replay(&action);
+        Some(action)
+    }
+
+    /// Align or distribute the elements. A group is arranged as one block.
+    ///
+    /// Returns the action to record, or `None` if there are fewer than two blocks or nothing
+    /// moved.
+    pub fn arrange_elements(
+        &mut self,
+        ids: &[u64],
+        arrangement: Arrangement,
+    ) -> Option<DrawingAction> {
+        let mut blocks: Vec<Vec<u64>> = Vec::new();
+        for id in self.expand_to_groups(ids) {
+            let group = self.group_of(id);
+            match blocks
+                .iter_mut()
+                .find(|block| group.is_some() && self.group_of(block[0]) == group)
+            {
+                Some(block) => block.push(id),
+                None => blocks.push(vec![id]),
+            }
+        }
+        if blocks.len() < 2 {
+            return None;
+        }
+
+        let bounds: Vec<Rect> = blocks
+            .iter()
+            .filter_map(|block| {
+                combined_bounds(block.iter().filter_map(|&id| self.get_element_by_id(id)))
+            })
+            .collect();
+        let mut actions = Vec::new();
+        for (block, (dx, dy)) in blocks.iter().zip(arrange_rects(&bounds, arrangement)) {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+            for &id in block {
+                if let Some(element) = self.get_element_by_id(id) {
+                    actions.push(DrawingAction::MoveElement {
+                        id,
+                        dx,
+                        dy,
+                        old_points: element.points.clone(),
+                        old_rect: element.rect,
+                    });
+                }
+            }
+        }
+        if actions.is_empty() {
+            return None;
+        }
+        let action = DrawingAction::Compound { actions };
+        self.replay(&action);
+        Some(action)
+    }
+
+    /// Stack the elements in `order` (ids, bottom first). Unlisted elements go on top.
+    fn apply_order(&mut self, order: &[u64]) {
+        self.elements.sort_by_key(|e| {
+            order
+                .iter()
+                .position(|&id| id == e.id)
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    fn group_of(&self, id: u64) -> Option<u64> {
+        self.get_element_by_id(id).and_then(|e| e.group_id)
+    }
+
+    /// Number a new step marker would get.
+    pub fn next_step_number(&self) -> u32 {
+        self.elements
+            .iter()
+            .filter(|e| e.tool == DrawingTool::Step)
+            .count() as u32
+            + 1
+    }
+
+    /// Close the gaps in step numbering left by removed markers.
+    ///
+    /// Markers keep their relative order (by current number, then stacking order) and are
+    /// renumbered from 1. Returns `true` if any number changed.
+    pub fn renumber_steps(&mut self) -> bool {
+        let mut steps: Vec<usize> = (0..self.elements.len())
+            .filter(|&i| self.elements[i].tool == DrawingTool::Step)
+            .collect();
+        steps.sort_by_key(|&i| (self.elements[i].step_number, i));
+
+        let mut changed = false;
+        for (number, index) in (1..).zip(steps) {
+            let element = &mut self.elements[index];
+            changed |= element.step_number != number;
+            element.step_number = number;
+        }
+        changed
+    }
+
+    /// Rebuild stale redaction patches. Returns `true` if any element changed.
+    pub fn refresh_redactions(&mut self, source: Option<&RedactionSource>) -> bool {
+        let mut changed = false;
+        for element in &mut self.elements {
+            changed |= redaction::refresh_patch(element, source);
+        }
+        changed
+    }
+
+    pub fn apply_undo(&mut self, action: &DrawingAction) {
+        self.revert(action);
+        self.renumber_steps();
+    }
+
+    pub fn apply_redo(&mut self, action: &DrawingAction) {
+        self.replay(action);
+        self.renumber_steps();
+    }
+
+    fn revert(&mut self, action: &DrawingAction) {
+        match action {
+            DrawingAction::AddElement { element, .. } => {
+                self.remove_element_by_id(element.id);
+            }
+            DrawingAction::RemoveElement { element, position } => {
+                self.restore_element(*position, element);
+            }
             DrawingAction::MoveElement {
-                index,
+                id,
                 old_points,
                 old_rect,
                 ..
             } => {
-                if let Some(element) = self.elements.get_mut(*index) {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
                     element.points = old_points.clone();
                     element.rect = *old_rect;
                 }
             }
             DrawingAction::ResizeElement {
-                index,
+                id,
                 old_points,
                 old_rect,
                 old_font_size,
+                old_blur_radius,
                 ..
             } => {
-                if let Some(element) = self.elements.get_mut(*index) {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
                     element.points = old_points.clone();
                     element.rect = *old_rect;
                     element.font_size = *old_font_size;
+                    element.blur_radius = *old_blur_radius;
                 }
             }
             DrawingAction::ModifyText {
-                index,
+                id,
                 old_text,
                 old_points,
                 old_rect,
                 ..
             } => {
-                if let Some(element) = self.elements.get_mut(*index) {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
                     element.text = old_text.clone();
                     element.points = old_points.clone();
                     element.rect = *old_rect;
                 }
             }
             DrawingAction::ModifyProperty {
-                index,
+                id,
                 old_color,
                 old_thickness,
+                old_fill,
+                old_arrow,
+                old_stroke,
                 ..
             } => {
-                if let Some(element) = self.elements.get_mut(*index) {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
                     element.color = *old_color;
                     element.thickness = *old_thickness;
+                    element.fill = *old_fill;
+                    element.arrow = *old_arrow;
+                    element.stroke = old_stroke.clone();
+                }
+            }
+            DrawingAction::RotateElement {
+                id, old_rotation, ..
+            } => {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
+                    element.rotation = *old_rotation;
+                }
+            }
+            DrawingAction::Restack { old_order, .. } => self.apply_order(old_order),
+            DrawingAction::SetGroup {
+                ids, old_groups, ..
+            } => {
+                for (id, group) in ids.iter().zip(old_groups) {
+                    if let Some(element) = self.get_element_by_id_mut(*id) {
+                        element.group_id = *group;
+                    }
                 }
             }
             DrawingAction::Compound { actions } => {
                 for action in actions.iter().rev() {
-                    self.apply_undo(action);
+                    self.revert(action);
                 }
             }
         }
     }
 
-    pub fn apply_redo(&mut self, action: &DrawingAction) {
+    fn replay(&mut self, action: &DrawingAction) {
         match action {
-            DrawingAction::AddElement { element, index } => {
-                if *index <= self.elements.len() {
-                    self.elements.insert(*index, element.clone());
-                }
+            DrawingAction::AddElement { element, position } => {
+                self.restore_element(*position, element);
             }
-            DrawingAction::RemoveElement { index, .. } => {
-                if *index < self.elements.len() {
-                    self.elements.remove(*index);
-                }
+            DrawingAction::RemoveElement { element, .. } => {
+                self.remove_element_by_id(element.id);
             }
-            DrawingAction::MoveElement { index, dx, dy, .. } => {
-                if let Some(element) = self.elements.get_mut(*index) {
+            DrawingAction::MoveElement { id, dx, dy, .. } => {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
                     element.move_by(*dx, *dy);
                 }
             }
             DrawingAction::ResizeElement {
-                index,
+                id,
                 new_points,
                 new_rect,
                 new_font_size,
+                new_blur_radius,
                 ..
             } => {
-                if let Some(element) = self.elements.get_mut(*index) {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
                     element.points = new_points.clone();
                     element.rect = *new_rect;
                     element.font_size = *new_font_size;
+                    element.blur_radius = *new_blur_radius;
                 }
             }
             DrawingAction::ModifyText {
-                index,
+                id,
                 new_text,
                 new_points,
                 new_rect,
                 ..
             } => {
-                if let Some(element) = self.elements.get_mut(*index) {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
                     element.text = new_text.clone();
                     element.points = new_points.clone();
                     element.rect = *new_rect;
                 }
             }
             DrawingAction::ModifyProperty {
-                index,
+                id,
                 new_color,
                 new_thickness,
+                new_fill,
+                new_arrow,
+                new_stroke,
                 ..
             } => {
-                if let Some(element) = self.elements.get_mut(*index) {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
                     element.color = *new_color;
                     element.thickness = *new_thickness;
+                    element.fill = *new_fill;
+                    element.arrow = *new_arrow;
+                    element.stroke = new_stroke.clone();
+                }
+            }
+            DrawingAction::RotateElement {
+                id, new_rotation, ..
+            } => {
+                if let Some(element) = self.get_element_by_id_mut(*id) {
+                    element.rotation = *new_rotation;
+                }
+            }
+            DrawingAction::Restack { new_order, .. } => self.apply_order(new_order),
+            DrawingAction::SetGroup { ids, new_group, .. } => {
+                for id in ids {
+                    if let Some(element) = self.get_element_by_id_mut(*id) {
+                        element.group_id = *new_group;
+                    }
                 }
             }
             DrawingAction::Compound { actions } => {
                 for action in actions {
-                    self.apply_redo(action);
+                    self.replay(action);
                 }
             }
         }
     }
+
+    /// Put a removed element back at `position` (clamped to the end of the list). Does nothing
+    /// if an element with the same id is already present.
+    fn restore_element(&mut self, position: usize, element: &DrawingElement) {
+        if self.index_of(element.id).is_none() {
+            let position = position.min(self.elements.len());
+            self.elements.insert(position, element.clone());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -300,6 +709,86 @@ mod tests {
         assert_eq!(manager.count(), 0);
     }
 
+    #[test]
+    fn test_restore_state_reserves_loaded_ids() {
+        let mut loaded = super::DrawingElement::new(super::DrawingTool::Rectangle);
+        loaded.id = u64::MAX / 2;
+
+        let mut manager = super::ElementManager::new();
+        manager.restore_state(vec![loaded]);
+
+        let fresh = super::DrawingElement::new(super::DrawingTool::Rectangle);
+        assert!(fresh.id > u64::MAX / 2);
+    }
+
+    fn step(manager: &super::ElementManager, x: i32) -> super::DrawingElement {
+        let mut element = super::DrawingElement::new(super::DrawingTool::Step);
+        element.add_point(x, 0);
+        element.step_number = manager.next_step_number();
+        element.update_bounding_rect();
+        element
+    }
+
+    fn step_numbers(manager: &super::ElementManager) -> Vec<(i32, u32)> {
+        manager
+            .get_elements()
+            .iter()
+            .filter(|e| e.tool == super::DrawingTool::Step)
+            .map(|e| (e.points[0].x, e.step_number))
+            .collect()
+    }
+
+    #[test]
+    fn test_step_markers_renumber_on_delete_and_undo() {
+        let mut manager = super::ElementManager::new();
+        let mut history = crate::ActionHistory::new();
+
+        for x in [10, 20, 30] {
+            let element = step(&manager, x);
+            let position = manager.count();
+            history.record_action(
+                super::DrawingAction::AddElement {
+                    element: element.clone(),
+                    position,
+                },
+                vec![],
+                vec![],
+            );
+            manager.add_element(element);
+        }
+        manager.add_element(super::DrawingElement::new(super::DrawingTool::Rectangle));
+        assert_eq!(step_numbers(&manager), vec![(10, 1), (20, 2), (30, 3)]);
+
+        let removed = manager.get_elements()[1].clone();
+        history.record_action(
+            super::DrawingAction::RemoveElement {
+                element: removed,
+                position: 1,
+            },
+            vec![],
+            vec![],
+        );
+        manager.remove_element(1);
+        assert_eq!(step_numbers(&manager), vec![(10, 1), (30, 2)]);
+        assert_eq!(manager.next_step_number(), 3);
+
+        let (action, _) = history.undo_action().unwrap();
+        manager.apply_undo(&action);
+        assert_eq!(step_numbers(&manager), vec![(10, 1), (20, 2), (30, 3)]);
+
+        let (action, _) = history.redo_action().unwrap();
+        manager.apply_redo(&action);
+        assert_eq!(step_numbers(&manager), vec![(10, 1), (30, 2)]);
+
+        // Undo everything back to the first placement; the remaining marker starts at 1 again.
+        for _ in 0..3 {
+            let (action, _) = history.undo_action().unwrap();
+            manager.apply_undo(&action);
+        }
+        assert_eq!(step_numbers(&manager), vec![(10, 1)]);
+        assert_eq!(manager.next_step_number(), 2);
+    }
+
     #[test]
     fn test_max_elements_limit() {
         let mut manager = super::ElementManager::with_max_elements(3);
@@ -321,5 +810,551 @@ mod tests {
 
         assert!(!manager.get_elements()[0].selected);
         assert!(manager.get_elements()[1].selected);
+
+        let ids: Vec<u64> = manager.get_elements().iter().map(|e| e.id).collect();
+        manager.set_selected_ids(&ids);
+        assert_eq!(manager.selected_ids(), ids);
+    }
+
+    #[test]
+    fn test_undo_survives_restacking_by_id() {
+        let mut manager = super::ElementManager::new();
+        let mut history = crate::ActionHistory::new();
+        for _ in 0..3 {
+            manager.add_element(super::DrawingElement::new(super::DrawingTool::Rectangle));
+        }
+        let target = manager.get_elements()[2].clone();
+
+        history.record_action(
+            super::DrawingAction::MoveElement {
+                id: target.id,
+                dx: 5,
+                dy: 5,
+                old_points: target.points.clone(),
+                old_rect: target.rect,
+            },
+            vec![],
+            vec![target.id],
+        );
+        manager.get_element_mut(2).unwrap().move_by(5, 5);
+
+        // Something below the moved element disappears without going through history.
+        manager.remove_element(0);
+
+        let (action, selected) = history.undo_action().unwrap();
+        manager.apply_undo(&action);
+        assert!(selected.is_empty());
+        assert_eq!(manager.get_element_by_id(target.id), Some(&target));
+        assert_eq!(manager.index_of(target.id), Some(1));
+    }
+
+    #[test]
+    fn test_undo_of_group_move_restores_whole_selection() {
+        let mut manager = super::ElementManager::new();
+        let mut history = crate::ActionHistory::new();
+        for _ in 0..3 {
+            manager.add_element(super::DrawingElement::new(super::DrawingTool::Rectangle));
+        }
+        let moved: Vec<super::DrawingElement> = manager.get_elements()[..2].to_vec();
+        let ids: Vec<u64> = moved.iter().map(|e| e.id).collect();
+        manager.set_selected_ids(&ids);
+
+        let actions = moved
+            .iter()
+            .map(|e| super::DrawingAction::MoveElement {
+                id: e.id,
+                dx: 10,
+                dy: 0,
+                old_points: e.points.clone(),
+                old_rect: e.rect,
+            })
+            .collect();
+        history.record_action(
+            super::DrawingAction::Compound { actions },
+            ids.clone(),
+            ids.clone(),
+        );
+        for index in 0..2 {
+            manager.get_element_mut(index).unwrap().move_by(10, 0);
+        }
+        manager.set_selected(Some(2));
+
+        let (action, selected) = history.undo_action().unwrap();
+        manager.apply_undo(&action);
+        manager.set_selected_ids(&selected);
+        assert_eq!(manager.selected_ids(), ids);
+        for element in &moved {
+            assert_eq!(
+                manager.get_element_by_id(element.id).unwrap().rect,
+                element.rect
+            );
+        }
+
+        let (_, selected) = history.redo_action().unwrap();
+        assert_eq!(selected, ids);
+    }
+
+    #[test]
+    fn test_group_and_ungroup() {
+        let mut manager = super::ElementManager::new();
+        let mut pen = super::DrawingElement::new(super::DrawingTool::Pen);
+        pen.add_point(0, 0);
+        pen.add_point(20, 0);
+        pen.update_bounding_rect();
+        let box_ = super::DrawingElement::new(super::DrawingTool::Rectangle);
+        let loose = super::DrawingElement::new(super::DrawingTool::Rectangle);
+        let (pen_id, box_id, loose_id) = (pen.id, box_.id, loose.id);
+        for element in [pen, box_, loose] {
+            manager.add_element(element);
+        }
+
+        // A loose freehand stroke cannot be clicked.
+        assert_eq!(manager.get_element_at_position_with_rect(10, 0, None), None);
+
+        let grouped = manager.group_elements(&[pen_id, box_id]).unwrap();
+        assert_eq!(manager.group_members(box_id), vec![pen_id, box_id]);
+        assert_eq!(manager.group_members(loose_id), vec![loose_id]);
+        assert_eq!(
+            manager.expand_to_groups(&[pen_id, loose_id]),
+            vec![pen_id, box_id, loose_id]
+        );
+        assert_eq!(
+            manager.get_element_at_position_with_rect(10, 0, None),
+            Some(0)
+        );
+
+        let ungrouped = manager.ungroup_elements(&[box_id]).unwrap();
+        assert_eq!(manager.group_members(box_id), vec![box_id]);
+        assert!(manager.ungroup_elements(&[box_id]).is_none());
+
+        manager.apply_undo(&ungrouped);
+        assert_eq!(manager.group_members(pen_id), vec![pen_id, box_id]);
+        manager.apply_undo(&grouped);
+        assert!(manager.get_elements().iter().all(|e| e.group_id.is_none()));
+        manager.apply_redo(&grouped);
+        assert_eq!(manager.group_members(pen_id), vec![pen_id, box_id]);
+    }
+
+    #[test]
+    fn test_restack_moves_selection_and_groups() {
+        use crate::ZOrder;
+
+        let mut manager = super::ElementManager::new();
+        let ids: Vec<u64> = (0..5)
+            .map(|_| {
+                let element = super::DrawingElement::new(super::DrawingTool::Rectangle);
+                let id = element.id;
+                manager.add_element(element);
+                id
+            })
+            .collect();
+        let order = |manager: &super::ElementManager| -> Vec<u64> {
+            manager.get_elements().iter().map(|e| e.id).collect()
+        };
+        let [a, b, c, d, e] = ids[..] else {
+            unreachable!()
+        };
+
+        let forward = manager.restack_elements(&[a, c], ZOrder::Forward).unwrap();
+        assert_eq!(order(&manager), vec![b, a, d, c, e]);
+        manager.restack_elements(&[a, c], ZOrder::Backward).unwrap();
+        assert_eq!(order(&manager), ids);
+
+        // Already on top: nothing to record.
+        assert!(manager.restack_elements(&[e], ZOrder::ToFront).is_none());
+        manager.restack_elements(&[b], ZOrder::ToFront).unwrap();
+        assert_eq!(order(&manager), vec![a, c, d, e, b]);
+
+        // Grouped elements travel together.
+        manager.group_elements(&[d, e]).unwrap();
+        manager.restack_elements(&[e], ZOrder::ToBack).unwrap();
+        assert_eq!(order(&manager), vec![d, e, a, c, b]);
+
+        manager.apply_undo(&forward);
+        manager.apply_redo(&forward);
+        assert_eq!(order(&manager), vec![b, a, d, c, e]);
+    }
+
+    #[test]
+    fn test_arrange_moves_groups_as_blocks() {
+        use crate::Arrangement;
+
+        let mut manager = super::ElementManager::new();
+        let mut add = |left: i32, top: i32| {
+            let mut element = super::DrawingElement::new(super::DrawingTool::Rectangle);
+            element.add_point(left, top);
+            element.add_point(left + 20, top + 20);
+            element.update_bounding_rect();
+            let id = element.id;
+            manager.add_element(element);
+            id
+        };
+        let (a, b, c) = (add(0, 0), add(50, 10), add(80, 30));
+        let left =
+            |manager: &super::ElementManager, id| manager.get_element_by_id(id).unwrap().rect.left;
+        manager.group_elements(&[b, c]).unwrap();
+
+        // b and c keep their spacing and move together to the left edge.
+        let aligned = manager
+            .arrange_elements(&[a, b], Arrangement::AlignLeft)
+            .unwrap();
+        assert_eq!(
+            (left(&manager, a), left(&manager, b), left(&manager, c)),
+            (0, 0, 30)
+        );
+        assert!(
+            matches!(&aligned, super::DrawingAction::Compound { actions } if actions.len() == 2)
+        );
+
+        manager.apply_undo(&aligned);
+        assert_eq!((left(&manager, b), left(&manager, c)), (50, 80));
+
+        // A single block has nothing to line up with.
+        assert!(
+            manager
+                .arrange_elements(&[b], Arrangement::AlignTop)
+                .is_none()
+        );
+        // Already aligned: nothing to record.
+        manager.apply_redo(&aligned);
+        assert!(
+            manager
+                .arrange_elements(&[a, b], Arrangement::AlignLeft)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_set_fill_skips_unfillable_elements() {
+        let mut manager = super::ElementManager::new();
+        let mut add = |tool| {
+            let mut element = super::DrawingElement::new(tool);
+            element.add_point(0, 0);
+            element.add_point(20, 20);
+            element.update_bounding_rect();
+            let id = element.id;
+            manager.add_element(element);
+            id
+        };
+        let (rect, arrow) = (
+            add(super::DrawingTool::Rectangle),
+            add(super::DrawingTool::Arrow),
+        );
+        let fill =
+            |manager: &super::ElementManager, id| manager.get_element_by_id(id).unwrap().fill;
+        let tint = |e: &super::DrawingElement| Some(e.color.with_alpha(0.3));
+
+        // Only the rectangle can be filled, so a single property change is recorded.
+        let action = manager.set_fill(&[rect, arrow], tint).unwrap();
+        assert!(matches!(action, super::DrawingAction::ModifyProperty { id, .. } if id == rect));
+        assert_eq!(fill(&manager, rect).map(|c| c.a), Some(0.3));
+        assert_eq!(fill(&manager, arrow), None);
+        assert!(manager.set_fill(&[rect], tint).is_none());
+
+        manager.apply_undo(&action);
+        assert_eq!(fill(&manager, rect), None);
+        manager.apply_redo(&action);
+        assert!(fill(&manager, rect).is_some());
+    }
+
+    #[test]
+    fn test_set_arrow_style_changes_arrows_only() {
+        let mut manager = super::ElementManager::new();
+        let mut add = |tool| {
+            let mut element = super::DrawingElement::new(tool);
+            element.add_point(0, 0);
+            element.add_point(40, 0);
+            element.update_bounding_rect();
+            let id = element.id;
+            manager.add_element(element);
+            id
+        };
+        let (rect, arrow) = (
+            add(super::DrawingTool::Rectangle),
+            add(super::DrawingTool::Arrow),
+        );
+        let double = |_: &super::DrawingElement| crate::ArrowStyle {
+            head: crate::ArrowHead::Triangle,
+            tail: crate::ArrowHead::Triangle,
+        };
+
+        let action = manager.set_arrow_style(&[rect, arrow], double).unwrap();
+        assert!(matches!(action, super::DrawingAction::ModifyProperty { id, .. } if id == arrow));
+        let style =
+            |manager: &super::ElementManager| manager.get_element_by_id(arrow).unwrap().arrow;
+        assert!(style(&manager).is_double_headed());
+        assert!(manager.set_arrow_style(&[arrow], double).is_none());
+
+        manager.apply_undo(&action);
+        assert_eq!(style(&manager), crate::ArrowStyle::default());
+    }
+
+    #[test]
+    fn test_set_stroke_style_alone_is_undoable() {
+        let mut manager = super::ElementManager::new();
+        let mut rect = super::DrawingElement::new(super::DrawingTool::Rectangle);
+        rect.add_point(0, 0);
+        rect.add_point(20, 20);
+        rect.update_bounding_rect();
+        let id = rect.id;
+        manager.add_element(rect);
+
+        let dashed = |_: &super::DrawingElement| crate::StrokeStyle {
+            dash: crate::DashStyle::Dash,
+            ..Default::default()
+        };
+        let stroke =
+            |manager: &super::ElementManager| manager.get_element_by_id(id).unwrap().stroke.clone();
+
+        let action = manager.set_stroke_style(&[id], dashed).unwrap();
+        assert_eq!(stroke(&manager).dash, crate::DashStyle::Dash);
+        assert!(manager.set_stroke_style(&[id], dashed).is_none());
+
+        manager.apply_undo(&action);
+        assert_eq!(stroke(&manager), crate::StrokeStyle::default());
+        manager.apply_redo(&action);
+        assert_eq!(stroke(&manager).dash, crate::DashStyle::Dash);
+    }
+
+    /// Deterministic xorshift generator so failures reproduce from the seed.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+
+        fn coord(&mut self) -> i32 {
+            self.below(400) as i32 - 200
+        }
+    }
+
+    fn random_rect(rng: &mut Rng) -> super::Rect {
+        let (left, top) = (rng.coord(), rng.coord());
+        super::Rect::new(
+            left,
+            top,
+            left + 1 + rng.below(100) as i32,
+            top + 1 + rng.below(100) as i32,
+        )
+    }
+
+    /// Build a random edit against `state`, apply it there and return the matching action
+    /// with the ids it leaves selected.
+    fn random_edit(
+        rng: &mut Rng,
+        state: &mut Vec<super::DrawingElement>,
+    ) -> (super::DrawingAction, Vec<u64>) {
+        use super::DrawingAction;
+
+        let kind = if state.is_empty() { 0 } else { rng.below(10) };
+        let index = rng.below(state.len().max(1));
+        match kind {
+            0 => {
+                let mut element = super::DrawingElement::new(super::DrawingTool::Rectangle);
+                element.rect = random_rect(rng);
+                element.points = vec![
+                    crate::Point::new(element.rect.left, element.rect.top),
+                    crate::Point::new(element.rect.right, element.rect.bottom),
+                ];
+                let position = rng.below(state.len() + 1);
+                state.insert(position, element.clone());
+                let id = element.id;
+                (DrawingAction::AddElement { element, position }, vec![id])
+            }
+            1 => {
+                let element = state.remove(index);
+                let action = DrawingAction::RemoveElement {
+                    element,
+                    position: index,
+                };
+                (action, vec![])
+            }
+            2 => {
+                let element = &mut state[index];
+                let (dx, dy) = (rng.coord(), rng.coord());
+                let action = DrawingAction::MoveElement {
+                    id: element.id,
+                    dx,
+                    dy,
+                    old_points: element.points.clone(),
+                    old_rect: element.rect,
+                };
+                element.move_by(dx, dy);
+                (action, vec![element.id])
+            }
+            3 => {
+                let element = &mut state[index];
+                let old = element.clone();
+                element.rect = random_rect(rng);
+                element.points = vec![crate::Point::new(element.rect.left, element.rect.top)];
+                element.font_size = rng.below(72) as f32;
+                element.blur_radius = rng.below(30) as f32;
+                let action = DrawingAction::ResizeElement {
+                    id: element.id,
+                    old_points: old.points,
+                    old_rect: old.rect,
+                    old_font_size: old.font_size,
+                    old_blur_radius: old.blur_radius,
+                    new_points: element.points.clone(),
+                    new_rect: element.rect,
+                    new_font_size: element.font_size,
+                    new_blur_radius: element.blur_radius,
+                };
+                (action, vec![element.id])
+            }
+            4 => {
+                let element = &mut state[index];
+                let old = element.clone();
+                element.text = format!("text {}", rng.below(1000));
+                element.rect = random_rect(rng);
+                let action = DrawingAction::ModifyText {
+                    id: element.id,
+                    old_text: old.text,
+                    new_text: element.text.clone(),
+                    old_points: old.points,
+                    old_rect: old.rect,
+                    new_points: element.points.clone(),
+                    new_rect: element.rect,
+                };
+                (action, vec![element.id])
+            }
+            5 => {
+                let element = &mut state[index];
+                let old = element.clone();
+                element.color = crate::Color::rgb(rng.below(256) as f32 / 255.0, 0.5, 0.25);
+                element.thickness = 1.0 + rng.below(20) as f32;
+                element.fill = (rng.below(2) == 0).then(|| element.color.with_alpha(0.5));
+                element.arrow.tail = [crate::ArrowHead::None, crate::ArrowHead::Dot][rng.below(2)];
+                element.stroke.dash = if rng.below(2) == 0 {
+                    crate::DashStyle::Solid
+                } else {
+                    crate::DashStyle::Dot
+                };
+                let action = DrawingAction::ModifyProperty {
+                    id: element.id,
+                    old_color: old.color,
+                    old_thickness: old.thickness,
+                    old_fill: old.fill,
+                    old_arrow: old.arrow,
+                    old_stroke: old.stroke,
+                    new_color: element.color,
+                    new_thickness: element.thickness,
+                    new_fill: element.fill,
+                    new_arrow: element.arrow,
+                    new_stroke: element.stroke.clone(),
+                };
+                (action, vec![element.id])
+            }
+            6 => {
+                let element = &mut state[index];
+                let old_group = element.group_id;
+                element.group_id = [None, Some(1), Some(2)][rng.below(3)];
+                let action = DrawingAction::SetGroup {
+                    ids: vec![element.id],
+                    old_groups: vec![old_group],
+                    new_group: element.group_id,
+                };
+                (action, vec![element.id])
+            }
+            7 => {
+                let old_order: Vec<u64> = state.iter().map(|e| e.id).collect();
+                let element = state.remove(index);
+                let id = element.id;
+                state.insert(rng.below(state.len() + 1), element);
+                let new_order = state.iter().map(|e| e.id).collect();
+                let action = DrawingAction::Restack {
+                    old_order,
+                    new_order,
+                };
+                (action, vec![id])
+            }
+            8 => {
+                let element = &mut state[index];
+                let old_rotation = element.rotation;
+                element.rotation = rng.below(360) as f32 - 180.0;
+                let action = DrawingAction::RotateElement {
+                    id: element.id,
+                    old_rotation,
+                    new_rotation: element.rotation,
+                };
+                (action, vec![element.id])
+            }
+            _ => {
+                let count = 2 + rng.below(3);
+                let (actions, selected): (Vec<_>, Vec<_>) =
+                    (0..count).map(|_| random_edit(rng, state)).unzip();
+                (
+                    DrawingAction::Compound { actions },
+                    selected.into_iter().flatten().collect(),
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_edits_undo_redo_match_reference() {
+        for seed in 1..=20u64 {
+            let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+            let mut manager = super::ElementManager::new();
+            let mut history = crate::ActionHistory::new();
+            // Reference model: every state the history can reach, plus the selection in it.
+            let mut states: Vec<(Vec<super::DrawingElement>, Vec<u64>)> =
+                vec![(Vec::new(), Vec::new())];
+            let mut cursor = 0;
+
+            for step in 0..400 {
+                match rng.below(10) {
+                    0..=5 => {
+                        let (mut next, selected_before) = states[cursor].clone();
+                        let (action, selected_after) = random_edit(&mut rng, &mut next);
+                        history.record_action(action, selected_before, selected_after.clone());
+                        // The live editor mutates elements directly; mirror that here.
+                        manager.restore_state(next.clone());
+
+                        states.truncate(cursor + 1);
+                        states.push((next, selected_after));
+                        if states.len() > 51 {
+                            states.remove(0);
+                        }
+                        cursor = states.len() - 1;
+                    }
+                    6..=7 => {
+                        let result = history.undo_action();
+                        assert_eq!(result.is_some(), cursor > 0, "seed {seed} step {step}");
+                        if let Some((action, selected)) = result {
+                            manager.apply_undo(&action);
+                            cursor -= 1;
+                            assert_eq!(selected, states[cursor].1, "seed {seed} step {step}");
+                        }
+                    }
+                    _ => {
+                        let result = history.redo_action();
+                        assert_eq!(
+                            result.is_some(),
+                            cursor + 1 < states.len(),
+                            "seed {seed} step {step}"
+                        );
+                        if let Some((action, selected)) = result {
+                            manager.apply_redo(&action);
+                            cursor += 1;
+                            assert_eq!(selected, states[cursor].1, "seed {seed} step {step}");
+                        }
+                    }
+                }
+                assert_eq!(
+                    manager.get_elements(),
+                    &states[cursor].0,
+                    "seed {seed} step {step}"
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,246 @@
+use crate::element::Rect;
+
+/// Byte order of a 4-channel 8-bit pixel.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelOrder {
+    #[default]
+    Rgba,
+    /// GDI/DIB order, as produced by the screen capture path.
+    Bgra,
+}
+
+/// Tightly packed, top-down 4-channel pixel buffer.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PixelBuffer {
+    pub width: u32,
+    pub height: u32,
+    pub order: ChannelOrder,
+    pub pixels: Vec<u8>,
+}
+
+impl PixelBuffer {
+    pub fn new(width: u32, height: u32, order: ChannelOrder) -> Self {
+        Self {
+            width,
+            height,
+            order,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Wrap existing pixels; returns `None` if the length does not match the size.
+    pub fn from_raw(width: u32, height: u32, order: ChannelOrder, pixels: Vec<u8>) -> Option<Self> {
+        if pixels.len() != width as usize * height as usize * 4 {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            order,
+            pixels,
+        })
+    }
+
+    /// Read an uncompressed 32bpp BMP (as produced by the capture path) into a BGRA buffer.
+    pub fn from_bmp(data: &[u8]) -> Option<Self> {
+        let u32_at = |i: usize| Some(u32::from_le_bytes(data.get(i..i + 4)?.try_into().ok()?));
+
+        if data.get(0..2)? != b"BM" || data.get(28..30)? != [32, 0] {
+            return None;
+        }
+        let offset = u32_at(10)? as usize;
+        let width = u32_at(18)? as i32;
+        let raw_height = u32_at(22)? as i32;
+        if width <= 0 || raw_height == 0 {
+            return None;
+        }
+
+        let (width, height) = (width as u32, raw_height.unsigned_abs());
+        let row_bytes = width as usize * 4;
+        let body = data.get(offset..offset + row_bytes * height as usize)?;
+
+        let pixels = if raw_height < 0 {
+            body.to_vec()
+        } else {
+            body.chunks_exact(row_bytes)
+                .rev()
+                .flatten()
+                .copied()
+                .collect()
+        };
+        Self::from_raw(width, height, ChannelOrder::Bgra, pixels)
+    }
+
+    /// Encode as an uncompressed top-down 32bpp BMP, the format [`Self::from_bmp`] reads.
+    ///
+    /// The pixels are written in BGRA order regardless of [`Self::order`].
+    pub fn to_bmp(&self) -> Vec<u8> {
+        let data_size = self.pixels.len() as u32;
+        let mut data = Vec::with_capacity(54 + self.pixels.len());
+        data.extend_from_slice(b"BM");
+        data.extend_from_slice(&(54 + data_size).to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&54u32.to_le_bytes());
+        data.extend_from_slice(&40u32.to_le_bytes());
+        data.extend_from_slice(&(self.width as i32).to_le_bytes());
+        data.extend_from_slice(&(-(self.height as i32)).to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&32u16.to_le_bytes());
+        data.extend_from_slice(&[0; 24]);
+
+        match self.order {
+            ChannelOrder::Bgra => data.extend_from_slice(&self.pixels),
+            ChannelOrder::Rgba => data.extend(
+                self.pixels
+                    .chunks_exact(4)
+                    .flat_map(|p| [p[2], p[1], p[0], p[3]]),
+            ),
+        }
+        data
+    }
+
+    /// Pixel-space bounds of the buffer (`right`/`bottom` exclusive).
+    #[inline]
+    pub fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width as i32, self.height as i32)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.width as usize * 4
+    }
+
+    #[inline]
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = y as usize * self.stride() + x as usize * 4;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    #[inline]
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: [u8; 4]) {
+        let i = y as usize * self.stride() + x as usize * 4;
+        self.pixels[i..i + 4].copy_from_slice(&value);
+    }
+
+    /// Copy out the part of `rect` that lies inside the buffer.
+    pub fn crop(&self, rect: Rect) -> Option<PixelBuffer> {
+        let rect = clip_rect(rect, self.bounds())?;
+        let width = rect.width() as u32;
+        let height = rect.height() as u32;
+        let row_bytes = width as usize * 4;
+
+        let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+        for y in rect.top..rect.bottom {
+            let start = y as usize * self.stride() + rect.left as usize * 4;
+            pixels.extend_from_slice(&self.pixels[start..start + row_bytes]);
+        }
+
+        Some(PixelBuffer {
+            width,
+            height,
+            order: self.order,
+            pixels,
+        })
+    }
+}
+
+/// Intersect `rect` (normalized first) with `bounds`; `None` when nothing is left.
+pub fn clip_rect(rect: Rect, bounds: Rect) -> Option<Rect> {
+    let clipped = Rect::new(
+        rect.left.min(rect.right).max(bounds.left),
+        rect.top.min(rect.bottom).max(bounds.top),
+        rect.left.max(rect.right).min(bounds.right),
+        rect.top.max(rect.bottom).min(bounds.bottom),
+    );
+    (clipped.width() > 0 && clipped.height() > 0).then_some(clipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient(width: u32, height: u32) -> PixelBuffer {
+        let mut buffer = PixelBuffer::new(width, height, ChannelOrder::Rgba);
+        for y in 0..height {
+            for x in 0..width {
+                buffer.set_pixel(x, y, [x as u8, y as u8, 0, 255]);
+            }
+        }
+        buffer
+    }
+
+    fn bmp(width: i32, height: i32, rows: &[[u8; 4]]) -> Vec<u8> {
+        let mut data = b"BM".to_vec();
+        data.extend_from_slice(&[0; 8]);
+        data.extend_from_slice(&54u32.to_le_bytes());
+        data.extend_from_slice(&40u32.to_le_bytes());
+        data.extend_from_slice(&width.to_le_bytes());
+        data.extend_from_slice(&height.to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&32u16.to_le_bytes());
+        data.extend_from_slice(&[0; 24]);
+        data.extend(rows.iter().flatten());
+        data
+    }
+
+    #[test]
+    fn from_raw_checks_length() {
+        assert!(PixelBuffer::from_raw(2, 2, ChannelOrder::Rgba, vec![0; 16]).is_some());
+        assert!(PixelBuffer::from_raw(2, 2, ChannelOrder::Rgba, vec![0; 15]).is_none());
+    }
+
+    #[test]
+    fn from_bmp_reads_both_row_orders() {
+        let rows = [[1, 2, 3, 0], [4, 5, 6, 0]];
+
+        let top_down = PixelBuffer::from_bmp(&bmp(1, -2, &rows)).unwrap();
+        assert_eq!(top_down.order, ChannelOrder::Bgra);
+        assert_eq!(top_down.pixel(0, 0), [1, 2, 3, 0]);
+
+        let bottom_up = PixelBuffer::from_bmp(&bmp(1, 2, &rows)).unwrap();
+        assert_eq!(bottom_up.pixel(0, 0), [4, 5, 6, 0]);
+        assert_eq!(bottom_up.pixel(0, 1), [1, 2, 3, 0]);
+
+        assert!(PixelBuffer::from_bmp(&bmp(2, 2, &rows)).is_none());
+        assert!(PixelBuffer::from_bmp(b"BM").is_none());
+    }
+
+    #[test]
+    fn to_bmp_round_trips_as_bgra() {
+        let buffer = gradient(3, 2);
+        let decoded = PixelBuffer::from_bmp(&buffer.to_bmp()).unwrap();
+        assert_eq!(decoded.order, ChannelOrder::Bgra);
+        assert_eq!((decoded.width, decoded.height), (3, 2));
+        assert_eq!(decoded.pixel(2, 1), [0, 1, 2, 255]);
+        assert_eq!(PixelBuffer::from_bmp(&decoded.to_bmp()).unwrap(), decoded);
+    }
+
+    #[test]
+    fn crop_is_clamped_to_bounds() {
+        let buffer = gradient(10, 8);
+
+        let cropped = buffer.crop(Rect::new(6, 5, 20, 20)).unwrap();
+        assert_eq!((cropped.width, cropped.height), (4, 3));
+        assert_eq!(cropped.pixel(0, 0), [6, 5, 0, 255]);
+        assert_eq!(cropped.pixel(3, 2), [9, 7, 0, 255]);
+
+        assert!(buffer.crop(Rect::new(10, 0, 12, 4)).is_none());
+        assert!(buffer.crop(Rect::new(-5, -5, 0, 3)).is_none());
+    }
+
+    #[test]
+    fn clip_rect_normalizes_inverted_rects() {
+        let clipped = clip_rect(Rect::new(8, 6, 2, 1), Rect::new(0, 0, 5, 5)).unwrap();
+        assert_eq!(clipped, Rect::new(2, 1, 5, 5));
+    }
+}
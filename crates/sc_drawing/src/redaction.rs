@@ -0,0 +1,515 @@
+//! CPU filters for redaction elements.
+//!
+//! The filters run on [`PixelBuffer`]s and never look at channel order, so the same code
+//! serves the BGRA screen capture and the RGBA preview image.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::element::{DrawingElement, Rect};
+use crate::raster::{PixelBuffer, clip_rect};
+use crate::types::DrawingTool;
+
+static NEXT_SOURCE_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Image that redaction elements sample from.
+#[derive(Debug, Clone)]
+pub struct RedactionSource {
+    pub image: Arc<PixelBuffer>,
+    /// Where the image is displayed, in element coordinates. The image is stretched to fit.
+    pub bounds: Rect,
+    /// Unique per constructed source, so a patch never survives a new image that happens to
+    /// reuse the allocation of the old one.
+    generation: u64,
+}
+
+impl RedactionSource {
+    pub fn new(image: Arc<PixelBuffer>, bounds: Rect) -> Self {
+        Self {
+            image,
+            bounds,
+            generation: NEXT_SOURCE_GENERATION.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Source displayed 1:1 with its top-left corner at the origin.
+    pub fn unscaled(image: Arc<PixelBuffer>) -> Self {
+        let bounds = image.bounds();
+        Self::new(image, bounds)
+    }
+
+    /// Source pixels per element-space pixel, horizontally and vertically.
+    fn scale(&self) -> (f64, f64) {
+        (
+            self.image.width as f64 / self.bounds.width().max(1) as f64,
+            self.image.height as f64 / self.bounds.height().max(1) as f64,
+        )
+    }
+
+    /// Map an element-space rect (already clipped to `bounds`) to source pixels.
+    fn to_image_rect(&self, rect: Rect) -> Rect {
+        let (sx, sy) = self.scale();
+        Rect::new(
+            ((rect.left - self.bounds.left) as f64 * sx).floor() as i32,
+            ((rect.top - self.bounds.top) as f64 * sy).floor() as i32,
+            ((rect.right - self.bounds.left) as f64 * sx).ceil() as i32,
+            ((rect.bottom - self.bounds.top) as f64 * sy).ceil() as i32,
+        )
+    }
+}
+
+/// Filtered pixels drawn opaquely over the area a redaction element covers.
+///
+/// The patch only ever holds filtered pixels, so anything composited from it (screen or
+/// export) cannot leak the original content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionPatch {
+    /// Element-space area the pixels are stretched over.
+    pub rect: Rect,
+    pub pixels: Arc<PixelBuffer>,
+    key: PatchKey,
+}
+
+/// Inputs a patch was built from; a patch is rebuilt whenever these change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PatchKey {
+    tool: DrawingTool,
+    rect: Rect,
+    bounds: Rect,
+    generation: u64,
+    block_size: u32,
+    blur_radius: u32,
+}
+
+impl PatchKey {
+    fn new(element: &DrawingElement, source: &RedactionSource) -> Self {
+        Self {
+            tool: element.tool,
+            rect: element.rect,
+            bounds: source.bounds,
+            generation: source.generation,
+            block_size: element.block_size,
+            blur_radius: element.blur_radius.to_bits(),
+        }
+    }
+}
+
+/// Bring `element.redaction` up to date with its geometry and `source`.
+///
+/// Returns `true` if the patch changed. Non-redaction elements are left untouched.
+pub fn refresh_patch(element: &mut DrawingElement, source: Option<&RedactionSource>) -> bool {
+    if !element.tool.is_redaction() {
+        return false;
+    }
+
+    let Some(source) = source else {
+        return element.redaction.take().is_some();
+    };
+
+    let key = PatchKey::new(element, source);
+    if element.redaction.as_ref().is_some_and(|p| p.key == key) {
+        return false;
+    }
+
+    element.redaction = build_patch(element, source, key);
+    true
+}
+
+fn build_patch(
+    element: &DrawingElement,
+    source: &RedactionSource,
+    key: PatchKey,
+) -> Option<RedactionPatch> {
+    let rect = clip_rect(element.rect, source.bounds)?;
+    let mut pixels = source.image.crop(source.to_image_rect(rect))?;
+
+    match element.tool {
+        DrawingTool::Mosaic => {
+            let (sx, sy) = source.scale();
+            let block = (element.block_size.max(1) as f64 * sx.max(sy)).round() as u32;
+            pixelate(&mut pixels, block);
+        }
+        DrawingTool::Blur => {
+            let (sx, sy) = source.scale();
+            gaussian_blur(&mut pixels, element.blur_radius * sx.max(sy) as f32);
+        }
+        _ => return None,
+    }
+
+    Some(RedactionPatch {
+        rect,
+        pixels: Arc::new(pixels),
+        key,
+    })
+}
+
+/// Replace every `block_size` square of the buffer with its average colour.
+///
+/// Blocks are aligned to the buffer's top-left corner; edge blocks are averaged over the
+/// pixels they actually contain.
+pub fn pixelate(buffer: &mut PixelBuffer, block_size: u32) {
+    let region = buffer.bounds();
+    pixelate_region(buffer, region, block_size);
+}
+
+/// [`pixelate`] restricted to `region`; pixels outside it are neither read nor written.
+pub fn pixelate_region(buffer: &mut PixelBuffer, region: Rect, block_size: u32) {
+    let Some(region) = clip_rect(region, buffer.bounds()) else {
+        return;
+    };
+    let block = block_size.max(1) as i32;
+    if block == 1 {
+        return;
+    }
+
+    for top in (region.top..region.bottom).step_by(block as usize) {
+        let bottom = (top + block).min(region.bottom);
+        for left in (region.left..region.right).step_by(block as usize) {
+            let right = (left + block).min(region.right);
+
+            let mut sum = [0u64; 4];
+            for y in top..bottom {
+                for x in left..right {
+                    let p = buffer.pixel(x as u32, y as u32);
+                    for (acc, v) in sum.iter_mut().zip(p) {
+                        *acc += v as u64;
+                    }
+                }
+            }
+
+            let count = ((right - left) * (bottom - top)) as u64;
+            let average = sum.map(|s| ((s + count / 2) / count) as u8);
+            for y in top..bottom {
+                for x in left..right {
+                    buffer.set_pixel(x as u32, y as u32, average);
+                }
+            }
+        }
+    }
+}
+
+/// Normalized 1-D Gaussian weights covering `-radius..=radius`.
+///
+/// Sigma is half the radius, so the kernel is cut off at two standard deviations; the
+/// weights are renormalized after truncation. Radii below one pixel yield `[1.0]`.
+pub fn gaussian_kernel(radius: f32) -> Vec<f32> {
+    let half = radius.ceil().max(0.0) as usize;
+    if half == 0 {
+        return vec![1.0];
+    }
+
+    let sigma = radius / 2.0;
+    let denominator = 2.0 * sigma * sigma;
+    let mut kernel: Vec<f32> = (0..=half * 2)
+        .map(|i| {
+            let d = i as f32 - half as f32;
+            (-d * d / denominator).exp()
+        })
+        .collect();
+
+    let sum: f32 = kernel.iter().sum();
+    kernel.iter_mut().for_each(|w| *w /= sum);
+    kernel
+}
+
+/// Separable Gaussian blur over the whole buffer.
+///
+/// Samples past the edges are clamped to the nearest edge pixel, so nothing outside the
+/// buffer bleeds in and a redacted area never picks up its surroundings.
+pub fn gaussian_blur(buffer: &mut PixelBuffer, radius: f32) {
+    let region = buffer.bounds();
+    gaussian_blur_region(buffer, region, radius);
+}
+
+/// [`gaussian_blur`] restricted to `region`; pixels outside it are neither read nor written.
+pub fn gaussian_blur_region(buffer: &mut PixelBuffer, region: Rect, radius: f32) {
+    let Some(region) = clip_rect(region, buffer.bounds()) else {
+        return;
+    };
+    let kernel = gaussian_kernel(radius);
+    if kernel.len() == 1 {
+        return;
+    }
+
+    let half = (kernel.len() / 2) as i32;
+    let width = region.width() as usize;
+    let height = region.height() as usize;
+
+    // Horizontal pass into a float scratch buffer, so rounding happens only once.
+    let mut scratch = vec![0f32; width * height * 4];
+    for y in 0..height {
+        for x in 0..width {
+            let mut acc = [0f32; 4];
+            for (k, weight) in kernel.iter().enumerate() {
+                let sx = (x as i32 + k as i32 - half).clamp(0, width as i32 - 1);
+                let p = buffer.pixel((region.left + sx) as u32, (region.top + y as i32) as u32);
+                for (a, v) in acc.iter_mut().zip(p) {
+                    *a += v as f32 * weight;
+                }
+            }
+            let i = (y * width + x) * 4;
+            scratch[i..i + 4].copy_from_slice(&acc);
+        }
+    }
+
+    // Vertical pass writes back into the buffer.
+    for y in 0..height {
+        for x in 0..width {
+            let mut acc = [0f32; 4];
+            for (k, weight) in kernel.iter().enumerate() {
+                let sy = (y as i32 + k as i32 - half).clamp(0, height as i32 - 1) as usize;
+                let i = (sy * width + x) * 4;
+                for (a, v) in acc.iter_mut().zip(&scratch[i..i + 4]) {
+                    *a += v * weight;
+                }
+            }
+            buffer.set_pixel(
+                (region.left + x as i32) as u32,
+                (region.top + y as i32) as u32,
+                acc.map(|v| v.round().clamp(0.0, 255.0) as u8),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Point;
+    use crate::raster::ChannelOrder;
+
+    fn checkerboard(width: u32, height: u32) -> PixelBuffer {
+        let mut buffer = PixelBuffer::new(width, height, ChannelOrder::Rgba);
+        for y in 0..height {
+            for x in 0..width {
+                let v = if (x + y) % 2 == 0 { 255 } else { 0 };
+                buffer.set_pixel(x, y, [v, 0, 255 - v, 255]);
+            }
+        }
+        buffer
+    }
+
+    fn mosaic(left: i32, top: i32, right: i32, bottom: i32, block_size: u32) -> DrawingElement {
+        let mut element = DrawingElement::new(DrawingTool::Mosaic);
+        element.points = vec![Point::new(left, top), Point::new(right, bottom)];
+        element.block_size = block_size;
+        element.update_bounding_rect();
+        element
+    }
+
+    fn blur(left: i32, top: i32, right: i32, bottom: i32, radius: f32) -> DrawingElement {
+        let mut element = DrawingElement::new(DrawingTool::Blur);
+        element.points = vec![Point::new(left, top), Point::new(right, bottom)];
+        element.blur_radius = radius;
+        element.update_bounding_rect();
+        element
+    }
+
+    #[test]
+    fn pixelate_averages_each_block() {
+        let mut buffer = checkerboard(4, 4);
+        pixelate(&mut buffer, 2);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(buffer.pixel(x, y), [128, 0, 128, 255]);
+            }
+        }
+    }
+
+    #[test]
+    fn pixelate_handles_partial_edge_blocks() {
+        let mut buffer = PixelBuffer::new(3, 1, ChannelOrder::Bgra);
+        buffer.set_pixel(0, 0, [10, 20, 30, 255]);
+        buffer.set_pixel(1, 0, [30, 40, 50, 255]);
+        buffer.set_pixel(2, 0, [7, 8, 9, 255]);
+
+        pixelate(&mut buffer, 2);
+
+        assert_eq!(buffer.pixel(0, 0), [20, 30, 40, 255]);
+        assert_eq!(buffer.pixel(1, 0), [20, 30, 40, 255]);
+        assert_eq!(buffer.pixel(2, 0), [7, 8, 9, 255]);
+    }
+
+    #[test]
+    fn pixelate_region_leaves_outside_pixels_alone() {
+        let original = checkerboard(6, 6);
+        let mut buffer = original.clone();
+        pixelate_region(&mut buffer, Rect::new(2, 2, 4, 4), 2);
+
+        for y in 0..6 {
+            for x in 0..6 {
+                let inside = (2..4).contains(&x) && (2..4).contains(&y);
+                if inside {
+                    assert_eq!(buffer.pixel(x, y), [128, 0, 128, 255]);
+                } else {
+                    assert_eq!(buffer.pixel(x, y), original.pixel(x, y));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn block_size_one_is_identity() {
+        let original = checkerboard(5, 5);
+        let mut buffer = original.clone();
+        pixelate(&mut buffer, 1);
+        assert_eq!(buffer, original);
+    }
+
+    #[test]
+    fn patch_covers_clipped_rect_with_pixelated_pixels() {
+        let source = RedactionSource::unscaled(Arc::new(checkerboard(8, 8)));
+        let mut element = mosaic(4, 4, 20, 20, 2);
+
+        assert!(refresh_patch(&mut element, Some(&source)));
+        let patch = element.redaction.as_ref().unwrap();
+        assert_eq!(patch.rect, Rect::new(4, 4, 8, 8));
+        assert_eq!((patch.pixels.width, patch.pixels.height), (4, 4));
+        assert!(
+            patch
+                .pixels
+                .pixels
+                .chunks(4)
+                .all(|p| p == [128, 0, 128, 255])
+        );
+    }
+
+    #[test]
+    fn patch_is_rebuilt_only_when_inputs_change() {
+        let source = RedactionSource::unscaled(Arc::new(checkerboard(8, 8)));
+        let mut element = mosaic(0, 0, 4, 4, 2);
+
+        assert!(refresh_patch(&mut element, Some(&source)));
+        assert!(!refresh_patch(&mut element, Some(&source)));
+
+        element.move_by(2, 2);
+        assert!(refresh_patch(&mut element, Some(&source)));
+        assert_eq!(
+            element.redaction.as_ref().unwrap().rect,
+            Rect::new(2, 2, 6, 6)
+        );
+
+        element.block_size = 4;
+        assert!(refresh_patch(&mut element, Some(&source)));
+
+        assert!(refresh_patch(&mut element, None));
+        assert!(element.redaction.is_none());
+    }
+
+    #[test]
+    fn patch_is_rebuilt_for_a_new_source_with_the_same_image() {
+        let image = Arc::new(checkerboard(8, 8));
+        let mut element = mosaic(0, 0, 4, 4, 2);
+
+        let first = RedactionSource::unscaled(image.clone());
+        assert!(refresh_patch(&mut element, Some(&first)));
+        assert!(!refresh_patch(&mut element, Some(&first.clone())));
+
+        // A replacement image may land at the address of the one it replaces.
+        let second = RedactionSource::unscaled(image);
+        assert!(refresh_patch(&mut element, Some(&second)));
+    }
+
+    #[test]
+    fn scaled_source_maps_into_image_pixels() {
+        // 8x8 image displayed at 2x with its corner at (100, 100).
+        let image = Arc::new(checkerboard(8, 8));
+        let source = RedactionSource::new(image, Rect::new(100, 100, 116, 116));
+        let mut element = mosaic(100, 100, 108, 108, 2);
+
+        refresh_patch(&mut element, Some(&source));
+        let patch = element.redaction.as_ref().unwrap();
+        assert_eq!(patch.rect, Rect::new(100, 100, 108, 108));
+        assert_eq!((patch.pixels.width, patch.pixels.height), (4, 4));
+    }
+
+    #[test]
+    fn non_redaction_elements_are_ignored() {
+        let source = RedactionSource::unscaled(Arc::new(checkerboard(8, 8)));
+        let mut element = DrawingElement::new(DrawingTool::Rectangle);
+        element.points = vec![Point::new(0, 0), Point::new(4, 4)];
+        element.update_bounding_rect();
+
+        assert!(!refresh_patch(&mut element, Some(&source)));
+        assert!(element.redaction.is_none());
+    }
+
+    #[test]
+    fn gaussian_kernel_is_normalized_and_symmetric() {
+        let kernel = gaussian_kernel(3.0);
+        assert_eq!(kernel.len(), 7);
+        assert!((kernel.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+        for i in 0..3 {
+            assert!((kernel[i] - kernel[6 - i]).abs() < 1e-6);
+            assert!(kernel[i] < kernel[i + 1]);
+        }
+
+        assert_eq!(gaussian_kernel(0.0), vec![1.0]);
+    }
+
+    #[test]
+    fn gaussian_blur_keeps_flat_colour() {
+        let mut buffer = PixelBuffer::new(6, 5, ChannelOrder::Rgba);
+        for y in 0..5 {
+            for x in 0..6 {
+                buffer.set_pixel(x, y, [40, 90, 200, 255]);
+            }
+        }
+        let original = buffer.clone();
+
+        gaussian_blur(&mut buffer, 4.0);
+        assert_eq!(buffer, original);
+    }
+
+    #[test]
+    fn gaussian_blur_spreads_a_single_dot() {
+        let mut buffer = PixelBuffer::new(9, 9, ChannelOrder::Bgra);
+        buffer.set_pixel(4, 4, [255, 255, 255, 255]);
+
+        gaussian_blur(&mut buffer, 2.0);
+
+        let centre = buffer.pixel(4, 4)[0];
+        assert!(centre < 255 && centre > 0);
+        assert!(buffer.pixel(5, 4)[0] > 0);
+        assert!(buffer.pixel(5, 4)[0] < centre);
+        // Separable kernel, so horizontal and vertical neighbours match.
+        assert_eq!(buffer.pixel(5, 4), buffer.pixel(4, 5));
+        assert_eq!(buffer.pixel(3, 4), buffer.pixel(5, 4));
+        // Beyond the kernel radius nothing changes.
+        assert_eq!(buffer.pixel(0, 0), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn gaussian_blur_region_leaves_outside_pixels_alone() {
+        let original = checkerboard(8, 8);
+        let mut buffer = original.clone();
+        gaussian_blur_region(&mut buffer, Rect::new(2, 2, 6, 6), 2.0);
+
+        for y in 0..8 {
+            for x in 0..8 {
+                let inside = (2..6).contains(&x) && (2..6).contains(&y);
+                if !inside {
+                    assert_eq!(buffer.pixel(x, y), original.pixel(x, y));
+                }
+            }
+        }
+        assert_ne!(buffer.pixel(3, 3), original.pixel(3, 3));
+    }
+
+    #[test]
+    fn blur_patch_is_rebuilt_when_radius_changes() {
+        let source = RedactionSource::unscaled(Arc::new(checkerboard(8, 8)));
+        let mut element = blur(0, 0, 8, 8, 2.0);
+
+        assert!(refresh_patch(&mut element, Some(&source)));
+        let first = element.redaction.clone().unwrap();
+        assert_eq!(first.rect, Rect::new(0, 0, 8, 8));
+        assert_ne!(*first.pixels, source.image.crop(first.rect).unwrap());
+        assert!(!refresh_patch(&mut element, Some(&source)));
+
+        element.set_blur_radius(4.0);
+        assert!(refresh_patch(&mut element, Some(&source)));
+        assert_ne!(element.redaction.as_ref().unwrap().pixels, first.pixels);
+    }
+}
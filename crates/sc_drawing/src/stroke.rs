@@ -0,0 +1,235 @@
+//! Outline styles: dash pattern, line caps and line joins.
+//!
+//! Each style has a short text form (`"dash-dot"`, `"round"`, `"3,1.5"`) used to keep the
+//! defaults for new annotations in the settings file.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Dash pattern of an outline.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum DashStyle {
+    #[default]
+    Solid,
+    Dash,
+    Dot,
+    DashDot,
+    /// Alternating dash and gap lengths, in multiples of the stroke width.
+    Custom(Vec<f32>),
+}
+
+impl DashStyle {
+    /// Dash and gap lengths in stroke widths, as seen on screen. `None` for a solid line.
+    fn pattern(&self) -> Option<&[f32]> {
+        match self {
+            Self::Solid => None,
+            Self::Dash => Some(&[3.0, 2.0]),
+            Self::Dot => Some(&[1.0, 1.0]),
+            Self::DashDot => Some(&[3.0, 1.5, 1.0, 1.5]),
+            Self::Custom(lengths) => Some(lengths),
+        }
+    }
+
+    /// Parse the text form: a style name or comma-separated dash and gap lengths.
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim() {
+            "solid" => Some(Self::Solid),
+            "dash" => Some(Self::Dash),
+            "dot" => Some(Self::Dot),
+            "dash-dot" => Some(Self::DashDot),
+            lengths => lengths
+                .split(',')
+                .map(|length| length.trim().parse::<f32>().ok())
+                .collect::<Option<Vec<_>>>()
+                .map(Self::Custom),
+        }
+    }
+}
+
+impl fmt::Display for DashStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Solid => f.write_str("solid"),
+            Self::Dash => f.write_str("dash"),
+            Self::Dot => f.write_str("dot"),
+            Self::DashDot => f.write_str("dash-dot"),
+            Self::Custom(lengths) => {
+                let lengths: Vec<String> = lengths.iter().map(f32::to_string).collect();
+                f.write_str(&lengths.join(","))
+            }
+        }
+    }
+}
+
+/// Shape drawn at the open ends of a line and of every dash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum LineCap {
+    #[default]
+    Flat,
+    Round,
+    Square,
+}
+
+impl LineCap {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim() {
+            "flat" => Some(Self::Flat),
+            "round" => Some(Self::Round),
+            "square" => Some(Self::Square),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LineCap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Flat => "flat",
+            Self::Round => "round",
+            Self::Square => "square",
+        })
+    }
+}
+
+/// Shape of the corners where two segments of an outline meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl LineJoin {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim() {
+            "miter" => Some(Self::Miter),
+            "round" => Some(Self::Round),
+            "bevel" => Some(Self::Bevel),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LineJoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Miter => "miter",
+            Self::Round => "round",
+            Self::Bevel => "bevel",
+        })
+    }
+}
+
+/// How an outline is stroked. The default, a solid line with flat caps and mitred corners, is
+/// what annotations looked like before styles existed.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StrokeStyle {
+    pub dash: DashStyle,
+    pub cap: LineCap,
+    pub join: LineJoin,
+}
+
+impl StrokeStyle {
+    /// Build a style from its text forms; parts that do not parse keep their default.
+    pub fn parse(dash: &str, cap: &str, join: &str) -> Self {
+        Self {
+            dash: DashStyle::parse(dash).unwrap_or_default(),
+            cap: LineCap::parse(cap).unwrap_or_default(),
+            join: LineJoin::parse(join).unwrap_or_default(),
+        }
+    }
+
+    /// Plain solid line with flat caps and mitred corners.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Dash and gap lengths in stroke widths to hand to a renderer, or `None` for a solid
+    /// line.
+    ///
+    /// Round and square caps extend every dash by half a width at each end, so dashes are
+    /// shortened and gaps widened by one width to keep the visible pattern. Odd-length
+    /// patterns are repeated once, as SVG does. Empty or invalid patterns draw solid.
+    pub fn dash_array(&self) -> Option<Vec<f32>> {
+        let pattern = self.dash.pattern()?;
+        if pattern
+            .iter()
+            .any(|length| !length.is_finite() || *length < 0.0)
+            || pattern.iter().sum::<f32>() <= 0.0
+        {
+            return None;
+        }
+
+        let mut lengths = pattern.to_vec();
+        if lengths.len() % 2 == 1 {
+            lengths.extend_from_within(..);
+        }
+        if self.cap != LineCap::Flat {
+            for (i, length) in lengths.iter_mut().enumerate() {
+                *length = if i % 2 == 0 {
+                    (*length - 1.0).max(0.0)
+                } else {
+                    *length + 1.0
+                };
+            }
+        }
+        Some(lengths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_forms_round_trip() {
+        for dash in [
+            DashStyle::Solid,
+            DashStyle::Dash,
+            DashStyle::Dot,
+            DashStyle::DashDot,
+            DashStyle::Custom(vec![4.0, 1.5]),
+        ] {
+            assert_eq!(DashStyle::parse(&dash.to_string()), Some(dash));
+        }
+        assert_eq!(
+            LineCap::parse(&LineCap::Square.to_string()),
+            Some(LineCap::Square)
+        );
+        assert_eq!(
+            LineJoin::parse(&LineJoin::Bevel.to_string()),
+            Some(LineJoin::Bevel)
+        );
+
+        let style = StrokeStyle::parse("dot", "round", "wavy");
+        assert_eq!(style.dash, DashStyle::Dot);
+        assert_eq!(style.cap, LineCap::Round);
+        assert_eq!(style.join, LineJoin::Miter);
+        assert_eq!(DashStyle::parse("3,x"), None);
+    }
+
+    #[test]
+    fn dash_array_accounts_for_caps() {
+        assert_eq!(StrokeStyle::default().dash_array(), None);
+
+        let mut style = StrokeStyle {
+            dash: DashStyle::Dash,
+            ..Default::default()
+        };
+        assert_eq!(style.dash_array(), Some(vec![3.0, 2.0]));
+        style.cap = LineCap::Round;
+        assert_eq!(style.dash_array(), Some(vec![2.0, 3.0]));
+
+        // Odd patterns repeat; dots shrink to zero-length dashes drawn by the caps alone.
+        style.dash = DashStyle::Custom(vec![1.0]);
+        assert_eq!(style.dash_array(), Some(vec![0.0, 2.0]));
+
+        style.dash = DashStyle::Custom(vec![0.0, 0.0]);
+        assert_eq!(style.dash_array(), None);
+        style.dash = DashStyle::Custom(vec![2.0, -1.0]);
+        assert_eq!(style.dash_array(), None);
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DrawingTool {
     #[default]
     None,
@@ -7,24 +9,62 @@ pub enum DrawingTool {
     Arrow,
     Pen,
     Text,
+    Mosaic,
+    Blur,
+    Step,
+    Highlighter,
+    /// Rounded text box with a tail pointing at something.
+    Callout,
 }
 
 impl DrawingTool {
     pub fn is_shape(&self) -> bool {
-        matches!(self, Self::Rectangle | Self::Circle | Self::Arrow)
+        matches!(
+            self,
+            Self::Rectangle | Self::Circle | Self::Arrow | Self::Mosaic | Self::Blur
+        )
     }
 
     pub fn is_freeform(&self) -> bool {
-        matches!(self, Self::Pen)
+        matches!(self, Self::Pen | Self::Highlighter)
     }
 
+    /// Tools whose text is typed in place and grows the box as it goes.
     pub fn is_text(&self) -> bool {
-        matches!(self, Self::Text)
+        matches!(self, Self::Text | Self::Callout)
+    }
+
+    /// Tools that destroy the pixels underneath instead of drawing on top of them.
+    pub fn is_redaction(&self) -> bool {
+        matches!(self, Self::Mosaic | Self::Blur)
+    }
+
+    /// Numbered markers that are placed with a single click.
+    pub fn is_marker(&self) -> bool {
+        matches!(self, Self::Step)
     }
 
     pub fn can_draw(&self) -> bool {
         !matches!(self, Self::None)
     }
+
+    /// Box-shaped tools that can be turned about their centre with the rotation handle.
+    pub fn can_rotate(&self) -> bool {
+        matches!(self, Self::Rectangle | Self::Circle | Self::Text)
+    }
+
+    /// Closed outlines that can carry an interior fill.
+    pub fn can_fill(&self) -> bool {
+        matches!(self, Self::Rectangle | Self::Circle | Self::Callout)
+    }
+
+    /// Line-drawing tools whose outline follows the element's stroke style.
+    pub fn uses_stroke_style(&self) -> bool {
+        matches!(
+            self,
+            Self::Rectangle | Self::Circle | Self::Arrow | Self::Pen | Self::Callout
+        )
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
@@ -43,6 +83,12 @@ pub enum DragMode {
     ResizingBottomCenter,
     ResizingBottomLeft,
     ResizingMiddleLeft,
+    /// Turning an element with its rotation handle.
+    Rotating,
+    /// Dragging the midpoint handle of an arrow to curve it.
+    BendingArrow,
+    /// Dragging the tip of a callout's tail.
+    MovingCalloutTail,
 }
 
 impl DragMode {
@@ -80,6 +126,10 @@ pub enum ElementInteractionMode {
     Drawing,
     MovingElement,
     ResizingElement(DragMode),
+    /// Turning the selected element about its centre.
+    RotatingElement,
+    /// Dragging out a rubber band to select the elements inside it.
+    RubberBand,
 }
 
 impl ElementInteractionMode {
@@ -95,7 +145,10 @@ impl ElementInteractionMode {
             | DragMode::ResizingBottomRight
             | DragMode::ResizingBottomCenter
             | DragMode::ResizingBottomLeft
-            | DragMode::ResizingMiddleLeft => ElementInteractionMode::ResizingElement(drag_mode),
+            | DragMode::ResizingMiddleLeft
+            | DragMode::BendingArrow
+            | DragMode::MovingCalloutTail => ElementInteractionMode::ResizingElement(drag_mode),
+            DragMode::Rotating => ElementInteractionMode::RotatingElement,
             _ => ElementInteractionMode::None,
         }
     }
@@ -105,6 +158,40 @@ impl ElementInteractionMode {
     }
 }
 
+/// Where to move elements in the stacking order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZOrder {
+    /// One step up, past the next element above.
+    Forward,
+    /// One step down, past the next element below.
+    Backward,
+    /// Above everything else.
+    ToFront,
+    /// Below everything else.
+    ToBack,
+}
+
+/// How to line up or space out several elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arrangement {
+    /// Left edges on the left-most one.
+    AlignLeft,
+    /// Horizontal centres on the centre of the combined bounds.
+    AlignCenter,
+    /// Right edges on the right-most one.
+    AlignRight,
+    /// Top edges on the top-most one.
+    AlignTop,
+    /// Vertical centres on the middle of the combined bounds.
+    AlignMiddle,
+    /// Bottom edges on the bottom-most one.
+    AlignBottom,
+    /// Equal horizontal gaps between neighbours.
+    DistributeHorizontally,
+    /// Equal vertical gaps between neighbours.
+    DistributeVertically,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ElementInfo {
     pub id: u64,
@@ -124,15 +211,35 @@ mod tests {
         assert!(super::DrawingTool::Arrow.is_shape());
         assert!(!super::DrawingTool::Pen.is_shape());
         assert!(!super::DrawingTool::Text.is_shape());
+        assert!(super::DrawingTool::Mosaic.is_shape());
+        assert!(super::DrawingTool::Blur.is_shape());
+
+        assert!(super::DrawingTool::Mosaic.is_redaction());
+        assert!(super::DrawingTool::Blur.is_redaction());
+        assert!(!super::DrawingTool::Rectangle.is_redaction());
+
+        assert!(super::DrawingTool::Step.is_marker());
+        assert!(!super::DrawingTool::Step.is_shape());
 
         assert!(super::DrawingTool::Pen.is_freeform());
+        assert!(super::DrawingTool::Highlighter.is_freeform());
         assert!(!super::DrawingTool::Rectangle.is_freeform());
 
         assert!(super::DrawingTool::Text.is_text());
+        assert!(super::DrawingTool::Callout.is_text());
         assert!(!super::DrawingTool::Pen.is_text());
 
         assert!(super::DrawingTool::Pen.can_draw());
         assert!(!super::DrawingTool::None.can_draw());
+
+        assert!(super::DrawingTool::Text.can_rotate());
+        assert!(!super::DrawingTool::Arrow.can_rotate());
+        assert!(super::DrawingTool::Circle.can_fill());
+        assert!(!super::DrawingTool::Text.can_fill());
+        assert!(super::DrawingTool::Callout.can_fill());
+        assert!(!super::DrawingTool::Callout.can_rotate());
+        assert!(super::DrawingTool::Pen.uses_stroke_style());
+        assert!(!super::DrawingTool::Highlighter.uses_stroke_style());
     }
 
     #[test]
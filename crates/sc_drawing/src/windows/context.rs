@@ -1,13 +1,14 @@
 use std::collections::HashMap;
 use windows::Win32::Graphics::Direct2D::Common::D2D1_COLOR_F;
 use windows::Win32::Graphics::Direct2D::{
-    D2D1_CAP_STYLE_FLAT, D2D1_DASH_STYLE_CUSTOM, D2D1_DASH_STYLE_SOLID, D2D1_LINE_JOIN_MITER,
-    D2D1_STROKE_STYLE_PROPERTIES, ID2D1Factory, ID2D1RenderTarget, ID2D1SolidColorBrush,
-    ID2D1StrokeStyle,
+    D2D1_CAP_STYLE, D2D1_CAP_STYLE_FLAT, D2D1_CAP_STYLE_ROUND, D2D1_CAP_STYLE_SQUARE,
+    D2D1_DASH_STYLE_CUSTOM, D2D1_DASH_STYLE_SOLID, D2D1_LINE_JOIN_BEVEL, D2D1_LINE_JOIN_MITER,
+    D2D1_LINE_JOIN_ROUND, D2D1_STROKE_STYLE_PROPERTIES, ID2D1Factory, ID2D1RenderTarget,
+    ID2D1SolidColorBrush, ID2D1StrokeStyle,
 };
 use windows::Win32::Graphics::DirectWrite::IDWriteFactory;
 
-use crate::Color;
+use crate::{Color, LineCap, LineJoin, StrokeStyle};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BorderStyle {
@@ -102,6 +103,8 @@ pub struct RenderContext<'a> {
     brush_cache: HashMap<ColorKey, ID2D1SolidColorBrush>,
     dashed_style: Option<ID2D1StrokeStyle>,
     solid_style: Option<ID2D1StrokeStyle>,
+    round_style: Option<ID2D1StrokeStyle>,
+    element_styles: Vec<(StrokeStyle, ID2D1StrokeStyle)>,
 }
 
 impl<'a> RenderContext<'a> {
@@ -117,6 +120,8 @@ impl<'a> RenderContext<'a> {
             brush_cache: HashMap::new(),
             dashed_style: None,
             solid_style: None,
+            round_style: None,
+            element_styles: Vec::new(),
         }
     }
 
@@ -177,6 +182,66 @@ impl<'a> RenderContext<'a> {
         self.solid_style.as_ref()
     }
 
+    /// Solid stroke with round caps and joins, for freehand strokes.
+    pub fn get_round_style(&mut self) -> Option<&ID2D1StrokeStyle> {
+        if self.round_style.is_none() {
+            let props = D2D1_STROKE_STYLE_PROPERTIES {
+                startCap: D2D1_CAP_STYLE_ROUND,
+                endCap: D2D1_CAP_STYLE_ROUND,
+                dashCap: D2D1_CAP_STYLE_ROUND,
+                lineJoin: D2D1_LINE_JOIN_ROUND,
+                miterLimit: 10.0,
+                dashStyle: D2D1_DASH_STYLE_SOLID,
+                dashOffset: 0.0,
+            };
+            let style = unsafe { self.factory.CreateStrokeStyle(&props, None).ok()? };
+            self.round_style = Some(style);
+        }
+        self.round_style.as_ref()
+    }
+
+    /// Stroke style for an element outline. `None` for the plain default, which Direct2D
+    /// draws without a style object, or if the style cannot be created.
+    pub fn get_element_stroke_style(&mut self, style: &StrokeStyle) -> Option<ID2D1StrokeStyle> {
+        if style.is_default() {
+            return None;
+        }
+        if let Some((_, cached)) = self.element_styles.iter().find(|(key, _)| key == style) {
+            return Some(cached.clone());
+        }
+
+        let cap: D2D1_CAP_STYLE = match style.cap {
+            LineCap::Flat => D2D1_CAP_STYLE_FLAT,
+            LineCap::Round => D2D1_CAP_STYLE_ROUND,
+            LineCap::Square => D2D1_CAP_STYLE_SQUARE,
+        };
+        let dashes = style.dash_array();
+        let props = D2D1_STROKE_STYLE_PROPERTIES {
+            startCap: cap,
+            endCap: cap,
+            dashCap: cap,
+            lineJoin: match style.join {
+                LineJoin::Miter => D2D1_LINE_JOIN_MITER,
+                LineJoin::Round => D2D1_LINE_JOIN_ROUND,
+                LineJoin::Bevel => D2D1_LINE_JOIN_BEVEL,
+            },
+            miterLimit: 10.0,
+            dashStyle: if dashes.is_some() {
+                D2D1_DASH_STYLE_CUSTOM
+            } else {
+                D2D1_DASH_STYLE_SOLID
+            },
+            dashOffset: 0.0,
+        };
+        let created = unsafe {
+            self.factory
+                .CreateStrokeStyle(&props, dashes.as_deref())
+                .ok()?
+        };
+        self.element_styles.push((style.clone(), created.clone()));
+        Some(created)
+    }
+
     pub fn clear_brush_cache(&mut self) {
         self.brush_cache.clear();
     }
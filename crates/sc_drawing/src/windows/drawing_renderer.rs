@@ -17,10 +17,13 @@ use windows::Win32::Graphics::DirectWrite::{
 use windows::core::{PCWSTR, w};
 use windows_numerics::{Matrix3x2, Vector2};
 
-use crate::{Color, DrawingElement, DrawingTool, Point, defaults::MIN_FONT_SIZE};
+use crate::{Color, DrawingElement, DrawingTool, Point, Rect, defaults::MIN_FONT_SIZE};
 
 use super::cache::{ElementId, GeometryCache};
 use super::context::{BorderStyle, RenderContext, RenderOptions};
+use super::elements::common::{
+    push_element_rotation, render_handles_8, render_selection_border, restore_transform,
+};
 use super::renderable::{RenderError, RenderResult, RendererRegistry};
 
 /// Text rendering constants (kept aligned with the app defaults)
@@ -43,6 +46,10 @@ pub struct TextCursorState {
 }
 
 /// Scene-level renderer for drawing elements on Windows.
+///
+/// Elements that are not being edited are cached in a transparent layer. Highlighters blend
+/// against what is underneath, so the layer only holds the elements below the first one; that
+/// highlighter and everything above it are drawn live, in z-order.
 #[derive(Default)]
 pub struct DrawingRenderer {
     registry: RendererRegistry,
@@ -50,6 +57,8 @@ pub struct DrawingRenderer {
 
     static_layer: Option<ID2D1BitmapRenderTarget>,
     static_layer_size: (u32, u32),
+    /// Index of the first element that is not in the static layer.
+    static_layer_end: usize,
 
     pen_stroke_cache: Option<ID2D1BitmapRenderTarget>,
     pen_stroke_size: (u32, u32),
@@ -63,6 +72,7 @@ impl DrawingRenderer {
             geometry_cache: GeometryCache::new(),
             static_layer: None,
             static_layer_size: (0, 0),
+            static_layer_end: 0,
             pen_stroke_cache: None,
             pen_stroke_size: (0, 0),
             last_drawn_point_index: 0,
@@ -102,6 +112,7 @@ impl DrawingRenderer {
         current_element: Option<&DrawingElement>,
         selected_index: Option<usize>,
         cursor: Option<TextCursorState>,
+        rubber_band: Option<Rect>,
         clip_rect: Option<&RECT>,
         static_layer_needs_rebuild: bool,
     ) -> RenderResult<bool> {
@@ -132,16 +143,19 @@ impl DrawingRenderer {
             rebuilt = true;
         }
 
-        // Draw static layer if available, otherwise draw the static content directly.
-        if self.static_layer.is_some() {
+        // Draw the static layer if available, then whatever it does not hold directly.
+        let live_start = if self.static_layer.is_some() {
             self.draw_static_layer(render_target)?;
+            self.static_layer_end
         } else {
+            0
+        };
+        {
             let mut ctx = RenderContext::new(factory, render_target, dwrite_factory);
-            for (i, element) in elements.iter().enumerate() {
-                if Some(i) == selected_index {
-                    continue;
+            for (i, element) in elements.iter().enumerate().skip(live_start) {
+                if Some(i) != selected_index {
+                    self.draw_element(element, &mut ctx, None)?;
                 }
-                self.draw_element(element, &mut ctx, None)?;
             }
         }
 
@@ -154,8 +168,14 @@ impl DrawingRenderer {
         }
 
         // Dynamic: current element
+        // A two-point pen stroke may be a Shift-straightened line whose end keeps moving, which
+        // the append-only stroke cache cannot follow. Dashes would restart on every cached
+        // segment, so dashed strokes are redrawn whole.
         if let Some(element) = current_element {
-            if element.tool == DrawingTool::Pen {
+            if element.tool == DrawingTool::Pen
+                && element.points.len() > 2
+                && element.stroke.dash_array().is_none()
+            {
                 self.draw_incremental_pen_stroke(factory, render_target, screen_size, element)?;
                 self.draw_pen_stroke_from_cache(render_target)?;
             } else {
@@ -173,6 +193,7 @@ impl DrawingRenderer {
             dwrite_factory,
             elements,
             editing_text_id,
+            rubber_band,
         )?;
 
         // Pop clip
@@ -251,12 +272,16 @@ impl DrawingRenderer {
         let layer_rt: &ID2D1RenderTarget = &layer;
         let mut ctx = RenderContext::new(factory, layer_rt, dwrite_factory);
 
-        for (i, element) in elements.iter().enumerate() {
-            if Some(i) == selected_index {
-                continue;
+        let end = elements
+            .iter()
+            .position(|element| element.tool == DrawingTool::Highlighter)
+            .unwrap_or(elements.len());
+        for (i, element) in elements[..end].iter().enumerate() {
+            if Some(i) != selected_index {
+                self.draw_element(element, &mut ctx, None)?;
             }
-            self.draw_element(element, &mut ctx, None)?;
         }
+        self.static_layer_end = end;
 
         unsafe {
             layer.EndDraw(None, None).map_err(|e| {
@@ -282,6 +307,7 @@ impl DrawingRenderer {
         dwrite_factory: Option<&IDWriteFactory>,
         elements: &[DrawingElement],
         editing_text_id: Option<ElementId>,
+        rubber_band: Option<Rect>,
     ) -> RenderResult<()> {
         let mut ctx = RenderContext::new(factory, render_target, dwrite_factory);
 
@@ -293,6 +319,28 @@ impl DrawingRenderer {
             ..RenderOptions::default()
         };
 
+        if let Some(band) = rubber_band {
+            render_selection_border(band, &mut ctx, &options)?;
+        }
+
+        // Several selected elements: outline each one and put the handles on their combined box.
+        let selected: Vec<&DrawingElement> = elements.iter().filter(|e| e.selected).collect();
+        if selected.len() > 1 {
+            let border_only = RenderOptions {
+                show_handles: false,
+                ..options.clone()
+            };
+            for element in &selected {
+                self.registry
+                    .render_element_selection(element, &mut ctx, &border_only)?;
+            }
+            if let Some(bounds) = crate::interaction::combined_bounds(selected) {
+                render_selection_border(bounds, &mut ctx, &options)?;
+                render_handles_8(bounds, &mut ctx, &options)?;
+            }
+            return Ok(());
+        }
+
         for element in elements.iter().filter(|e| e.selected) {
             if element.tool == DrawingTool::Text {
                 // Only show the text box (selection border + handles) while editing this text element.
@@ -314,11 +362,18 @@ impl DrawingRenderer {
         ctx: &mut RenderContext,
         cursor: Option<TextCursorState>,
     ) -> RenderResult<()> {
-        match element.tool {
+        let rotated = push_element_rotation(ctx, element);
+        let result = match element.tool {
             DrawingTool::Pen => self.draw_pen_element(ctx, element),
             DrawingTool::Text => self.draw_text_element(ctx, element, cursor),
+            DrawingTool::Callout => self
+                .registry
+                .render_element(element, ctx)
+                .and_then(|()| self.draw_text_element(ctx, element, cursor)),
             _ => self.registry.render_element(element, ctx),
-        }
+        };
+        restore_transform(ctx, rotated);
+        result
     }
 
     /// Draws an element without mutating internal caches.
@@ -327,6 +382,17 @@ impl DrawingRenderer {
         &self,
         element: &DrawingElement,
         ctx: &mut RenderContext,
+    ) -> RenderResult<()> {
+        let rotated = push_element_rotation(ctx, element);
+        let result = self.draw_element_export_in_frame(element, ctx);
+        restore_transform(ctx, rotated);
+        result
+    }
+
+    fn draw_element_export_in_frame(
+        &self,
+        element: &DrawingElement,
+        ctx: &mut RenderContext,
     ) -> RenderResult<()> {
         match element.tool {
             DrawingTool::Pen => {
@@ -339,10 +405,15 @@ impl DrawingRenderer {
                     .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
                     .clone();
 
+                let stroke_style = ctx.get_element_stroke_style(&element.stroke);
                 if let Some(path) = Self::create_pen_path_geometry(ctx.factory, &element.points) {
                     unsafe {
-                        ctx.render_target
-                            .DrawGeometry(&path, &brush, element.thickness, None);
+                        ctx.render_target.DrawGeometry(
+                            &path,
+                            &brush,
+                            element.thickness,
+                            stroke_style.as_ref(),
+                        );
                     }
                 }
 
@@ -354,6 +425,10 @@ impl DrawingRenderer {
                 }
                 draw_text_layout_with_cursor(ctx, element, None, false)
             }
+            DrawingTool::Callout => {
+                self.registry.render_element(element, ctx)?;
+                draw_text_layout_with_cursor(ctx, element, None, false)
+            }
             _ => self.registry.render_element(element, ctx),
         }
     }
@@ -372,6 +447,7 @@ impl DrawingRenderer {
             .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
             .clone();
 
+        let stroke_style = ctx.get_element_stroke_style(&element.stroke);
         let id = element.id;
         let factory = ctx.factory;
         let points = &element.points;
@@ -384,8 +460,12 @@ impl DrawingRenderer {
             })?;
 
         unsafe {
-            ctx.render_target
-                .DrawGeometry(geometry, &brush, element.thickness, None);
+            ctx.render_target.DrawGeometry(
+                geometry,
+                &brush,
+                element.thickness,
+                stroke_style.as_ref(),
+            );
         }
 
         Ok(())
@@ -529,6 +609,7 @@ impl DrawingRenderer {
                 .get_brush(element.color)
                 .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
                 .clone();
+            let stroke_style = ctx.get_element_stroke_style(&element.stroke);
 
             for i in start_idx..current_len.saturating_sub(1) {
                 let p1 = &points[i];
@@ -543,7 +624,7 @@ impl DrawingRenderer {
                     Y: p2.y as f32,
                 };
 
-                cache_rt.DrawLine(start, end, &brush, element.thickness, None);
+                cache_rt.DrawLine(start, end, &brush, element.thickness, stroke_style.as_ref());
             }
 
             cache.EndDraw(None, None).map_err(|e| {
@@ -1,34 +1,115 @@
+use windows::Win32::Graphics::Direct2D::Common::{
+    D2D1_FIGURE_BEGIN_FILLED, D2D1_FIGURE_BEGIN_HOLLOW, D2D1_FIGURE_END_CLOSED,
+    D2D1_FIGURE_END_OPEN,
+};
+use windows::Win32::Graphics::Direct2D::{
+    D2D1_ELLIPSE, D2D1_QUADRATIC_BEZIER_SEGMENT, ID2D1PathGeometry, ID2D1SolidColorBrush,
+    ID2D1StrokeStyle,
+};
 use windows_numerics::Vector2;
 
 use super::common::render_endpoint_handles;
 use crate::windows::context::{RenderContext, RenderOptions};
 use crate::windows::renderable::{RenderError, RenderResult, Renderable};
-use crate::{DrawingElement, Rect};
+use crate::{ArrowMarker, ArrowPath, ArrowShape, DashStyle, DrawingElement, Rect, StrokeStyle};
 
 pub struct ArrowRenderer;
 
+fn vector((x, y): (f64, f64)) -> Vector2 {
+    Vector2 {
+        X: x as f32,
+        Y: y as f32,
+    }
+}
+
 impl ArrowRenderer {
-    const ARROW_LENGTH: f64 = 15.0;
-    const ARROW_ANGLE: f64 = 0.5;
-    const MIN_LENGTH_FOR_HEAD: f64 = 20.0;
+    /// Open figure running along a curved shaft.
+    fn curve_geometry(ctx: &RenderContext, shaft: &ArrowPath) -> Option<ID2D1PathGeometry> {
+        let control = shaft.control?;
+        unsafe {
+            let geometry = ctx.factory.CreatePathGeometry().ok()?;
+            let sink = geometry.Open().ok()?;
+            sink.BeginFigure(vector(shaft.start), D2D1_FIGURE_BEGIN_HOLLOW);
+            sink.AddQuadraticBezier(&D2D1_QUADRATIC_BEZIER_SEGMENT {
+                point1: vector(control),
+                point2: vector(shaft.end),
+            });
+            sink.EndFigure(D2D1_FIGURE_END_OPEN);
+            sink.Close().ok()?;
+            Some(geometry)
+        }
+    }
+
+    /// Closed, filled triangle.
+    fn triangle_geometry(
+        ctx: &RenderContext,
+        corners: &[(f64, f64); 3],
+    ) -> Option<ID2D1PathGeometry> {
+        unsafe {
+            let geometry = ctx.factory.CreatePathGeometry().ok()?;
+            let sink = geometry.Open().ok()?;
+            sink.BeginFigure(vector(corners[0]), D2D1_FIGURE_BEGIN_FILLED);
+            sink.AddLine(vector(corners[1]));
+            sink.AddLine(vector(corners[2]));
+            sink.EndFigure(D2D1_FIGURE_END_CLOSED);
+            sink.Close().ok()?;
+            Some(geometry)
+        }
+    }
+
+    fn render_marker(
+        marker: &ArrowMarker,
+        brush: &ID2D1SolidColorBrush,
+        thickness: f32,
+        style: Option<&ID2D1StrokeStyle>,
+        ctx: &RenderContext,
+    ) -> RenderResult {
+        unsafe {
+            match marker {
+                ArrowMarker::Open([wing1, tip, wing2]) => {
+                    ctx.render_target.DrawLine(
+                        vector(*tip),
+                        vector(*wing1),
+                        brush,
+                        thickness,
+                        style,
+                    );
+                    ctx.render_target.DrawLine(
+                        vector(*tip),
+                        vector(*wing2),
+                        brush,
+                        thickness,
+                        style,
+                    );
+                }
+                ArrowMarker::Triangle(corners) => {
+                    let geometry = Self::triangle_geometry(ctx, corners).ok_or_else(|| {
+                        RenderError::ResourceCreation("Failed to create arrow head".into())
+                    })?;
+                    ctx.render_target.FillGeometry(&geometry, brush, None);
+                }
+                ArrowMarker::Dot { center, radius } => {
+                    let ellipse = D2D1_ELLIPSE {
+                        point: vector(*center),
+                        radiusX: *radius as f32,
+                        radiusY: *radius as f32,
+                    };
+                    ctx.render_target.FillEllipse(&ellipse, brush);
+                }
+                ArrowMarker::Bar([from, to]) => {
+                    ctx.render_target
+                        .DrawLine(vector(*from), vector(*to), brush, thickness, style);
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Renderable for ArrowRenderer {
     fn render(&self, element: &DrawingElement, ctx: &mut RenderContext) -> RenderResult {
-        if element.points.len() < 2 {
+        let Some(shape) = ArrowShape::of(element) else {
             return Ok(());
-        }
-
-        let p0 = &element.points[0];
-        let p1 = &element.points[1];
-
-        let start = Vector2 {
-            X: p0.x as f32,
-            Y: p0.y as f32,
-        };
-        let end = Vector2 {
-            X: p1.x as f32,
-            Y: p1.y as f32,
         };
 
         let brush = ctx
@@ -36,46 +117,39 @@ impl Renderable for ArrowRenderer {
             .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
             .clone();
 
-        unsafe {
-            ctx.render_target
-                .DrawLine(start, end, &brush, element.thickness, None);
-
-            let dx = p1.x - p0.x;
-            let dy = p1.y - p0.y;
-            let length = ((dx * dx + dy * dy) as f64).sqrt();
+        // Only the shaft is dashed; a broken head would be hard to read.
+        let shaft_style = ctx.get_element_stroke_style(&element.stroke);
+        let head_style = ctx.get_element_stroke_style(&StrokeStyle {
+            dash: DashStyle::Solid,
+            ..element.stroke.clone()
+        });
 
-            if length > Self::MIN_LENGTH_FOR_HEAD {
-                let unit_x = dx as f64 / length;
-                let unit_y = dy as f64 / length;
-
-                let cos_angle = Self::ARROW_ANGLE.cos();
-                let sin_angle = Self::ARROW_ANGLE.sin();
-
-                let wing1 = Vector2 {
-                    X: (p1.x as f64
-                        - Self::ARROW_LENGTH * (unit_x * cos_angle + unit_y * sin_angle))
-                        as f32,
-                    Y: (p1.y as f64
-                        - Self::ARROW_LENGTH * (unit_y * cos_angle - unit_x * sin_angle))
-                        as f32,
-                };
-
-                let wing2 = Vector2 {
-                    X: (p1.x as f64
-                        - Self::ARROW_LENGTH * (unit_x * cos_angle - unit_y * sin_angle))
-                        as f32,
-                    Y: (p1.y as f64
-                        - Self::ARROW_LENGTH * (unit_y * cos_angle + unit_x * sin_angle))
-                        as f32,
-                };
-
-                ctx.render_target
-                    .DrawLine(end, wing1, &brush, element.thickness, None);
-                ctx.render_target
-                    .DrawLine(end, wing2, &brush, element.thickness, None);
+        unsafe {
+            if shape.shaft.control.is_some() {
+                let geometry = Self::curve_geometry(ctx, &shape.shaft).ok_or_else(|| {
+                    RenderError::ResourceCreation("Failed to create arrow geometry".into())
+                })?;
+                ctx.render_target.DrawGeometry(
+                    &geometry,
+                    &brush,
+                    element.thickness,
+                    shaft_style.as_ref(),
+                );
+            } else {
+                ctx.render_target.DrawLine(
+                    vector(shape.shaft.start),
+                    vector(shape.shaft.end),
+                    &brush,
+                    element.thickness,
+                    shaft_style.as_ref(),
+                );
             }
         }
 
+        for marker in [shape.head, shape.tail].iter().flatten() {
+            Self::render_marker(marker, &brush, element.thickness, head_style.as_ref(), ctx)?;
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,102 @@
+use windows::Win32::Graphics::Direct2D::Common::{
+    D2D1_FIGURE_BEGIN_FILLED, D2D1_FIGURE_END_CLOSED,
+};
+use windows::Win32::Graphics::Direct2D::{D2D1_QUADRATIC_BEZIER_SEGMENT, ID2D1PathGeometry};
+use windows_numerics::Vector2;
+
+use super::common::{render_handles_corners, render_selection_border};
+use crate::windows::context::{RenderContext, RenderOptions};
+use crate::windows::renderable::{RenderError, RenderResult, Renderable};
+use crate::{CalloutShape, DrawingElement, OutlineSegment, Rect};
+
+/// Draws the bubble of a callout. Its text goes on top through the same path as text elements,
+/// so that the editing cursor shows up in both.
+pub struct CalloutRenderer;
+
+fn vector((x, y): (f64, f64)) -> Vector2 {
+    Vector2 {
+        X: x as f32,
+        Y: y as f32,
+    }
+}
+
+impl CalloutRenderer {
+    fn outline_geometry(ctx: &RenderContext, shape: &CalloutShape) -> Option<ID2D1PathGeometry> {
+        unsafe {
+            let geometry = ctx.factory.CreatePathGeometry().ok()?;
+            let sink = geometry.Open().ok()?;
+            sink.BeginFigure(vector(shape.start), D2D1_FIGURE_BEGIN_FILLED);
+            for segment in &shape.segments {
+                match *segment {
+                    OutlineSegment::Line(to) => sink.AddLine(vector(to)),
+                    OutlineSegment::Curve { control, to } => {
+                        sink.AddQuadraticBezier(&D2D1_QUADRATIC_BEZIER_SEGMENT {
+                            point1: vector(control),
+                            point2: vector(to),
+                        })
+                    }
+                }
+            }
+            sink.EndFigure(D2D1_FIGURE_END_CLOSED);
+            sink.Close().ok()?;
+            Some(geometry)
+        }
+    }
+}
+
+impl Renderable for CalloutRenderer {
+    fn render(&self, element: &DrawingElement, ctx: &mut RenderContext) -> RenderResult {
+        let Some(shape) = CalloutShape::of(element) else {
+            return Ok(());
+        };
+        let geometry = Self::outline_geometry(ctx, &shape).ok_or_else(|| {
+            RenderError::ResourceCreation("Failed to create callout geometry".into())
+        })?;
+
+        if let Some(fill) = element.fill {
+            let fill_brush = ctx
+                .get_brush(fill)
+                .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
+                .clone();
+            unsafe {
+                ctx.render_target.FillGeometry(&geometry, &fill_brush, None);
+            }
+        }
+
+        let brush = ctx
+            .get_brush(element.color)
+            .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
+            .clone();
+
+        let stroke_style = ctx.get_element_stroke_style(&element.stroke);
+
+        unsafe {
+            ctx.render_target.DrawGeometry(
+                &geometry,
+                &brush,
+                element.thickness,
+                stroke_style.as_ref(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn render_selection(
+        &self,
+        bounds: Rect,
+        ctx: &mut RenderContext,
+        options: &RenderOptions,
+    ) -> RenderResult {
+        render_selection_border(bounds, ctx, options)
+    }
+
+    fn render_handles(
+        &self,
+        bounds: Rect,
+        ctx: &mut RenderContext,
+        options: &RenderOptions,
+    ) -> RenderResult {
+        render_handles_corners(bounds, ctx, options)
+    }
+}
@@ -31,14 +31,30 @@ impl Renderable for CircleRenderer {
             radiusY: radius_y,
         };
 
+        if let Some(fill) = element.fill {
+            let fill_brush = ctx
+                .get_brush(fill)
+                .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
+                .clone();
+            unsafe {
+                ctx.render_target.FillEllipse(&ellipse, &fill_brush);
+            }
+        }
+
         let brush = ctx
             .get_brush(element.color)
             .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
             .clone();
 
+        let stroke_style = ctx.get_element_stroke_style(&element.stroke);
+
         unsafe {
-            ctx.render_target
-                .DrawEllipse(&ellipse, &brush, element.thickness, None);
+            ctx.render_target.DrawEllipse(
+                &ellipse,
+                &brush,
+                element.thickness,
+                stroke_style.as_ref(),
+            );
         }
 
         Ok(())
@@ -1,10 +1,11 @@
 use windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F;
 use windows::Win32::Graphics::Direct2D::D2D1_ELLIPSE;
-use windows_numerics::Vector2;
+use windows_numerics::{Matrix3x2, Vector2};
 
-use crate::Rect;
+use crate::interaction::ROTATION_HANDLE_DISTANCE;
 use crate::windows::context::{BorderStyle, RenderContext, RenderOptions};
 use crate::windows::renderable::{RenderError, RenderResult};
+use crate::{ArrowPath, DrawingElement, Point, Rect};
 
 pub fn render_selection_border(
     bounds: Rect,
@@ -115,6 +116,96 @@ fn render_handle_circles(
     Ok(())
 }
 
+/// Turn the target about the element's centre on top of its current transform, so that the
+/// element can be drawn in its own unrotated frame.
+///
+/// Returns the transform to put back with [`restore_transform`], or `None` if the element is
+/// not rotated.
+pub fn push_element_rotation(ctx: &RenderContext, element: &DrawingElement) -> Option<Matrix3x2> {
+    if !element.is_rotated() {
+        return None;
+    }
+    let (cx, cy) = element.rect.center();
+    let rotation = Matrix3x2::rotation_around(
+        element.rotation,
+        Vector2 {
+            X: cx as f32,
+            Y: cy as f32,
+        },
+    );
+    let mut previous = Matrix3x2::identity();
+    unsafe {
+        ctx.render_target.GetTransform(&mut previous);
+        ctx.render_target.SetTransform(&(rotation * previous));
+    }
+    Some(previous)
+}
+
+pub fn restore_transform(ctx: &RenderContext, previous: Option<Matrix3x2>) {
+    if let Some(previous) = previous {
+        unsafe {
+            ctx.render_target.SetTransform(&previous);
+        }
+    }
+}
+
+/// Rotation handle above the top edge of `bounds`, joined to it by a short line.
+pub fn render_rotation_handle(
+    bounds: Rect,
+    ctx: &mut RenderContext,
+    options: &RenderOptions,
+) -> RenderResult {
+    let mid_x = (bounds.left + bounds.right) as f32 / 2.0;
+    let top = bounds.top as f32;
+    let handle_y = (bounds.top - ROTATION_HANDLE_DISTANCE) as f32;
+
+    let brush = ctx
+        .get_brush(options.selection_color)
+        .ok_or_else(|| RenderError::ResourceCreation("Failed to create selection brush".into()))?
+        .clone();
+    unsafe {
+        ctx.render_target.DrawLine(
+            Vector2 { X: mid_x, Y: top },
+            Vector2 {
+                X: mid_x,
+                Y: handle_y,
+            },
+            &brush,
+            1.0,
+            None,
+        );
+    }
+
+    render_handle_circles(ctx, &[(mid_x, handle_y)], options)
+}
+
+/// Handles at both ends of an arrow and at its bend handle halfway along.
+pub fn render_arrow_handles(
+    path: &ArrowPath,
+    ctx: &mut RenderContext,
+    options: &RenderOptions,
+) -> RenderResult {
+    let handle = |(x, y): (f64, f64)| (x as f32, y as f32);
+    render_handle_circles(
+        ctx,
+        &[
+            handle(path.start),
+            handle(path.midpoint()),
+            handle(path.end),
+        ],
+        options,
+    )
+}
+
+/// Handle on the tip of a callout's tail.
+pub fn render_callout_tail_handle(
+    tip: Point,
+    ctx: &mut RenderContext,
+    options: &RenderOptions,
+) -> RenderResult {
+    render_handle_circles(ctx, &[(tip.x as f32, tip.y as f32)], options)
+}
+
 pub fn render_endpoint_handles(
     start: (f32, f32),
     end: (f32, f32),
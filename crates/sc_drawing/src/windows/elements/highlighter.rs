@@ -0,0 +1,92 @@
+use windows::Win32::Graphics::Direct2D::{
+    D2D1_ANTIALIAS_MODE_ALIASED, D2D1_PRIMITIVE_BLEND_MIN, D2D1_PRIMITIVE_BLEND_SOURCE_OVER,
+    ID2D1DeviceContext,
+};
+use windows::core::Interface;
+
+use super::PenRenderer;
+use super::common::render_selection_border;
+use crate::blend::multiply_tint;
+use crate::windows::context::{RenderContext, RenderOptions};
+use crate::windows::renderable::{RenderError, RenderResult, Renderable};
+use crate::{DrawingElement, Rect};
+
+/// Draws highlighter strokes.
+///
+/// The whole stroke is a single geometry, so overlapping segments are covered once instead
+/// of stacking their alpha. Direct2D has no multiply blend for primitives, so on targets that
+/// expose `ID2D1DeviceContext` the opaque [`multiply_tint`] is drawn with a per-channel
+/// minimum instead. That matches a multiply on white and leaves dark text untouched, but
+/// mid-tones are darkened less: a 0.5 grey under a 0.5 tint stays 0.5 where a multiply would
+/// give 0.25. Other targets fall back to plain alpha blending.
+///
+/// The minimum needs the backdrop, so callers must draw highlighters onto a target that
+/// already holds the image underneath (not onto a transparent cache layer).
+pub struct HighlighterRenderer;
+
+impl Renderable for HighlighterRenderer {
+    fn render(&self, element: &DrawingElement, ctx: &mut RenderContext) -> RenderResult {
+        if element.points.len() < 2 {
+            return Ok(());
+        }
+
+        let geometry =
+            PenRenderer::create_path_geometry(ctx, &element.points).ok_or_else(|| {
+                RenderError::ResourceCreation("Failed to create path geometry".into())
+            })?;
+        let style = ctx
+            .get_round_style()
+            .ok_or_else(|| RenderError::ResourceCreation("Failed to create stroke style".into()))?
+            .clone();
+
+        let device_context = ctx.render_target.cast::<ID2D1DeviceContext>().ok();
+        let color = if device_context.is_some() {
+            multiply_tint(element.color)
+        } else {
+            element.color
+        };
+        let brush = ctx
+            .get_brush(color)
+            .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
+            .clone();
+
+        unsafe {
+            match device_context {
+                Some(dc) => {
+                    // Antialiased edges would be blended as darker partial coverage under
+                    // the minimum, leaving a dark rim; draw them aliased instead.
+                    let antialias = dc.GetAntialiasMode();
+                    dc.SetAntialiasMode(D2D1_ANTIALIAS_MODE_ALIASED);
+                    dc.SetPrimitiveBlend(D2D1_PRIMITIVE_BLEND_MIN);
+                    dc.DrawGeometry(&geometry, &brush, element.thickness, &style);
+                    dc.SetPrimitiveBlend(D2D1_PRIMITIVE_BLEND_SOURCE_OVER);
+                    dc.SetAntialiasMode(antialias);
+                }
+                None => {
+                    ctx.render_target
+                        .DrawGeometry(&geometry, &brush, element.thickness, &style);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_selection(
+        &self,
+        bounds: Rect,
+        ctx: &mut RenderContext,
+        options: &RenderOptions,
+    ) -> RenderResult {
+        render_selection_border(bounds, ctx, options)
+    }
+
+    fn render_handles(
+        &self,
+        _bounds: Rect,
+        _ctx: &mut RenderContext,
+        _options: &RenderOptions,
+    ) -> RenderResult {
+        Ok(())
+    }
+}
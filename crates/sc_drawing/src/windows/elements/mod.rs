@@ -1,12 +1,20 @@
 mod arrow;
+mod callout;
 mod circle;
 pub mod common;
+mod highlighter;
 mod pen;
 mod rectangle;
+mod redaction;
+mod step;
 mod text;
 
 pub use arrow::ArrowRenderer;
+pub use callout::CalloutRenderer;
 pub use circle::CircleRenderer;
+pub use highlighter::HighlighterRenderer;
 pub use pen::{CachedPenRenderer, PenRenderer};
 pub use rectangle::RectangleRenderer;
+pub use redaction::RedactionRenderer;
+pub use step::StepRenderer;
 pub use text::TextRenderer;
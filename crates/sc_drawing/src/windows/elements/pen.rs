@@ -33,7 +33,10 @@ impl PenRenderer {
         self.geometry_cache.remove(&element_id);
     }
 
-    fn create_path_geometry(ctx: &RenderContext, points: &[Point]) -> Option<ID2D1PathGeometry> {
+    pub(super) fn create_path_geometry(
+        ctx: &RenderContext,
+        points: &[Point],
+    ) -> Option<ID2D1PathGeometry> {
         if points.len() < 2 {
             return None;
         }
@@ -79,9 +82,15 @@ impl Renderable for PenRenderer {
             RenderError::ResourceCreation("Failed to create path geometry".into())
         })?;
 
+        let stroke_style = ctx.get_element_stroke_style(&element.stroke);
+
         unsafe {
-            ctx.render_target
-                .DrawGeometry(&path_geometry, &brush, element.thickness, None);
+            ctx.render_target.DrawGeometry(
+                &path_geometry,
+                &brush,
+                element.thickness,
+                stroke_style.as_ref(),
+            );
         }
 
         Ok(())
@@ -136,9 +145,15 @@ impl<'a> CachedPenRenderer<'a> {
             new_geom
         };
 
+        let stroke_style = ctx.get_element_stroke_style(&element.stroke);
+
         unsafe {
-            ctx.render_target
-                .DrawGeometry(&geometry, &brush, element.thickness, None);
+            ctx.render_target.DrawGeometry(
+                &geometry,
+                &brush,
+                element.thickness,
+                stroke_style.as_ref(),
+            );
         }
 
         Ok(())
@@ -23,14 +23,30 @@ impl Renderable for RectangleRenderer {
             bottom: p0.y.max(p1.y) as f32,
         };
 
+        if let Some(fill) = element.fill {
+            let fill_brush = ctx
+                .get_brush(fill)
+                .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
+                .clone();
+            unsafe {
+                ctx.render_target.FillRectangle(&rect, &fill_brush);
+            }
+        }
+
         let brush = ctx
             .get_brush(element.color)
             .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
             .clone();
 
+        let stroke_style = ctx.get_element_stroke_style(&element.stroke);
+
         unsafe {
-            ctx.render_target
-                .DrawRectangle(&rect, &brush, element.thickness, None);
+            ctx.render_target.DrawRectangle(
+                &rect,
+                &brush,
+                element.thickness,
+                stroke_style.as_ref(),
+            );
         }
 
         Ok(())
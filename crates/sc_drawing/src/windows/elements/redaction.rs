@@ -0,0 +1,123 @@
+use std::ffi::c_void;
+
+use windows::Win32::Graphics::Direct2D::Common::{
+    D2D_RECT_F, D2D_SIZE_U, D2D1_ALPHA_MODE_IGNORE, D2D1_PIXEL_FORMAT,
+};
+use windows::Win32::Graphics::Direct2D::{
+    D2D1_BITMAP_INTERPOLATION_MODE_LINEAR, D2D1_BITMAP_INTERPOLATION_MODE_NEAREST_NEIGHBOR,
+    D2D1_BITMAP_PROPERTIES,
+};
+use windows::Win32::Graphics::Dxgi::Common::{
+    DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_FORMAT_R8G8B8A8_UNORM,
+};
+
+use super::common::{render_handles_8, render_selection_border};
+use crate::windows::context::{RenderContext, RenderOptions};
+use crate::windows::renderable::{RenderError, RenderResult, Renderable};
+use crate::{ChannelOrder, Color, DrawingElement, DrawingTool, Rect};
+
+/// Shown when no source image is attached; still hides what is underneath.
+const PLACEHOLDER_COLOR: Color = Color {
+    r: 0.5,
+    g: 0.5,
+    b: 0.5,
+    a: 1.0,
+};
+
+/// Draws the pre-filtered patch of mosaic/blur elements.
+pub struct RedactionRenderer;
+
+impl Renderable for RedactionRenderer {
+    fn render(&self, element: &DrawingElement, ctx: &mut RenderContext) -> RenderResult {
+        if element.points.len() < 2 {
+            return Ok(());
+        }
+
+        let Some(patch) = element.redaction.as_ref() else {
+            let brush = ctx
+                .get_brush(PLACEHOLDER_COLOR)
+                .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
+                .clone();
+            unsafe {
+                ctx.render_target
+                    .FillRectangle(&to_d2d_rect(element.rect), &brush);
+            }
+            return Ok(());
+        };
+
+        let pixels = &patch.pixels;
+        let format = match pixels.order {
+            ChannelOrder::Rgba => DXGI_FORMAT_R8G8B8A8_UNORM,
+            ChannelOrder::Bgra => DXGI_FORMAT_B8G8R8A8_UNORM,
+        };
+        // Capture pixels come from GDI and may carry a zero alpha; the patch is always opaque.
+        let properties = D2D1_BITMAP_PROPERTIES {
+            pixelFormat: D2D1_PIXEL_FORMAT {
+                format,
+                alphaMode: D2D1_ALPHA_MODE_IGNORE,
+            },
+            dpiX: 96.0,
+            dpiY: 96.0,
+        };
+        // Mosaic cells must stay crisp when the preview stretches the patch.
+        let interpolation = match element.tool {
+            DrawingTool::Mosaic => D2D1_BITMAP_INTERPOLATION_MODE_NEAREST_NEIGHBOR,
+            _ => D2D1_BITMAP_INTERPOLATION_MODE_LINEAR,
+        };
+        let size = D2D_SIZE_U {
+            width: pixels.width,
+            height: pixels.height,
+        };
+
+        unsafe {
+            let bitmap = ctx
+                .render_target
+                .CreateBitmap(
+                    size,
+                    Some(pixels.pixels.as_ptr() as *const c_void),
+                    pixels.stride() as u32,
+                    &properties,
+                )
+                .map_err(|e| {
+                    RenderError::ResourceCreation(format!("Redaction bitmap failed: {e:?}"))
+                })?;
+
+            ctx.render_target.DrawBitmap(
+                &bitmap,
+                Some(&to_d2d_rect(patch.rect)),
+                1.0,
+                interpolation,
+                None,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn render_selection(
+        &self,
+        bounds: Rect,
+        ctx: &mut RenderContext,
+        options: &RenderOptions,
+    ) -> RenderResult {
+        render_selection_border(bounds, ctx, options)
+    }
+
+    fn render_handles(
+        &self,
+        bounds: Rect,
+        ctx: &mut RenderContext,
+        options: &RenderOptions,
+    ) -> RenderResult {
+        render_handles_8(bounds, ctx, options)
+    }
+}
+
+fn to_d2d_rect(rect: Rect) -> D2D_RECT_F {
+    D2D_RECT_F {
+        left: rect.left as f32,
+        top: rect.top as f32,
+        right: rect.right as f32,
+        bottom: rect.bottom as f32,
+    }
+}
@@ -0,0 +1,121 @@
+use windows::Win32::Graphics::Direct2D::Common::D2D_RECT_F;
+use windows::Win32::Graphics::Direct2D::{D2D1_DRAW_TEXT_OPTIONS_NONE, D2D1_ELLIPSE};
+use windows::Win32::Graphics::DirectWrite::{
+    DWRITE_FONT_STRETCH_NORMAL, DWRITE_FONT_STYLE_NORMAL, DWRITE_FONT_WEIGHT_BOLD,
+    DWRITE_MEASURING_MODE_NATURAL, DWRITE_PARAGRAPH_ALIGNMENT_CENTER, DWRITE_TEXT_ALIGNMENT_CENTER,
+    DWRITE_WORD_WRAPPING_NO_WRAP,
+};
+use windows::core::{PCWSTR, w};
+use windows_numerics::Vector2;
+
+use super::common::render_selection_border;
+use crate::windows::context::{RenderContext, RenderOptions};
+use crate::windows::renderable::{RenderError, RenderResult, Renderable};
+use crate::{Color, DrawingElement, Rect, defaults};
+
+/// Number colour on top of the filled marker.
+const NUMBER_COLOR: Color = Color {
+    r: 1.0,
+    g: 1.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+/// Draws numbered step markers: a filled circle with the step number centred inside.
+pub struct StepRenderer;
+
+impl Renderable for StepRenderer {
+    fn render(&self, element: &DrawingElement, ctx: &mut RenderContext) -> RenderResult {
+        let Some(center) = element.points.first() else {
+            return Ok(());
+        };
+
+        let radius = defaults::STEP_RADIUS as f32;
+        let ellipse = D2D1_ELLIPSE {
+            point: Vector2 {
+                X: center.x as f32,
+                Y: center.y as f32,
+            },
+            radiusX: radius,
+            radiusY: radius,
+        };
+
+        let fill = ctx
+            .get_brush(element.color)
+            .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
+            .clone();
+        unsafe {
+            ctx.render_target.FillEllipse(&ellipse, &fill);
+        }
+
+        let Some(dwrite_factory) = ctx.dwrite_factory else {
+            return Ok(());
+        };
+        let text_brush = ctx
+            .get_brush(NUMBER_COLOR)
+            .ok_or_else(|| RenderError::ResourceCreation("Failed to create brush".into()))?
+            .clone();
+
+        let label: Vec<u16> = element.step_number.to_string().encode_utf16().collect();
+        let font_name: Vec<u16> = element
+            .font_name
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect();
+        // Shrink the font for multi-digit numbers so they stay inside the circle.
+        let font_size = radius * if label.len() > 2 { 0.9 } else { 1.2 };
+
+        unsafe {
+            let text_format = dwrite_factory
+                .CreateTextFormat(
+                    PCWSTR(font_name.as_ptr()),
+                    None,
+                    DWRITE_FONT_WEIGHT_BOLD,
+                    DWRITE_FONT_STYLE_NORMAL,
+                    DWRITE_FONT_STRETCH_NORMAL,
+                    font_size,
+                    w!(""),
+                )
+                .map_err(|e| RenderError::ResourceCreation(format!("TextFormat: {e:?}")))?;
+            let _ = text_format.SetTextAlignment(DWRITE_TEXT_ALIGNMENT_CENTER);
+            let _ = text_format.SetParagraphAlignment(DWRITE_PARAGRAPH_ALIGNMENT_CENTER);
+            let _ = text_format.SetWordWrapping(DWRITE_WORD_WRAPPING_NO_WRAP);
+
+            let layout_rect = D2D_RECT_F {
+                left: center.x as f32 - radius,
+                top: center.y as f32 - radius,
+                right: center.x as f32 + radius,
+                bottom: center.y as f32 + radius,
+            };
+            ctx.render_target.DrawText(
+                &label,
+                &text_format,
+                &layout_rect,
+                &text_brush,
+                D2D1_DRAW_TEXT_OPTIONS_NONE,
+                DWRITE_MEASURING_MODE_NATURAL,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn render_selection(
+        &self,
+        bounds: Rect,
+        ctx: &mut RenderContext,
+        options: &RenderOptions,
+    ) -> RenderResult {
+        render_selection_border(bounds, ctx, options)
+    }
+
+    fn render_handles(
+        &self,
+        _bounds: Rect,
+        _ctx: &mut RenderContext,
+        _options: &RenderOptions,
+    ) -> RenderResult {
+        // Markers have a fixed size, so there is nothing to drag.
+        Ok(())
+    }
+}
@@ -9,7 +9,10 @@ pub mod text;
 pub use cache::{CacheStats, ElementId, GeometryCache};
 pub use context::{BorderStyle, RenderContext, RenderOptions};
 pub use drawing_renderer::{DrawingRenderer, TextCursorState};
-pub use elements::{ArrowRenderer, CircleRenderer, PenRenderer, RectangleRenderer, TextRenderer};
+pub use elements::{
+    ArrowRenderer, CalloutRenderer, CircleRenderer, HighlighterRenderer, PenRenderer,
+    RectangleRenderer, RedactionRenderer, StepRenderer, TextRenderer,
+};
 pub use ext::{PointExt, RectExt};
 pub use renderable::{RenderError, RenderResult, Renderable, RendererRegistry};
 pub use text::{text_padding_for_font_size, update_text_element_size_dwrite};
@@ -1,6 +1,9 @@
 use super::context::{RenderContext, RenderOptions};
-use super::elements::common::render_endpoint_handles;
-use crate::{DrawingElement, DrawingTool, Rect};
+use super::elements::common::{
+    push_element_rotation, render_arrow_handles, render_callout_tail_handle,
+    render_rotation_handle, restore_transform,
+};
+use crate::{ArrowPath, DrawingElement, DrawingTool, Rect};
 
 pub type RenderResult<T = ()> = Result<T, RenderError>;
 
@@ -49,7 +52,11 @@ pub struct RendererRegistry {
     pub circle: super::elements::CircleRenderer,
     pub arrow: super::elements::ArrowRenderer,
     pub pen: super::elements::PenRenderer,
+    pub highlighter: super::elements::HighlighterRenderer,
     pub text: super::elements::TextRenderer,
+    pub redaction: super::elements::RedactionRenderer,
+    pub step: super::elements::StepRenderer,
+    pub callout: super::elements::CalloutRenderer,
 }
 
 impl Default for RendererRegistry {
@@ -65,7 +72,11 @@ impl RendererRegistry {
             circle: super::elements::CircleRenderer,
             arrow: super::elements::ArrowRenderer,
             pen: super::elements::PenRenderer::new(),
+            highlighter: super::elements::HighlighterRenderer,
             text: super::elements::TextRenderer,
+            redaction: super::elements::RedactionRenderer,
+            step: super::elements::StepRenderer,
+            callout: super::elements::CalloutRenderer,
         }
     }
 
@@ -75,7 +86,11 @@ impl RendererRegistry {
             DrawingTool::Circle => &self.circle,
             DrawingTool::Arrow => &self.arrow,
             DrawingTool::Pen => &self.pen,
+            DrawingTool::Highlighter => &self.highlighter,
             DrawingTool::Text => &self.text,
+            DrawingTool::Mosaic | DrawingTool::Blur => &self.redaction,
+            DrawingTool::Step => &self.step,
+            DrawingTool::Callout => &self.callout,
             _ => &self.rectangle,
         }
     }
@@ -96,7 +111,19 @@ impl RendererRegistry {
     ) -> RenderResult {
         let bounds = element.rect;
         let renderer = self.get_renderer(element);
+        let rotated = push_element_rotation(ctx, element);
+        let result = Self::render_selection_in_frame(element, bounds, renderer, ctx, options);
+        restore_transform(ctx, rotated);
+        result
+    }
 
+    fn render_selection_in_frame(
+        element: &DrawingElement,
+        bounds: Rect,
+        renderer: &dyn Renderable,
+        ctx: &mut RenderContext,
+        options: &RenderOptions,
+    ) -> RenderResult {
         if options.show_selection_border {
             renderer.render_selection(bounds, ctx, options)?;
         }
@@ -104,14 +131,20 @@ impl RendererRegistry {
         if options.show_handles {
             // Arrow handles must be rendered at the actual endpoints, not at the bounding-rect corners.
             if element.tool == DrawingTool::Arrow {
-                if element.points.len() >= 2 {
-                    let start = (element.points[0].x as f32, element.points[0].y as f32);
-                    let end = (element.points[1].x as f32, element.points[1].y as f32);
-                    render_endpoint_handles(start, end, ctx, options)?;
+                if let Some(path) = ArrowPath::of(element) {
+                    render_arrow_handles(&path, ctx, options)?;
                 }
             } else {
                 renderer.render_handles(bounds, ctx, options)?;
             }
+            if element.tool == DrawingTool::Callout
+                && let Some(&tip) = element.points.get(2)
+            {
+                render_callout_tail_handle(tip, ctx, options)?;
+            }
+            if element.tool.can_rotate() {
+                render_rotation_handle(bounds, ctx, options)?;
+            }
         }
 
         Ok(())
@@ -0,0 +1,83 @@
+use std::cell::RefCell;
+
+use sc_drawing::history::DrawingAction;
+use sc_drawing::{DrawingElement, ElementClipboard, PASTE_OFFSET, duplicate_elements};
+
+use super::DrawingManager;
+use sc_host_protocol::Command;
+
+thread_local! {
+    /// Shared by every editor on the UI thread, so annotations copied in the capture overlay
+    /// can be pasted into a pinned preview and the next capture.
+    static CLIPBOARD: RefCell<ElementClipboard> = const { RefCell::new(ElementClipboard::new()) };
+}
+
+impl DrawingManager {
+    /// Put the selected elements on the annotation clipboard.
+    pub(super) fn copy_selected_elements(&mut self) -> Vec<Command> {
+        let selected: Vec<_> = self
+            .elements
+            .get_elements()
+            .iter()
+            .filter(|e| e.selected)
+            .collect();
+        if self.text_editing || selected.is_empty() {
+            return vec![];
+        }
+        CLIPBOARD.with_borrow_mut(|clipboard| clipboard.copy(selected));
+        vec![Command::UpdateToolbar]
+    }
+
+    /// Insert the clipboard contents and select them.
+    pub(super) fn paste_elements(&mut self) -> Vec<Command> {
+        if self.text_editing {
+            return vec![];
+        }
+        let copies = CLIPBOARD.with_borrow_mut(ElementClipboard::paste);
+        self.insert_copies(copies)
+    }
+
+    /// Copy the selected elements in place, slightly offset, without touching the clipboard.
+    pub(super) fn duplicate_selected_elements(&mut self) -> Vec<Command> {
+        if self.text_editing {
+            return vec![];
+        }
+        let selected: Vec<_> = self
+            .elements
+            .get_elements()
+            .iter()
+            .filter(|e| e.selected)
+            .cloned()
+            .collect();
+        self.insert_copies(duplicate_elements(&selected, PASTE_OFFSET))
+    }
+
+    /// Add the copies on top as one undo step.
+    fn insert_copies(&mut self, copies: Vec<DrawingElement>) -> Vec<Command> {
+        if copies.is_empty() {
+            return vec![];
+        }
+        let selected_before = self.elements.selected_ids();
+        let ids: Vec<u64> = copies.iter().map(|e| e.id).collect();
+        let mut actions = Vec::new();
+        for element in copies {
+            let position = self.elements.count();
+            actions.push(DrawingAction::AddElement {
+                element: element.clone(),
+                position,
+            });
+            self.elements.add_element(element);
+        }
+        let action = if actions.len() == 1 {
+            actions.remove(0)
+        } else {
+            DrawingAction::Compound { actions }
+        };
+
+        self.select_ids(&ids);
+        self.history.record_action(action, selected_before, ids);
+        self.refresh_redactions();
+        self.static_layer_dirty = true;
+        vec![Command::UpdateToolbar, Command::RequestRedraw]
+    }
+}
@@ -1,7 +1,10 @@
 use sc_drawing::{
-    Color, HandleConfig, Point, Rect, calculate_text_proportional_resize_with_min_font,
-    clamp_to_rect, defaults, detect_arrow_handle, detect_handle_at_position,
-    detect_handle_at_position_with_radius, is_drag_threshold_exceeded,
+    ANGLE_SNAP_DEGREES, Arrangement, Color, HANDLE_DETECTION_RADIUS, HandleConfig, Point, Rect,
+    SNAP_TOLERANCE, ZOrder, calculate_blur_radius_for_resize, calculate_resized_rect,
+    calculate_resized_rect_keep_aspect, calculate_text_proportional_resize_with_min_font,
+    clamp_to_rect, constrain_drag_end, defaults, detect_arrow_handle, detect_callout_tail,
+    detect_element_handle, detect_handle_at_position_with_radius, is_drag_threshold_exceeded,
+    rotate_point, rotated_resize_offset, rotation_for_drag, snap_angle, snap_rect,
 };
 
 use sc_drawing::history;
@@ -11,6 +14,26 @@ use crate::constants::{
     MIN_TEXT_HEIGHT, MIN_TEXT_WIDTH, TEXT_HANDLE_DETECTION_RADIUS, TEXT_LINE_HEIGHT_SCALE,
 };
 use sc_host_protocol::{Command, DrawingMessage};
+use sc_platform::Modifiers;
+
+/// Dragged arrow end, snapped to angle steps around the fixed end when constrained.
+fn arrow_end(fixed: Point, x: i32, y: i32, constrain: bool) -> Point {
+    let end = Point::new(x, y);
+    if constrain {
+        snap_angle(fixed, end, ANGLE_SNAP_DEGREES)
+    } else {
+        end
+    }
+}
+
+/// Bend point for the midpoint handle of an arrow dragged to `(x, y)`, or `None` if it is
+/// close enough to the straight line between the ends to leave the arrow straight.
+fn arrow_bend(start: Point, end: Point, x: i32, y: i32) -> Option<Point> {
+    let (dx, dy) = ((end.x - start.x) as f64, (end.y - start.y) as f64);
+    let length = dx.hypot(dy);
+    let off_line = (dx * (y - start.y) as f64 - dy * (x - start.x) as f64).abs() / length;
+    (length == 0.0 || off_line > SNAP_TOLERANCE as f64).then_some(Point::new(x, y))
+}
 
 impl DrawingManager {
     pub fn handle_mouse_move(
@@ -18,6 +41,7 @@ impl DrawingManager {
         x: i32,
         y: i32,
         selection_rect: Option<Rect>,
+        modifiers: Modifiers,
     ) -> (Vec<Command>, bool) {
         if self.mouse_pressed {
             if is_drag_threshold_exceeded(
@@ -26,35 +50,79 @@ impl DrawingManager {
                 x,
                 y,
             ) {
-                self.update_drag(x, y, selection_rect);
+                self.update_drag(x, y, selection_rect, modifiers);
                 (vec![Command::RequestRedraw], true)
             } else {
                 (vec![], true)
             }
         } else {
-            if let Some(_index) = self.elements.get_element_at_position(x, y) {
-                (vec![], false)
-            } else {
-                (vec![], false)
-            }
+            (vec![], false)
         }
     }
 
-    pub(super) fn update_drag(&mut self, x: i32, y: i32, selection_rect: Option<Rect>) {
+    /// Follow a drag to `(x, y)`. With Shift held new shapes are squared or snapped to angle
+    /// steps and resizes keep the aspect ratio. Moved and resized elements snap to the capture
+    /// area and to other elements unless Alt is held.
+    pub(super) fn update_drag(
+        &mut self,
+        x: i32,
+        y: i32,
+        selection_rect: Option<Rect>,
+        modifiers: Modifiers,
+    ) {
+        let constrain = modifiers.shift;
+        self.snap_guides.clear();
+        let targets = if modifiers.alt {
+            Vec::new()
+        } else {
+            self.snap_targets(selection_rect)
+        };
+
+        if !self.group_drag_start.is_empty() {
+            self.update_group_drag(x, y, constrain, &targets);
+            self.refresh_redactions();
+            return;
+        }
+
         match &self.interaction_mode {
             ElementInteractionMode::Drawing => {
                 if let Some(ref mut element) = self.current_element {
-                    let (clamped_x, clamped_y) = if let Some(rect) = selection_rect {
+                    let (clamped_x, clamped_y) = if constrain {
+                        let start = element
+                            .points
+                            .first()
+                            .copied()
+                            .unwrap_or(self.interaction_start_pos);
+                        let end = constrain_drag_end(
+                            element.tool,
+                            start,
+                            Point::new(x, y),
+                            selection_rect.as_ref(),
+                        );
+                        (end.x, end.y)
+                    } else if let Some(rect) = selection_rect {
                         clamp_to_rect(x, y, &rect)
                     } else {
                         (x, y)
                     };
 
                     match element.tool {
-                        DrawingTool::Pen => {
+                        DrawingTool::Pen | DrawingTool::Highlighter if constrain => {
+                            // Shift turns the stroke into a straight line from where it began.
+                            if element.points.len() > 2 {
+                                self.win_renderer.clear_pen_stroke_cache();
+                            }
+                            element.points.truncate(1);
                             element.add_point(clamped_x, clamped_y);
                         }
-                        DrawingTool::Rectangle | DrawingTool::Circle | DrawingTool::Arrow => {
+                        DrawingTool::Pen | DrawingTool::Highlighter => {
+                            element.add_point(clamped_x, clamped_y);
+                        }
+                        DrawingTool::Rectangle
+                        | DrawingTool::Circle
+                        | DrawingTool::Arrow
+                        | DrawingTool::Mosaic
+                        | DrawingTool::Blur => {
                             if element.points.is_empty() {
                                 element.add_point(
                                     self.interaction_start_pos.x,
@@ -64,6 +132,11 @@ impl DrawingManager {
                             element.set_end_point(clamped_x, clamped_y);
                             element.update_bounding_rect();
                         }
+                        DrawingTool::Step => {
+                            // Dragging before release repositions the marker.
+                            element.points = vec![Point::new(clamped_x, clamped_y)];
+                            element.update_bounding_rect();
+                        }
                         _ => {}
                     }
                 }
@@ -71,10 +144,26 @@ impl DrawingManager {
             ElementInteractionMode::MovingElement => {
                 if let Some(index) = self.selected_element
                     && let Some(element) = self.elements.get_elements().get(index)
-                    && element.tool != DrawingTool::Pen
+                    && !element.tool.is_freeform()
                 {
-                    let dx = x - self.interaction_start_pos.x;
-                    let dy = y - self.interaction_start_pos.y;
+                    // The unrotated frame of a turned element does not line up with anything.
+                    let targets = if element.is_rotated() {
+                        &[][..]
+                    } else {
+                        &targets[..]
+                    };
+                    let (moved, guides) = snap_rect(
+                        self.interaction_start_rect.offset(
+                            x - self.interaction_start_pos.x,
+                            y - self.interaction_start_pos.y,
+                        ),
+                        DragMode::MovingElement,
+                        targets,
+                        SNAP_TOLERANCE,
+                    );
+                    self.snap_guides = guides;
+                    let dx = moved.left - self.interaction_start_rect.left;
+                    let dy = moved.top - self.interaction_start_rect.top;
                     if let Some(el) = self.elements.get_element_mut(index) {
                         let current_dx = el.rect.left - self.interaction_start_rect.left;
                         let current_dy = el.rect.top - self.interaction_start_rect.top;
@@ -84,80 +173,138 @@ impl DrawingManager {
                 }
             }
             ElementInteractionMode::ResizingElement(resize_mode) => {
-                self.handle_resize_drag(x, y, *resize_mode);
+                self.handle_resize_drag(x, y, *resize_mode, constrain, &targets);
+            }
+            ElementInteractionMode::RotatingElement => {
+                if let Some(index) = self.selected_element
+                    && let Some(el) = self.elements.get_element_mut(index)
+                {
+                    el.rotation = rotation_for_drag(
+                        &self.interaction_start_rect,
+                        self.interaction_start_rotation,
+                        self.interaction_start_pos,
+                        Point::new(x, y),
+                        constrain,
+                    );
+                }
+            }
+            ElementInteractionMode::RubberBand => {
+                self.update_rubber_band(x, y);
             }
             _ => {}
         }
+        self.refresh_redactions();
+    }
+
+    /// Bounding rects that a dragged element can snap to: every element that is not being
+    /// dragged, plus the capture area.
+    fn snap_targets(&self, selection_rect: Option<Rect>) -> Vec<Rect> {
+        self.elements
+            .get_elements()
+            .iter()
+            .filter(|e| !e.selected)
+            .map(|e| e.get_bounding_rect())
+            .chain(selection_rect)
+            .collect()
     }
 
-    fn handle_resize_drag(&mut self, x: i32, y: i32, resize_mode: DragMode) {
+    fn handle_resize_drag(
+        &mut self,
+        x: i32,
+        y: i32,
+        resize_mode: DragMode,
+        keep_aspect: bool,
+        targets: &[Rect],
+    ) {
         if let Some(index) = self.selected_element
             && let Some(el) = self.elements.get_element_mut(index)
         {
-            if el.tool == DrawingTool::Pen {
+            if el.tool.is_freeform() {
                 return;
             }
             let start_rect = self.interaction_start_rect;
             let start_font_size = self.interaction_start_font_size;
-            let mut new_rect = start_rect;
-            let dx = x - self.interaction_start_pos.x;
-            let dy = y - self.interaction_start_pos.y;
-
-            match resize_mode {
-                DragMode::ResizingTopLeft => {
-                    new_rect.left += dx;
-                    new_rect.top += dy;
-                }
-                DragMode::ResizingTopCenter => {
-                    new_rect.top += dy;
-                }
-                DragMode::ResizingTopRight => {
-                    new_rect.right += dx;
-                    new_rect.top += dy;
-                }
-                DragMode::ResizingMiddleRight => {
-                    new_rect.right += dx;
-                }
-                DragMode::ResizingBottomRight => {
-                    new_rect.right += dx;
-                    new_rect.bottom += dy;
-                }
-                DragMode::ResizingBottomCenter => {
-                    new_rect.bottom += dy;
-                }
-                DragMode::ResizingBottomLeft => {
-                    new_rect.left += dx;
-                    new_rect.bottom += dy;
-                }
-                DragMode::ResizingMiddleLeft => {
-                    new_rect.left += dx;
+            let start_blur_radius = self.interaction_start_blur_radius;
+            let rotation = if el.is_rotated() { el.rotation } else { 0.0 };
+            // A rotated element is resized in its own frame, where the handles are.
+            let delta = rotate_point(
+                Point::new(
+                    x - self.interaction_start_pos.x,
+                    y - self.interaction_start_pos.y,
+                ),
+                (0.0, 0.0),
+                -rotation,
+            );
+            let (dx, dy) = (delta.x, delta.y);
+            // Snapping single edges would break the aspect ratio, so it is left out with Shift.
+            // Rotated edges do not line up with anything, so they are resized freely.
+            let new_rect = if keep_aspect {
+                calculate_resized_rect_keep_aspect(start_rect, resize_mode, dx, dy)
+            } else if rotation != 0.0 {
+                calculate_resized_rect(start_rect, resize_mode, dx, dy)
+            } else {
+                let (rect, guides) = snap_rect(
+                    calculate_resized_rect(start_rect, resize_mode, dx, dy),
+                    resize_mode,
+                    targets,
+                    SNAP_TOLERANCE,
+                );
+                if !matches!(
+                    el.tool,
+                    DrawingTool::Arrow | DrawingTool::Text | DrawingTool::Callout
+                ) {
+                    self.snap_guides = guides;
                 }
-                _ => {}
-            }
+                rect
+            };
 
             match el.tool {
                 DrawingTool::Arrow => {
                     if el.points.len() >= 2 {
                         match resize_mode {
                             DragMode::ResizingTopLeft => {
-                                el.points[0] = Point::new(x, y);
+                                el.points[0] = arrow_end(el.points[1], x, y, keep_aspect);
                                 el.update_bounding_rect();
                             }
                             DragMode::ResizingBottomRight => {
-                                el.points[1] = Point::new(x, y);
+                                el.points[1] = arrow_end(el.points[0], x, y, keep_aspect);
+                                el.update_bounding_rect();
+                            }
+                            DragMode::BendingArrow => {
+                                let bend = arrow_bend(el.points[0], el.points[1], x, y);
+                                el.points.truncate(2);
+                                el.points.extend(bend);
                                 el.update_bounding_rect();
                             }
                             _ => {}
                         }
                     }
                 }
-                DrawingTool::Text => {
+                DrawingTool::Callout if resize_mode == DragMode::MovingCalloutTail => {
+                    el.points.truncate(2);
+                    el.add_point(x, y);
+                }
+                DrawingTool::Text | DrawingTool::Callout => {
                     Self::apply_text_resize(el, resize_mode, dx, dy, start_rect, start_font_size);
                 }
+                DrawingTool::Blur => {
+                    el.resize(new_rect);
+                    el.set_blur_radius(calculate_blur_radius_for_resize(
+                        start_rect,
+                        new_rect,
+                        resize_mode,
+                        start_blur_radius,
+                    ));
+                }
                 _ => {
                     el.resize(new_rect);
                 }
             }
+
+            if rotation != 0.0 {
+                let (ox, oy) = rotated_resize_offset(start_rect, el.rect, rotation);
+                el.move_by(ox, oy);
+            }
         }
     }
 
@@ -258,23 +405,27 @@ impl DrawingManager {
         tool: DrawingTool,
         element_index: usize,
     ) -> DragMode {
-        let element_points = self
-            .elements
-            .get_elements()
-            .get(element_index)
-            .map(|element| element.points.as_slice())
-            .unwrap_or(&[]);
-
-        match tool {
-            DrawingTool::Arrow => detect_arrow_handle(x, y, element_points),
-            DrawingTool::Text => detect_handle_at_position_with_radius(
-                x,
-                y,
-                rect,
-                HandleConfig::Corners,
-                TEXT_HANDLE_DETECTION_RADIUS,
-            ),
-            _ => detect_handle_at_position(x, y, rect, HandleConfig::Full),
+        let element = self.elements.get_elements().get(element_index);
+        let (config, radius) = match tool {
+            DrawingTool::Arrow => {
+                let points = element.map(|e| e.points.as_slice()).unwrap_or(&[]);
+                return detect_arrow_handle(x, y, points);
+            }
+            DrawingTool::Step => return DragMode::None,
+            DrawingTool::Callout => {
+                let points = element.map(|e| e.points.as_slice()).unwrap_or(&[]);
+                let tail = detect_callout_tail(x, y, points);
+                if tail != DragMode::None {
+                    return tail;
+                }
+                (HandleConfig::Corners, TEXT_HANDLE_DETECTION_RADIUS)
+            }
+            DrawingTool::Text => (HandleConfig::Corners, TEXT_HANDLE_DETECTION_RADIUS),
+            _ => (HandleConfig::Full, HANDLE_DETECTION_RADIUS),
+        };
+        match element {
+            Some(element) => detect_element_handle(x, y, element, config, radius),
+            None => detect_handle_at_position_with_radius(x, y, rect, config, radius),
         }
     }
 
@@ -283,6 +434,7 @@ impl DrawingManager {
         x: i32,
         y: i32,
         selection_rect: Option<Rect>,
+        modifiers: Modifiers,
     ) -> (Vec<Command>, bool) {
         self.just_saved_text = false;
 
@@ -310,6 +462,8 @@ impl DrawingManager {
                     self.interaction_start_pos = Point::new(x, y);
                     self.interaction_start_rect = element.rect;
                     self.interaction_start_font_size = element.font_size;
+                    self.interaction_start_blur_radius = element.blur_radius;
+                    self.interaction_start_rotation = element.rotation;
                     self.interaction_start_points = element.points.clone();
                     return (vec![Command::RequestRedraw], true);
                 }
@@ -319,20 +473,39 @@ impl DrawingManager {
             return (stop_commands, true);
         }
 
+        // Shift or Ctrl adds to or takes from the selection instead of replacing it. While a
+        // drawing tool is active Shift constrains the new shape instead, so only Ctrl toggles.
+        let toggles_selection =
+            modifiers.ctrl || (modifiers.shift && self.current_tool == DrawingTool::None);
+        if inside_selection && toggles_selection {
+            if let Some(idx) = self
+                .elements
+                .get_element_at_position_with_rect(x, y, selection_rect)
+            {
+                let id = self.elements.get_elements()[idx].id;
+                return (self.toggle_element_selection(id), true);
+            }
+            if self.current_tool == DrawingTool::None {
+                self.begin_rubber_band(x, y);
+                return (vec![Command::RequestRedraw], true);
+            }
+        }
+
         if inside_selection
-            && self.current_tool == DrawingTool::Text
+            && self.current_tool.is_text()
             && !self.text_editing
             && !self.just_saved_text
         {
             if let Some(idx) = self.elements.get_element_at_position(x, y) {
                 if let Some(element) = self.elements.get_elements().get(idx)
-                    && element.tool == DrawingTool::Text
+                    && element.tool == self.current_tool
                 {
+                    let element_id = element.id;
                     let element_rect = element.rect;
                     let element_font_size = element.font_size;
                     let element_points = element.points.clone();
 
-                    self.handle_message(DrawingMessage::SelectElement(Some(idx)));
+                    self.handle_message(DrawingMessage::SelectElement(Some(element_id)));
 
                     self.interaction_mode = ElementInteractionMode::MovingElement;
                     self.mouse_pressed = true;
@@ -344,7 +517,7 @@ impl DrawingManager {
                     return (vec![Command::UpdateToolbar, Command::RequestRedraw], true);
                 }
             } else {
-                return self.create_and_edit_text_element(x, y);
+                return self.create_and_edit_text_element(self.current_tool, x, y);
             }
         }
 
@@ -352,10 +525,14 @@ impl DrawingManager {
             return (vec![], false);
         }
 
+        if inside_selection && self.begin_group_drag(x, y) {
+            return (vec![Command::RequestRedraw], true);
+        }
+
         if inside_selection
             && let Some(sel_idx) = self.selected_element
             && let Some(element) = self.elements.get_elements().get(sel_idx)
-            && element.tool != DrawingTool::Pen
+            && !element.tool.is_freeform()
         {
             let handle_mode =
                 self.get_element_handle_at_position(x, y, &element.rect, element.tool, sel_idx);
@@ -365,6 +542,8 @@ impl DrawingManager {
                 self.interaction_start_pos = Point::new(x, y);
                 self.interaction_start_rect = element.rect;
                 self.interaction_start_font_size = element.font_size;
+                self.interaction_start_blur_radius = element.blur_radius;
+                self.interaction_start_rotation = element.rotation;
                 self.interaction_start_points = element.points.clone();
                 return (vec![Command::RequestRedraw], true);
             }
@@ -386,20 +565,50 @@ impl DrawingManager {
             }
         }
 
+        // A Shift-drag with a drawing tool draws a constrained shape even on top of an
+        // existing annotation, rather than picking that annotation up.
+        if inside_selection
+            && modifiers.shift
+            && self.current_tool != DrawingTool::None
+            && !self.current_tool.is_text()
+        {
+            self.interaction_start_pos = Point::new(x, y);
+            self.start_drawing_shape(x, y);
+            self.mouse_pressed = true;
+            return (vec![Command::RequestRedraw], true);
+        }
+
         if inside_selection
             && let Some(idx) = self
                 .elements
                 .get_element_at_position_with_rect(x, y, selection_rect)
         {
-            let (element_tool, element_rect, element_font_size, element_points) = {
+            // Grouped elements (including grouped freehand strokes) are picked up as a unit.
+            let id = self.elements.get_elements()[idx].id;
+            if self.begin_group_click(id, x, y) {
+                return (vec![Command::UpdateToolbar, Command::RequestRedraw], true);
+            }
+
+            let (
+                element_id,
+                element_tool,
+                element_rect,
+                element_font_size,
+                element_blur_radius,
+                element_rotation,
+                element_points,
+            ) = {
                 if let Some(element) = self.elements.get_elements().get(idx) {
-                    if element.tool == DrawingTool::Pen {
+                    if element.tool.is_freeform() {
                         return (vec![], false);
                     }
                     (
+                        element.id,
                         element.tool,
                         element.rect,
                         element.font_size,
+                        element.blur_radius,
+                        element.rotation,
                         element.points.clone(),
                     )
                 } else {
@@ -407,15 +616,17 @@ impl DrawingManager {
                 }
             };
 
-            if element_tool == DrawingTool::Pen {
+            if element_tool.is_freeform() {
                 return (vec![], false);
             }
 
-            self.handle_message(DrawingMessage::SelectElement(Some(idx)));
+            self.handle_message(DrawingMessage::SelectElement(Some(element_id)));
 
             self.interaction_start_rect = element_rect;
             self.interaction_start_pos = Point::new(x, y);
             self.interaction_start_font_size = element_font_size;
+            self.interaction_start_blur_radius = element_blur_radius;
+            self.interaction_start_rotation = element_rotation;
             self.interaction_start_points = element_points;
 
             let handle_mode =
@@ -434,8 +645,8 @@ impl DrawingManager {
 
         if self.current_tool != DrawingTool::None {
             if inside_selection {
-                if self.current_tool == DrawingTool::Text {
-                    return self.create_and_edit_text_element(x, y);
+                if self.current_tool.is_text() {
+                    return self.create_and_edit_text_element(self.current_tool, x, y);
                 }
                 self.interaction_start_pos = Point::new(x, y);
                 self.start_drawing_shape(x, y);
@@ -445,7 +656,7 @@ impl DrawingManager {
             return (vec![], false);
         }
 
-        if self.selected_element.is_some() {
+        if self.selected_element.is_some() || self.has_group_selection() {
             self.selected_element = None;
             self.elements.set_selected(None);
             self.static_layer_dirty = true;
@@ -479,6 +690,19 @@ impl DrawingManager {
             new_element.font_italic = self.config.font_italic;
             new_element.font_underline = self.config.font_underline;
             new_element.font_strikeout = self.config.font_strikeout;
+        } else if self.current_tool == DrawingTool::Mosaic {
+            new_element.set_block_size(self.config.mosaic_block_size);
+        } else if self.current_tool == DrawingTool::Blur {
+            new_element.set_blur_radius(self.config.blur_radius);
+        } else if self.current_tool == DrawingTool::Highlighter {
+            let (r, g, b) = self.config.highlighter_color;
+            new_element.color = Color {
+                r: r as f32 / 255.0,
+                g: g as f32 / 255.0,
+                b: b as f32 / 255.0,
+                a: self.config.highlighter_opacity as f32 / 255.0,
+            };
+            new_element.thickness = self.config.highlighter_width;
         } else {
             let (r, g, b) = self.config.drawing_color;
             new_element.color = Color {
@@ -488,15 +712,29 @@ impl DrawingManager {
                 a: 1.0,
             };
             new_element.thickness = self.config.line_thickness;
+            if self.current_tool.uses_stroke_style() {
+                new_element.stroke = self.config.stroke_style.clone();
+            }
+            if self.current_tool == DrawingTool::Arrow {
+                new_element.arrow = self.config.arrow_style;
+            }
         }
 
         match self.current_tool {
             DrawingTool::Pen
+            | DrawingTool::Highlighter
             | DrawingTool::Rectangle
             | DrawingTool::Circle
             | DrawingTool::Arrow
-            | DrawingTool::Text => {
+            | DrawingTool::Text
+            | DrawingTool::Mosaic
+            | DrawingTool::Blur => {
+                new_element.add_point(x, y);
+            }
+            DrawingTool::Step => {
                 new_element.add_point(x, y);
+                new_element.step_number = self.elements.next_step_number();
+                new_element.update_bounding_rect();
             }
             _ => {}
         }
@@ -516,16 +754,26 @@ impl DrawingManager {
     }
 
     pub(super) fn end_drag(&mut self) {
+        self.snap_guides.clear();
+        if !self.group_drag_start.is_empty() {
+            self.end_group_drag();
+            return;
+        }
+
         match &self.interaction_mode {
             ElementInteractionMode::Drawing => {
                 if let Some(mut element) = self.current_element.take() {
-                    if element.tool == DrawingTool::Pen {
+                    if matches!(element.tool, DrawingTool::Pen | DrawingTool::Highlighter) {
                         self.win_renderer.clear_pen_stroke_cache();
                     }
 
                     let should_save = match element.tool {
-                        DrawingTool::Pen => element.points.len() > 1,
-                        DrawingTool::Rectangle | DrawingTool::Circle | DrawingTool::Arrow => {
+                        DrawingTool::Pen | DrawingTool::Highlighter => element.points.len() > 1,
+                        DrawingTool::Rectangle
+                        | DrawingTool::Circle
+                        | DrawingTool::Arrow
+                        | DrawingTool::Mosaic
+                        | DrawingTool::Blur => {
                             if element.points.len() >= 2 {
                                 let dx = (element.points[1].x - element.points[0].x).abs();
                                 let dy = (element.points[1].y - element.points[0].y).abs();
@@ -534,24 +782,26 @@ impl DrawingManager {
                                 false
                             }
                         }
-                        DrawingTool::Text => {
+                        DrawingTool::Text | DrawingTool::Callout => {
                             !element.points.is_empty() && !element.text.trim().is_empty()
                         }
+                        DrawingTool::Step => !element.points.is_empty(),
                         _ => false,
                     };
 
                     if should_save {
                         element.update_bounding_rect();
 
-                        let index = self.elements.count();
+                        let position = self.elements.count();
                         let action = history::DrawingAction::AddElement {
                             element: element.clone(),
-                            index,
+                            position,
                         };
                         self.history
-                            .record_action(action, self.selected_element, None);
+                            .record_action(action, self.elements.selected_ids(), vec![]);
 
                         self.elements.add_element(element);
+                        self.refresh_redactions();
                         self.static_layer_dirty = true;
                     }
                 }
@@ -565,17 +815,14 @@ impl DrawingManager {
 
                     if dx != 0 || dy != 0 {
                         let action = history::DrawingAction::MoveElement {
-                            index,
+                            id: element.id,
                             dx,
                             dy,
                             old_points: self.interaction_start_points.clone(),
                             old_rect: self.interaction_start_rect,
                         };
-                        self.history.record_action(
-                            action,
-                            self.selected_element,
-                            self.selected_element,
-                        );
+                        self.history
+                            .record_action(action, vec![element.id], vec![element.id]);
                     }
                 }
             }
@@ -590,30 +837,48 @@ impl DrawingManager {
                     let points_changed = element.points != self.interaction_start_points;
                     let font_size_changed =
                         (element.font_size - self.interaction_start_font_size).abs() > 0.01;
+                    let blur_radius_changed =
+                        (element.blur_radius - self.interaction_start_blur_radius).abs() > 0.01;
 
-                    if rect_changed || points_changed || font_size_changed {
+                    if rect_changed || points_changed || font_size_changed || blur_radius_changed {
                         let action = history::DrawingAction::ResizeElement {
-                            index,
+                            id: element.id,
                             old_points: self.interaction_start_points.clone(),
                             old_rect: self.interaction_start_rect,
                             old_font_size: self.interaction_start_font_size,
+                            old_blur_radius: self.interaction_start_blur_radius,
                             new_points: element.points.clone(),
                             new_rect: element.rect,
                             new_font_size: element.font_size,
+                            new_blur_radius: element.blur_radius,
                         };
-                        self.history.record_action(
-                            action,
-                            self.selected_element,
-                            self.selected_element,
-                        );
+                        self.history
+                            .record_action(action, vec![element.id], vec![element.id]);
                     }
                 }
             }
+            ElementInteractionMode::RotatingElement => {
+                if let Some(index) = self.selected_element
+                    && let Some(element) = self.elements.get_elements().get(index)
+                    && element.rotation != self.interaction_start_rotation
+                {
+                    let action = history::DrawingAction::RotateElement {
+                        id: element.id,
+                        old_rotation: self.interaction_start_rotation,
+                        new_rotation: element.rotation,
+                    };
+                    self.history
+                        .record_action(action, vec![element.id], vec![element.id]);
+                    self.static_layer_dirty = true;
+                }
+            }
+            ElementInteractionMode::RubberBand => self.finish_rubber_band(),
             ElementInteractionMode::None => {}
         }
     }
 
-    pub fn handle_key_input(&mut self, key: u32) -> Vec<Command> {
+    pub fn handle_key_input(&mut self, key: u32, modifiers: Modifiers) -> Vec<Command> {
+        let (ctrl, shift, alt) = (modifiers.ctrl, modifiers.shift, modifiers.alt);
         if self.text_editing {
             match key {
                 0x1B => return self.stop_text_editing(),         // VK_ESCAPE
@@ -630,12 +895,39 @@ impl DrawingManager {
         }
 
         match key {
-            26 => self.handle_message(DrawingMessage::Undo), // Ctrl+Z
-            25 => self.handle_message(DrawingMessage::Redo), // Ctrl+Y
+            // Ctrl+] / Ctrl+[ restack by one step, with Shift all the way.
+            0xDD if ctrl && shift => self.restack(ZOrder::ToFront),
+            0xDD if ctrl => self.restack(ZOrder::Forward),
+            0xDB if ctrl && shift => self.restack(ZOrder::ToBack),
+            0xDB if ctrl => self.restack(ZOrder::Backward),
+            // Ctrl+Alt+L/C/R and T/M/B align, Ctrl+Alt+H/V distribute.
+            0x4C if ctrl && alt => self.arrange(Arrangement::AlignLeft),
+            0x43 if ctrl && alt => self.arrange(Arrangement::AlignCenter),
+            0x52 if ctrl && alt => self.arrange(Arrangement::AlignRight),
+            0x54 if ctrl && alt => self.arrange(Arrangement::AlignTop),
+            0x4D if ctrl && alt => self.arrange(Arrangement::AlignMiddle),
+            0x42 if ctrl && alt => self.arrange(Arrangement::AlignBottom),
+            0x48 if ctrl && alt => self.arrange(Arrangement::DistributeHorizontally),
+            0x56 if ctrl && alt => self.arrange(Arrangement::DistributeVertically),
+            0x43 if ctrl => self.handle_message(DrawingMessage::CopySelected), // Ctrl+C
+            0x56 if ctrl => self.handle_message(DrawingMessage::Paste),        // Ctrl+V
+            0x44 if ctrl => self.handle_message(DrawingMessage::DuplicateSelected), // Ctrl+D
+            0x46 if ctrl && shift => {
+                self.handle_message(DrawingMessage::SetFill(self.fill_for_selection())) // Ctrl+Shift+F
+            }
+            0x41 if ctrl && shift => self.handle_message(DrawingMessage::ToggleDoubleArrow), // Ctrl+Shift+A
+            0x47 if ctrl && shift => {
+                self.handle_message(DrawingMessage::UngroupSelected) // Ctrl+Shift+G
+            }
+            0x47 if ctrl => self.handle_message(DrawingMessage::GroupSelected), // Ctrl+G
+            26 => self.handle_message(DrawingMessage::Undo),                    // Ctrl+Z
+            25 => self.handle_message(DrawingMessage::Redo),                    // Ctrl+Y
             46 => {
                 // Delete
-                if let Some(index) = self.selected_element {
-                    self.handle_message(DrawingMessage::DeleteElement(index))
+                if self.has_group_selection() {
+                    self.delete_selected_elements()
+                } else if let Some(id) = self.selected_element_id() {
+                    self.handle_message(DrawingMessage::DeleteElement(id))
                 } else {
                     vec![]
                 }
@@ -644,6 +936,14 @@ impl DrawingManager {
         }
     }
 
+    fn restack(&mut self, z_order: ZOrder) -> Vec<Command> {
+        self.handle_message(DrawingMessage::ChangeZOrder(z_order))
+    }
+
+    fn arrange(&mut self, arrangement: Arrangement) -> Vec<Command> {
+        self.handle_message(DrawingMessage::Arrange(arrangement))
+    }
+
     pub fn handle_double_click(
         &mut self,
         x: i32,
@@ -660,6 +960,7 @@ impl DrawingManager {
         match &self.interaction_mode {
             ElementInteractionMode::MovingElement => Some(DragMode::Moving),
             ElementInteractionMode::ResizingElement(mode) => Some(*mode),
+            ElementInteractionMode::RotatingElement => Some(DragMode::Rotating),
             _ => None,
         }
     }
@@ -668,3 +969,37 @@ impl DrawingManager {
         self.mouse_pressed && self.interaction_mode != ElementInteractionMode::None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DrawingConfig;
+
+    #[test]
+    fn shift_drag_over_an_element_draws_a_square() {
+        let mut manager = DrawingManager::new(DrawingConfig::default()).unwrap();
+        let mut existing = DrawingElement::new(DrawingTool::Rectangle);
+        existing.add_point(20, 20);
+        existing.add_point(80, 80);
+        existing.update_bounding_rect();
+        manager.elements.add_element(existing);
+        manager.set_current_tool(DrawingTool::Rectangle);
+
+        let shift = Modifiers {
+            shift: true,
+            ..Modifiers::NONE
+        };
+        // Starts on the existing rectangle's outline.
+        assert!(manager.elements.get_element_at_position(20, 50).is_some());
+        manager.handle_mouse_down(20, 50, None, shift);
+        manager.handle_mouse_move(120, 90, None, shift);
+        manager.handle_mouse_up(120, 90);
+
+        let elements = manager.elements.get_elements();
+        assert_eq!(elements.len(), 2);
+        assert!(!elements[0].selected);
+        let square = elements[1].rect;
+        assert_eq!(square.left, 20);
+        assert_eq!(square.right - square.left, square.bottom - square.top);
+    }
+}
@@ -1,10 +1,12 @@
+pub mod clipboard;
 pub mod constants;
 pub mod interaction;
+pub mod multi_select;
 pub mod rendering;
 pub mod text_editing;
 pub mod tools;
 
-use sc_drawing::{Point, Rect};
+use sc_drawing::{ArrowStyle, Point, Rect, RedactionSource, SnapGuide, StrokeStyle};
 
 use sc_host_protocol::{Command, DrawingMessage};
 
@@ -34,6 +36,27 @@ pub struct DrawingConfig {
     pub font_underline: bool,
     pub font_strikeout: bool,
     pub font_color: (u8, u8, u8),
+
+    /// Mosaic cell size for new mosaic regions.
+    pub mosaic_block_size: u32,
+    /// Gaussian radius for new blur regions.
+    pub blur_radius: f32,
+
+    // Highlighter config
+    pub highlighter_width: f32,
+    pub highlighter_color: (u8, u8, u8),
+    /// Stroke alpha, 0-255.
+    pub highlighter_opacity: u8,
+
+    // Fill config
+    pub fill_color: (u8, u8, u8),
+    /// Fill alpha, 0-255.
+    pub fill_opacity: u8,
+
+    /// Dashes, caps and joins for new shapes and pen strokes.
+    pub stroke_style: StrokeStyle,
+    /// Head and tail markers for new arrows.
+    pub arrow_style: ArrowStyle,
 }
 
 impl Default for DrawingConfig {
@@ -49,6 +72,19 @@ impl Default for DrawingConfig {
             font_underline: false,
             font_strikeout: false,
             font_color: (0, 0, 0),
+
+            mosaic_block_size: sc_drawing::defaults::MOSAIC_BLOCK_SIZE,
+            blur_radius: sc_drawing::defaults::BLUR_RADIUS,
+
+            highlighter_width: sc_drawing::defaults::HIGHLIGHTER_WIDTH,
+            highlighter_color: sc_drawing::defaults::HIGHLIGHTER_COLOR,
+            highlighter_opacity: sc_drawing::defaults::HIGHLIGHTER_OPACITY,
+
+            fill_color: sc_drawing::defaults::FILL_COLOR,
+            fill_opacity: sc_drawing::defaults::FILL_OPACITY,
+
+            stroke_style: StrokeStyle::default(),
+            arrow_style: ArrowStyle::default(),
         }
     }
 }
@@ -68,8 +104,16 @@ pub struct DrawingManager {
     interaction_start_pos: Point,
     interaction_start_rect: Rect,
     interaction_start_font_size: f32,
+    interaction_start_blur_radius: f32,
+    interaction_start_rotation: f32,
     /// Start points snapshot for command history.
     interaction_start_points: Vec<Point>,
+    /// Selected elements as they were when the current group move/resize started.
+    group_drag_start: Vec<DrawingElement>,
+    /// Rubber band being dragged out, if any.
+    rubber_band: Option<Rect>,
+    /// Guide lines for the snap in effect during the current move or resize.
+    snap_guides: Vec<SnapGuide>,
     text_editing: bool,
     editing_element_index: Option<usize>,
     text_cursor_pos: usize,
@@ -78,6 +122,8 @@ pub struct DrawingManager {
     just_saved_text: bool,
     /// Whether the static layer needs rebuild (actual cache lives in `win_renderer`).
     static_layer_dirty: bool,
+    /// Image that mosaic elements sample from (the capture or the pinned image).
+    redaction_source: Option<RedactionSource>,
 }
 
 impl DrawingManager {
@@ -109,7 +155,12 @@ impl DrawingManager {
             interaction_start_pos: Point::new(0, 0),
             interaction_start_rect: Rect::new(0, 0, 0, 0),
             interaction_start_font_size: 0.0,
+            interaction_start_blur_radius: 0.0,
+            interaction_start_rotation: 0.0,
             interaction_start_points: Vec::new(),
+            group_drag_start: Vec::new(),
+            rubber_band: None,
+            snap_guides: Vec::new(),
 
             text_editing: false,
             editing_element_index: None,
@@ -118,6 +169,7 @@ impl DrawingManager {
             cursor_timer_id: 1001,
             just_saved_text: false,
             static_layer_dirty: true,
+            redaction_source: None,
         })
     }
 
@@ -131,6 +183,9 @@ impl DrawingManager {
         self.win_renderer.invalidate_all();
         self.interaction_mode = ElementInteractionMode::None;
         self.mouse_pressed = false;
+        self.group_drag_start.clear();
+        self.rubber_band = None;
+        self.snap_guides.clear();
         self.text_editing = false;
         self.editing_element_index = None;
         self.text_cursor_pos = 0;
@@ -139,6 +194,55 @@ impl DrawingManager {
         self.static_layer_dirty = true;
     }
 
+    /// Copies of all committed elements, shifted so that `origin` becomes `(0, 0)`.
+    ///
+    /// Used to store annotations relative to the captured image (e.g. in project files).
+    pub fn export_elements(&self, origin: Point) -> Vec<DrawingElement> {
+        self.elements
+            .get_elements()
+            .iter()
+            .cloned()
+            .map(|mut element| {
+                element.move_by(-origin.x, -origin.y);
+                element
+            })
+            .collect()
+    }
+
+    /// Replace everything with `elements` given relative to `origin` (the inverse of
+    /// [`Self::export_elements`]). History starts empty and nothing is selected.
+    pub fn load_elements(&mut self, elements: Vec<DrawingElement>, origin: Point) {
+        self.reset_state();
+        let elements = elements
+            .into_iter()
+            .map(|mut element| {
+                element.move_by(origin.x, origin.y);
+                element.selected = false;
+                element.redaction = None;
+                element
+            })
+            .collect();
+        self.elements.restore_state(elements);
+        self.refresh_redactions();
+    }
+
+    /// Set the image that redaction elements sample from, in element coordinates.
+    pub fn set_redaction_source(&mut self, source: Option<RedactionSource>) {
+        self.redaction_source = source;
+        self.refresh_redactions();
+    }
+
+    /// Rebuild redaction patches whose geometry or source changed.
+    pub(crate) fn refresh_redactions(&mut self) {
+        let source = self.redaction_source.as_ref();
+        if self.elements.refresh_redactions(source) {
+            self.static_layer_dirty = true;
+        }
+        if let Some(element) = self.current_element.as_mut() {
+            sc_drawing::redaction::refresh_patch(element, source);
+        }
+    }
+
     /// Handle drawing messages.
     pub fn handle_message(&mut self, message: DrawingMessage) -> Vec<Command> {
         match message {
@@ -172,16 +276,24 @@ impl DrawingManager {
             DrawingMessage::UpdateDrawing(x, y) => {
                 if let Some(ref mut element) = self.current_element {
                     match self.current_tool {
-                        DrawingTool::Pen => {
+                        DrawingTool::Pen | DrawingTool::Highlighter => {
                             element.add_point(x, y);
                             // Pen current stroke caching is handled by `win_renderer`.
                         }
-                        DrawingTool::Rectangle | DrawingTool::Circle | DrawingTool::Arrow => {
+                        DrawingTool::Rectangle
+                        | DrawingTool::Circle
+                        | DrawingTool::Arrow
+                        | DrawingTool::Mosaic
+                        | DrawingTool::Blur => {
                             element.set_end_point(x, y);
                         }
+                        DrawingTool::Step => {
+                            element.points = vec![Point::new(x, y)];
+                        }
                         _ => {}
                     }
                     element.update_bounding_rect();
+                    self.refresh_redactions();
                     vec![Command::RequestRedraw]
                 } else {
                     vec![]
@@ -190,6 +302,7 @@ impl DrawingManager {
             DrawingMessage::FinishDrawing => {
                 if let Some(element) = self.current_element.take() {
                     self.elements.add_element(element);
+                    self.refresh_redactions();
                     self.static_layer_dirty = true;
                     vec![Command::UpdateToolbar, Command::RequestRedraw]
                 } else {
@@ -199,8 +312,8 @@ impl DrawingManager {
             DrawingMessage::Undo => {
                 if let Some((action, sel)) = self.history.undo_action() {
                     self.elements.apply_undo(&action);
-                    self.selected_element = sel;
-                    self.elements.set_selected(self.selected_element);
+                    self.refresh_redactions();
+                    self.select_ids(&sel);
 
                     self.static_layer_dirty = true;
                     self.win_renderer.invalidate_all();
@@ -213,8 +326,8 @@ impl DrawingManager {
             DrawingMessage::Redo => {
                 if let Some((action, sel)) = self.history.redo_action() {
                     self.elements.apply_redo(&action);
-                    self.selected_element = sel;
-                    self.elements.set_selected(self.selected_element);
+                    self.refresh_redactions();
+                    self.select_ids(&sel);
 
                     self.static_layer_dirty = true;
                     self.win_renderer.invalidate_all();
@@ -224,28 +337,24 @@ impl DrawingManager {
                     vec![]
                 }
             }
-            DrawingMessage::DeleteElement(index) => {
-                let element_id = self.elements.get_elements().get(index).map(|e| e.id);
-
-                if let Some(element) = self.elements.get_elements().get(index).cloned() {
-                    let action = history::DrawingAction::RemoveElement { element, index };
-                    self.history
-                        .record_action(action, self.selected_element, None);
-                }
-
-                if self.elements.remove_element(index) {
+            DrawingMessage::DeleteElement(id) => {
+                let selected_before = self.elements.selected_ids();
+                if let Some(position) = self.elements.index_of(id)
+                    && let Some(element) = self.elements.remove_element_by_id(id)
+                {
+                    let action = history::DrawingAction::RemoveElement { element, position };
+                    self.history.record_action(action, selected_before, vec![]);
                     self.selected_element = None;
                     self.static_layer_dirty = true;
-                    if let Some(id) = element_id {
-                        self.win_renderer.remove_element_cache(id);
-                    }
+                    self.win_renderer.remove_element_cache(id);
                     vec![Command::UpdateToolbar, Command::RequestRedraw]
                 } else {
                     vec![]
                 }
             }
-            DrawingMessage::SelectElement(index) => {
+            DrawingMessage::SelectElement(id) => {
                 let old_selection = self.selected_element;
+                let index = id.and_then(|id| self.elements.index_of(id));
                 self.selected_element = index;
                 self.elements.set_selected(index);
 
@@ -263,14 +372,15 @@ impl DrawingManager {
                 vec![Command::UpdateToolbar, Command::RequestRedraw]
             }
             DrawingMessage::AddElement(element) => {
-                let index = self.elements.count();
+                let position = self.elements.count();
                 let action = history::DrawingAction::AddElement {
                     element: (*element).clone(),
-                    index,
+                    position,
                 };
                 self.history
-                    .record_action(action, self.selected_element, None);
+                    .record_action(action, self.elements.selected_ids(), vec![]);
                 self.elements.add_element(*element);
+                self.refresh_redactions();
                 vec![Command::RequestRedraw]
             }
             DrawingMessage::CheckElementClick(x, y) => {
@@ -294,6 +404,19 @@ impl DrawingManager {
                     vec![Command::UpdateToolbar, Command::RequestRedraw]
                 }
             }
+            DrawingMessage::GroupSelected => self.group_selected_elements(),
+            DrawingMessage::UngroupSelected => self.ungroup_selected_elements(),
+            DrawingMessage::ChangeZOrder(z_order) => self.restack_selected_elements(z_order),
+            DrawingMessage::Arrange(arrangement) => self.arrange_selected_elements(arrangement),
+            DrawingMessage::SetFill(fill) => self.fill_selected_elements(|_| fill),
+            DrawingMessage::SetArrowStyle(style) => self.style_selected_arrows(|_| style),
+            DrawingMessage::SetStrokeStyle(style) => {
+                self.stroke_selected_elements(|_| style.clone())
+            }
+            DrawingMessage::ToggleDoubleArrow => self.toggle_selected_double_arrows(),
+            DrawingMessage::CopySelected => self.copy_selected_elements(),
+            DrawingMessage::Paste => self.paste_elements(),
+            DrawingMessage::DuplicateSelected => self.duplicate_selected_elements(),
         }
     }
 
@@ -325,6 +448,13 @@ impl DrawingManager {
         self.selected_element
     }
 
+    /// Id of the selected element, as recorded in history.
+    fn selected_element_id(&self) -> Option<u64> {
+        self.selected_element
+            .and_then(|index| self.elements.get_elements().get(index))
+            .map(|element| element.id)
+    }
+
     /// Read-only element reference.
     pub fn get_element_ref(&self, index: usize) -> Option<&DrawingElement> {
         self.elements.get_elements().get(index)
@@ -0,0 +1,412 @@
+use sc_drawing::{
+    Arrangement, ArrowHead, ArrowStyle, Color, DrawingElement, DrawingTool, HandleConfig, Point,
+    Rect, SNAP_TOLERANCE, StrokeStyle, ZOrder, calculate_resized_rect,
+    calculate_resized_rect_keep_aspect, combined_bounds, detect_handle_at_position,
+    elements_in_rubber_band, rubber_band_rect, scale_element_between, snap_rect, toggle_selection,
+};
+
+use sc_drawing::history::DrawingAction;
+
+use super::{DragMode, DrawingManager, ElementInteractionMode};
+use sc_host_protocol::Command;
+
+impl DrawingManager {
+    /// Ids of all selected elements, in stacking order.
+    pub fn get_selected_element_ids(&self) -> Vec<u64> {
+        self.elements.selected_ids()
+    }
+
+    /// More than one element is selected.
+    pub fn has_group_selection(&self) -> bool {
+        self.elements
+            .get_elements()
+            .iter()
+            .filter(|e| e.selected)
+            .count()
+            > 1
+    }
+
+    /// Replace the selection. A single id becomes the ordinary single selection.
+    pub(super) fn select_ids(&mut self, ids: &[u64]) {
+        let before = self.elements.selected_ids();
+        if let [id] = ids {
+            self.selected_element = self.elements.index_of(*id);
+            self.elements.set_selected(self.selected_element);
+        } else {
+            self.selected_element = None;
+            self.elements.set_selected_ids(ids);
+        }
+        if before != self.elements.selected_ids() {
+            self.static_layer_dirty = true;
+        }
+    }
+
+    /// Shift/Ctrl-click on an element. Grouped elements are toggled together.
+    pub(super) fn toggle_element_selection(&mut self, id: u64) -> Vec<Command> {
+        let mut ids = self.elements.selected_ids();
+        let members = self.elements.group_members(id);
+        if members.iter().all(|member| ids.contains(member)) {
+            ids.retain(|selected| !members.contains(selected));
+        } else {
+            for member in members {
+                if !ids.contains(&member) {
+                    toggle_selection(&mut ids, member);
+                }
+            }
+        }
+        self.select_ids(&ids);
+        vec![Command::UpdateToolbar, Command::RequestRedraw]
+    }
+
+    pub(super) fn begin_rubber_band(&mut self, x: i32, y: i32) {
+        self.interaction_mode = ElementInteractionMode::RubberBand;
+        self.mouse_pressed = true;
+        self.interaction_start_pos = Point::new(x, y);
+        self.rubber_band = None;
+    }
+
+    pub(super) fn update_rubber_band(&mut self, x: i32, y: i32) {
+        self.rubber_band = Some(rubber_band_rect(
+            self.interaction_start_pos,
+            Point::new(x, y),
+        ));
+    }
+
+    /// Add everything inside the band to the selection. Freehand strokes are left out, as they
+    /// are when clicking.
+    pub(super) fn finish_rubber_band(&mut self) {
+        let Some(band) = self.rubber_band.take() else {
+            return;
+        };
+        let mut ids = self.elements.selected_ids();
+        for id in elements_in_rubber_band(self.elements.get_elements(), &band) {
+            let freeform = self
+                .elements
+                .get_element_by_id(id)
+                .is_some_and(|e| e.tool.is_freeform());
+            if !freeform && !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        let ids = self.elements.expand_to_groups(&ids);
+        self.select_ids(&ids);
+    }
+
+    /// Click on a grouped element: select the whole group and start moving it.
+    pub(super) fn begin_group_click(&mut self, id: u64, x: i32, y: i32) -> bool {
+        let members = self.elements.group_members(id);
+        if members.len() < 2 {
+            return false;
+        }
+        self.select_ids(&members);
+        self.begin_group_drag(x, y)
+    }
+
+    /// Group the selection as one undo step.
+    pub(super) fn group_selected_elements(&mut self) -> Vec<Command> {
+        let ids = self.elements.selected_ids();
+        let Some(action) = self.elements.group_elements(&ids) else {
+            return vec![];
+        };
+        let selected = self.elements.expand_to_groups(&ids);
+        self.select_ids(&selected);
+        self.history.record_action(action, ids, selected);
+        vec![Command::UpdateToolbar, Command::RequestRedraw]
+    }
+
+    /// Dissolve the groups touched by the selection as one undo step. The members stay selected.
+    pub(super) fn ungroup_selected_elements(&mut self) -> Vec<Command> {
+        let ids = self.elements.selected_ids();
+        let Some(action) = self.elements.ungroup_elements(&ids) else {
+            return vec![];
+        };
+        self.history.record_action(action, ids.clone(), ids);
+        vec![Command::UpdateToolbar, Command::RequestRedraw]
+    }
+
+    /// Move the selection up or down the stacking order as one undo step.
+    pub(super) fn restack_selected_elements(&mut self, z_order: ZOrder) -> Vec<Command> {
+        let selected = self.selected_element_id();
+        let ids = self.elements.selected_ids();
+        let Some(action) = self.elements.restack_elements(&ids, z_order) else {
+            return vec![];
+        };
+        self.history.record_action(action, ids.clone(), ids);
+        self.selected_element = selected.and_then(|id| self.elements.index_of(id));
+        self.static_layer_dirty = true;
+        vec![Command::RequestRedraw]
+    }
+
+    /// Align or distribute the selection as one undo step.
+    pub(super) fn arrange_selected_elements(&mut self, arrangement: Arrangement) -> Vec<Command> {
+        let ids = self.elements.selected_ids();
+        let Some(action) = self.elements.arrange_elements(&ids, arrangement) else {
+            return vec![];
+        };
+        self.history.record_action(action, ids.clone(), ids);
+        self.static_layer_dirty = true;
+        self.refresh_redactions();
+        vec![Command::RequestRedraw]
+    }
+
+    /// Change the fill of the selected shapes as one undo step; `fill` picks each new fill.
+    pub(super) fn fill_selected_elements(
+        &mut self,
+        fill: impl Fn(&DrawingElement) -> Option<Color>,
+    ) -> Vec<Command> {
+        let ids = self.elements.selected_ids();
+        let Some(action) = self.elements.set_fill(&ids, fill) else {
+            return vec![];
+        };
+        self.history.record_action(action, ids.clone(), ids);
+        self.static_layer_dirty = true;
+        vec![Command::RequestRedraw]
+    }
+
+    /// The fill Ctrl+Shift+F applies: none if every selected shape already has one, otherwise
+    /// the configured fill colour and opacity.
+    pub(super) fn fill_for_selection(&self) -> Option<Color> {
+        let all_filled = self
+            .elements
+            .get_elements()
+            .iter()
+            .filter(|e| e.selected && e.tool.can_fill())
+            .all(|e| e.fill.is_some());
+        if all_filled {
+            return None;
+        }
+        let (r, g, b) = self.config.fill_color;
+        Some(Color {
+            r: r as f32 / 255.0,
+            g: g as f32 / 255.0,
+            b: b as f32 / 255.0,
+            a: self.config.fill_opacity as f32 / 255.0,
+        })
+    }
+
+    /// Change the markers of the selected arrows as one undo step; `style` picks each new style.
+    pub(super) fn style_selected_arrows(
+        &mut self,
+        style: impl Fn(&DrawingElement) -> ArrowStyle,
+    ) -> Vec<Command> {
+        let ids = self.elements.selected_ids();
+        let Some(action) = self.elements.set_arrow_style(&ids, style) else {
+            return vec![];
+        };
+        self.history.record_action(action, ids.clone(), ids);
+        self.static_layer_dirty = true;
+        vec![Command::RequestRedraw]
+    }
+
+    /// Change the stroke style of the selected shapes as one undo step; `style` picks each new
+    /// style.
+    pub(super) fn stroke_selected_elements(
+        &mut self,
+        style: impl Fn(&DrawingElement) -> StrokeStyle,
+    ) -> Vec<Command> {
+        let ids = self.elements.selected_ids();
+        let Some(action) = self.elements.set_stroke_style(&ids, style) else {
+            return vec![];
+        };
+        self.history.record_action(action, ids.clone(), ids);
+        self.static_layer_dirty = true;
+        vec![Command::RequestRedraw]
+    }
+
+    /// Take the tail marker off if every selected arrow has one, otherwise repeat each head at
+    /// the tail.
+    pub(super) fn toggle_selected_double_arrows(&mut self) -> Vec<Command> {
+        let all_double = self
+            .elements
+            .get_elements()
+            .iter()
+            .filter(|e| e.selected && e.tool == DrawingTool::Arrow)
+            .all(|e| e.arrow.tail != ArrowHead::None);
+        self.style_selected_arrows(|e| {
+            let head = match e.arrow.head {
+                ArrowHead::None => ArrowHead::Open,
+                head => head,
+            };
+            let tail = if all_double { ArrowHead::None } else { head };
+            ArrowStyle { head, tail }
+        })
+    }
+
+    /// Start moving or resizing the whole multi-selection if `(x, y)` is on its handles or on
+    /// one of its elements. Returns `false` if the press is not meant for the group.
+    pub(super) fn begin_group_drag(&mut self, x: i32, y: i32) -> bool {
+        if !self.has_group_selection() {
+            return false;
+        }
+        let members: Vec<_> = self
+            .elements
+            .get_elements()
+            .iter()
+            .filter(|e| e.selected)
+            .cloned()
+            .collect();
+        let Some(bounds) = combined_bounds(&members) else {
+            return false;
+        };
+
+        let handle = detect_handle_at_position(x, y, &bounds, HandleConfig::Full);
+        let on_member = self
+            .elements
+            .get_element_at_position(x, y)
+            .is_some_and(|index| self.elements.get_elements()[index].selected);
+        self.interaction_mode = if handle != DragMode::None {
+            ElementInteractionMode::ResizingElement(handle)
+        } else if on_member {
+            ElementInteractionMode::MovingElement
+        } else {
+            return false;
+        };
+
+        self.mouse_pressed = true;
+        self.interaction_start_pos = Point::new(x, y);
+        self.interaction_start_rect = bounds;
+        self.group_drag_start = members;
+        true
+    }
+
+    /// Lay the group out again from where it was when the drag started, snapping its bounds
+    /// onto `targets`.
+    pub(super) fn update_group_drag(
+        &mut self,
+        x: i32,
+        y: i32,
+        keep_aspect: bool,
+        targets: &[Rect],
+    ) {
+        let mut dx = x - self.interaction_start_pos.x;
+        let mut dy = y - self.interaction_start_pos.y;
+        let from = self.interaction_start_rect;
+        let to = match self.interaction_mode {
+            ElementInteractionMode::MovingElement => {
+                let (moved, guides) = snap_rect(
+                    from.offset(dx, dy),
+                    DragMode::MovingElement,
+                    targets,
+                    SNAP_TOLERANCE,
+                );
+                self.snap_guides = guides;
+                dx = moved.left - from.left;
+                dy = moved.top - from.top;
+                None
+            }
+            ElementInteractionMode::ResizingElement(mode) => {
+                let to = if keep_aspect {
+                    calculate_resized_rect_keep_aspect(from, mode, dx, dy)
+                } else {
+                    let (to, guides) = snap_rect(
+                        calculate_resized_rect(from, mode, dx, dy),
+                        mode,
+                        targets,
+                        SNAP_TOLERANCE,
+                    );
+                    self.snap_guides = guides;
+                    to
+                };
+                if to.width() < 1 || to.height() < 1 {
+                    return;
+                }
+                Some(to)
+            }
+            _ => return,
+        };
+
+        for start in &self.group_drag_start {
+            let mut element = start.clone();
+            match to {
+                Some(to) => scale_element_between(&mut element, from, to),
+                None => element.move_by(dx, dy),
+            }
+            if let Some(index) = self.elements.index_of(element.id) {
+                self.elements.set_element(index, element);
+            }
+        }
+        // Group members stay in the static layer, so it has to follow them.
+        self.static_layer_dirty = true;
+    }
+
+    /// Record the finished group drag as a single undo step.
+    pub(super) fn end_group_drag(&mut self) {
+        let start = std::mem::take(&mut self.group_drag_start);
+        let mut actions = Vec::new();
+        for old in start {
+            let Some(new) = self.elements.get_element_by_id(old.id) else {
+                continue;
+            };
+            match self.interaction_mode {
+                ElementInteractionMode::MovingElement => {
+                    let dx = new.rect.left - old.rect.left;
+                    let dy = new.rect.top - old.rect.top;
+                    if dx != 0 || dy != 0 {
+                        actions.push(DrawingAction::MoveElement {
+                            id: old.id,
+                            dx,
+                            dy,
+                            old_points: old.points,
+                            old_rect: old.rect,
+                        });
+                    }
+                }
+                ElementInteractionMode::ResizingElement(_)
+                    if new.rect != old.rect
+                        || new.points != old.points
+                        || new.font_size != old.font_size =>
+                {
+                    actions.push(DrawingAction::ResizeElement {
+                        id: old.id,
+                        old_points: old.points,
+                        old_rect: old.rect,
+                        old_font_size: old.font_size,
+                        old_blur_radius: old.blur_radius,
+                        new_points: new.points.clone(),
+                        new_rect: new.rect,
+                        new_font_size: new.font_size,
+                        new_blur_radius: new.blur_radius,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if !actions.is_empty() {
+            let selected = self.elements.selected_ids();
+            self.history.record_action(
+                DrawingAction::Compound { actions },
+                selected.clone(),
+                selected,
+            );
+        }
+        self.static_layer_dirty = true;
+    }
+
+    /// Remove every selected element as a single undo step.
+    pub(super) fn delete_selected_elements(&mut self) -> Vec<Command> {
+        // Top-most first, so that undo (which reverts in reverse order) reinserts each element
+        // after everything below it is back in place.
+        let selected_before = self.elements.selected_ids();
+        let mut actions = Vec::new();
+        for id in selected_before.iter().rev().copied() {
+            let Some(position) = self.elements.index_of(id) else {
+                continue;
+            };
+            if let Some(element) = self.elements.remove_element_by_id(id) {
+                self.win_renderer.remove_element_cache(id);
+                actions.push(DrawingAction::RemoveElement { element, position });
+            }
+        }
+        if actions.is_empty() {
+            return vec![];
+        }
+
+        self.history
+            .record_action(DrawingAction::Compound { actions }, selected_before, vec![]);
+        self.selected_element = None;
+        self.static_layer_dirty = true;
+        self.refresh_redactions();
+        vec![Command::UpdateToolbar, Command::RequestRedraw]
+    }
+}
@@ -2,6 +2,8 @@ use windows::Win32::Foundation::RECT as WinRect;
 use windows::Win32::Graphics::Direct2D::ID2D1RenderTarget;
 
 use sc_drawing::Rect as DrawingRect;
+use sc_drawing::sc_rendering::RenderList;
+use sc_drawing::snap_guide_render_items;
 use sc_drawing::windows::TextCursorState;
 use sc_platform_windows::windows::Direct2DRenderer;
 
@@ -77,6 +79,7 @@ impl DrawingManager {
                 self.current_element.as_ref(),
                 self.selected_element,
                 self.cursor_state_for_renderer(),
+                self.rubber_band,
                 win_selection_rect.as_ref(),
                 needs_rebuild,
             )
@@ -86,6 +89,14 @@ impl DrawingManager {
             self.static_layer_dirty = false;
         }
 
+        if !self.snap_guides.is_empty() {
+            let mut guides = RenderList::new();
+            guides.submit_batch(snap_guide_render_items(&self.snap_guides));
+            guides
+                .execute(d2d_renderer)
+                .map_err(|e| DrawingError::RenderError(format!("{e:?}")))?;
+        }
+
         Ok(())
     }
 
@@ -1,4 +1,5 @@
 use crate::constants::{MIN_TEXT_HEIGHT, MIN_TEXT_WIDTH, TEXT_LINE_HEIGHT_SCALE};
+use sc_drawing::defaults;
 use sc_host_protocol::Command;
 
 use super::{DrawingAction, DrawingElement, DrawingManager, DrawingTool};
@@ -6,12 +7,7 @@ use super::{DrawingAction, DrawingElement, DrawingManager, DrawingTool};
 impl DrawingManager {
     pub(super) fn get_text_element_at_position(&self, x: i32, y: i32) -> Option<usize> {
         for (index, element) in self.elements.get_elements().iter().enumerate() {
-            if element.tool == DrawingTool::Text
-                && x >= element.rect.left
-                && x <= element.rect.right
-                && y >= element.rect.top
-                && y <= element.rect.bottom
-            {
+            if element.tool.is_text() && element.contains_point(x, y) {
                 return Some(index);
             }
         }
@@ -38,22 +34,38 @@ impl DrawingManager {
         ]
     }
 
-    pub(super) fn create_and_edit_text_element(&mut self, x: i32, y: i32) -> (Vec<Command>, bool) {
+    /// Start typing into a new text element or callout of `tool` whose box starts at `(x, y)`.
+    pub(super) fn create_and_edit_text_element(
+        &mut self,
+        tool: DrawingTool,
+        x: i32,
+        y: i32,
+    ) -> (Vec<Command>, bool) {
         if self.selected_element.is_some() {
             self.static_layer_dirty = true;
         }
         self.elements.set_selected(None);
         self.selected_element = None;
 
-        self.current_tool = DrawingTool::Text;
+        self.current_tool = tool;
 
-        let mut text_element = DrawingElement::new(DrawingTool::Text);
+        let mut text_element = DrawingElement::new(tool);
         text_element.add_point(x, y);
 
-        // Use host-injected config (stored in the drawing manager).
-        let (r, g, b) = self.config.font_color;
+        // Use host-injected config (stored in the drawing manager). A callout is outlined like
+        // the other shapes and its text shares the outline colour.
+        let (r, g, b) = if tool == DrawingTool::Callout {
+            self.config.drawing_color
+        } else {
+            self.config.font_color
+        };
         text_element.color =
             sc_drawing::Color::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0);
+        if tool == DrawingTool::Callout {
+            text_element.thickness = self.config.line_thickness;
+            text_element.stroke = self.config.stroke_style.clone();
+            text_element.fill = Some(defaults::CALLOUT_FILL);
+        }
         text_element.font_size = self.config.font_size;
         text_element.font_name = self.config.font_name.clone();
         text_element.font_weight = self.config.font_weight;
@@ -70,6 +82,10 @@ impl DrawingManager {
         let initial_height = dynamic_line_height + (padding * 2.0).ceil() as i32;
 
         text_element.set_end_point(x + initial_width, y + initial_height);
+        if tool == DrawingTool::Callout {
+            // The tail starts out hanging below the box; its tip is dragged onto the target.
+            text_element.add_point(x, y + initial_height + defaults::CALLOUT_TAIL_LENGTH);
+        }
 
         text_element.update_bounding_rect();
 
@@ -104,7 +120,11 @@ impl DrawingManager {
         self.editing_element_index = None;
         self.text_cursor_pos = 0;
 
-        self.current_tool = DrawingTool::Text;
+        // Stay on the tool that made the element, so the next click places another one.
+        let tool = editing_index
+            .and_then(|index| self.elements.get_elements().get(index))
+            .map_or(DrawingTool::Text, |element| element.tool);
+        self.current_tool = tool;
 
         if let Some(element_index) = editing_index
             && let Some(element) = self.elements.get_elements().get(element_index).cloned()
@@ -124,13 +144,13 @@ impl DrawingManager {
             } else {
                 let action = DrawingAction::AddElement {
                     element,
-                    index: element_index,
+                    position: element_index,
                 };
-                self.history.record_action(action, None, None);
+                self.history.record_action(action, vec![], vec![]);
             }
         }
 
-        self.current_tool = DrawingTool::Text;
+        self.current_tool = tool;
 
         self.just_saved_text = true;
 
@@ -182,11 +202,12 @@ impl DrawingManager {
                 && let Some(element) = self.elements.get_elements().get(element_index)
             {
                 let cursor_margin = 5;
+                let bounds = element.get_bounding_rect();
                 let dirty_rect = sc_app::selection::RectI32 {
-                    left: element.rect.left - cursor_margin,
-                    top: element.rect.top - cursor_margin,
-                    right: element.rect.right + cursor_margin,
-                    bottom: element.rect.bottom + cursor_margin,
+                    left: bounds.left - cursor_margin,
+                    top: bounds.top - cursor_margin,
+                    right: bounds.right + cursor_margin,
+                    bottom: bounds.bottom + cursor_margin,
                 };
                 return vec![Command::RequestRedrawRect(dirty_rect)];
             }
@@ -0,0 +1,245 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ExtendedColorType, ImageEncoder, RgbaImage};
+
+use crate::types::{EncodeOptions, ExportError, ImageFormat};
+
+/// Encode tightly packed RGBA8 pixels into `format`.
+pub fn encode_rgba(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    format: ImageFormat,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let image = RgbaImage::from_raw(width, height, pixels.to_vec())
+        .ok_or(ExportError::InvalidDimensions { width, height })?;
+    encode_image(&image, format, options)
+}
+
+/// Re-encode a BMP blob (as produced by the D2D/GDI compose paths) into `format`.
+pub fn encode_bmp(
+    bmp_data: &[u8],
+    format: ImageFormat,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let image = image::load_from_memory_with_format(bmp_data, image::ImageFormat::Bmp)
+        .map_err(|e| ExportError::Decode(e.to_string()))?
+        .into_rgba8();
+    encode_image(&image, format, options)
+}
+
+/// Encode `bmp_data` in the format implied by `path`'s extension and write it out.
+///
+/// A path without an extension is rejected rather than renamed, so the file written is
+/// always the one the caller checked for overwriting. Returns the format that was written.
+pub fn save_bmp_to_file(
+    bmp_data: &[u8],
+    path: impl AsRef<Path>,
+    options: &EncodeOptions,
+) -> Result<ImageFormat, ExportError> {
+    let path = path.as_ref();
+    let ext = path.extension().ok_or(ExportError::MissingExtension)?;
+    let format = ImageFormat::from_path(path)
+        .ok_or_else(|| ExportError::UnsupportedFormat(ext.to_string_lossy().into()))?;
+    let encoded = encode_bmp(bmp_data, format, options)?;
+    std::fs::write(path, encoded)?;
+    Ok(format)
+}
+
+fn encode_image(
+    image: &RgbaImage,
+    format: ImageFormat,
+    options: &EncodeOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return Err(ExportError::InvalidDimensions { width, height });
+    }
+
+    #[cfg(feature = "lossy-webp")]
+    if format == ImageFormat::WebP && options.webp_quality < 100 {
+        return encode_lossy_webp(image, options.webp_quality);
+    }
+
+    let mut output = Cursor::new(Vec::new());
+    let result = match format {
+        ImageFormat::Png => {
+            let compression = match options.png_compression {
+                0 => CompressionType::Uncompressed,
+                level => CompressionType::Level(level.min(9)),
+            };
+            PngEncoder::new_with_quality(&mut output, compression, FilterType::Adaptive)
+                .write_image(image.as_raw(), width, height, ExtendedColorType::Rgba8)
+        }
+        ImageFormat::Jpeg => {
+            // JPEG has no alpha channel; captures are opaque, so nothing is lost.
+            let rgb = DynamicImage::ImageRgba8(image.clone()).into_rgb8();
+            JpegEncoder::new_with_quality(&mut output, options.jpeg_quality.clamp(1, 100))
+                .write_image(rgb.as_raw(), width, height, ExtendedColorType::Rgb8)
+        }
+        ImageFormat::WebP => WebPEncoder::new_lossless(&mut output).write_image(
+            image.as_raw(),
+            width,
+            height,
+            ExtendedColorType::Rgba8,
+        ),
+        ImageFormat::Bmp => image.write_to(&mut output, image::ImageFormat::Bmp),
+        ImageFormat::Tiff => image.write_to(&mut output, image::ImageFormat::Tiff),
+    };
+    result.map_err(|e| ExportError::Encode(e.to_string()))?;
+
+    Ok(output.into_inner())
+}
+
+/// `image` only ships a lossless WebP encoder, so lossy output goes through libwebp.
+#[cfg(feature = "lossy-webp")]
+fn encode_lossy_webp(image: &RgbaImage, quality: u8) -> Result<Vec<u8>, ExportError> {
+    let (width, height) = image.dimensions();
+    let encoded = webp::Encoder::from_rgba(image.as_raw(), width, height)
+        .encode_simple(false, quality.max(1) as f32)
+        .map_err(|e| ExportError::Encode(format!("{e:?}")))?;
+    Ok(encoded.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_pixels(width: u32, height: u32) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.extend_from_slice(&[(x * 16) as u8, (y * 16) as u8, 128, 255]);
+            }
+        }
+        pixels
+    }
+
+    fn sample_bmp(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_raw(width, height, sample_pixels(width, height)).unwrap();
+        let mut output = Cursor::new(Vec::new());
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut output, image::ImageFormat::Bmp)
+            .unwrap();
+        output.into_inner()
+    }
+
+    #[test]
+    fn format_from_extension_is_case_insensitive() {
+        assert_eq!(ImageFormat::from_path("a/shot.PNG"), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::from_path("shot.jpeg"), Some(ImageFormat::Jpeg));
+        assert_eq!(ImageFormat::from_path("shot.JPG"), Some(ImageFormat::Jpeg));
+        assert_eq!(ImageFormat::from_path("shot.webp"), Some(ImageFormat::WebP));
+        assert_eq!(ImageFormat::from_path("shot.tif"), Some(ImageFormat::Tiff));
+        assert_eq!(ImageFormat::from_path("shot.bmp"), Some(ImageFormat::Bmp));
+        assert_eq!(ImageFormat::from_path("shot"), None);
+        assert_eq!(ImageFormat::from_path("shot.gif"), None);
+    }
+
+    #[test]
+    fn encoded_output_matches_requested_container() {
+        let bmp = sample_bmp(8, 6);
+        let options = EncodeOptions::default();
+
+        for (format, expected) in [
+            (ImageFormat::Png, image::ImageFormat::Png),
+            (ImageFormat::Jpeg, image::ImageFormat::Jpeg),
+            (ImageFormat::WebP, image::ImageFormat::WebP),
+            (ImageFormat::Bmp, image::ImageFormat::Bmp),
+            (ImageFormat::Tiff, image::ImageFormat::Tiff),
+        ] {
+            let bytes = encode_bmp(&bmp, format, &options).unwrap();
+            assert_eq!(image::guess_format(&bytes).unwrap(), expected);
+
+            let decoded = image::load_from_memory(&bytes).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (8, 6));
+        }
+    }
+
+    #[test]
+    fn lossless_formats_round_trip_pixels() {
+        let pixels = sample_pixels(5, 4);
+        let options = EncodeOptions {
+            webp_quality: 100,
+            ..Default::default()
+        };
+
+        for format in [ImageFormat::Png, ImageFormat::WebP, ImageFormat::Tiff] {
+            let bytes = encode_rgba(&pixels, 5, 4, format, &options).unwrap();
+            let decoded = image::load_from_memory(&bytes).unwrap().into_rgba8();
+            assert_eq!(decoded.as_raw(), &pixels, "{format:?}");
+        }
+    }
+
+    #[cfg(not(feature = "lossy-webp"))]
+    #[test]
+    fn webp_is_lossless_without_lossy_feature() {
+        let pixels = sample_pixels(5, 4);
+        let options = EncodeOptions {
+            webp_quality: 50,
+            ..Default::default()
+        };
+
+        let bytes = encode_rgba(&pixels, 5, 4, ImageFormat::WebP, &options).unwrap();
+        let decoded = image::load_from_memory(&bytes).unwrap().into_rgba8();
+        assert_eq!(decoded.as_raw(), &pixels);
+    }
+
+    #[test]
+    fn jpeg_quality_affects_output_size() {
+        let pixels = sample_pixels(64, 64);
+        let low = EncodeOptions {
+            jpeg_quality: 10,
+            ..Default::default()
+        };
+        let high = EncodeOptions {
+            jpeg_quality: 100,
+            ..Default::default()
+        };
+
+        let low = encode_rgba(&pixels, 64, 64, ImageFormat::Jpeg, &low).unwrap();
+        let high = encode_rgba(&pixels, 64, 64, ImageFormat::Jpeg, &high).unwrap();
+        assert!(low.len() < high.len());
+    }
+
+    fn scratch_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("sc_export_{name}_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn unsupported_extension_is_not_written() {
+        let dir = scratch_dir("unsupported");
+        let path = dir.join("shot.gif");
+        let err = save_bmp_to_file(&sample_bmp(4, 4), &path, &EncodeOptions::default());
+        assert!(matches!(err, Err(ExportError::UnsupportedFormat(ext)) if ext == "gif"));
+        assert!(!path.exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_extension_is_not_written() {
+        let dir = scratch_dir("missing");
+        let err = save_bmp_to_file(
+            &sample_bmp(4, 4),
+            dir.join("shot"),
+            &EncodeOptions::default(),
+        );
+        assert!(matches!(err, Err(ExportError::MissingExtension)));
+        assert!(!dir.join("shot").exists());
+        assert!(!dir.join("shot.png").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_mismatched_buffer() {
+        let err = encode_rgba(&[0; 12], 4, 4, ImageFormat::Png, &EncodeOptions::default());
+        assert!(matches!(err, Err(ExportError::InvalidDimensions { .. })));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod encoder;
+pub mod project;
+pub mod svg;
+pub mod types;
+
+pub use encoder::*;
+pub use project::*;
+pub use svg::*;
+pub use types::*;
@@ -0,0 +1,341 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sc_drawing::redaction::refresh_patch;
+use sc_drawing::{ChannelOrder, DrawingElement, PixelBuffer, RedactionPatch, RedactionSource};
+use serde::{Deserialize, Serialize};
+
+use crate::encoder::encode_rgba;
+use crate::types::{EncodeOptions, ExportError, ImageFormat};
+
+/// File extension (without the dot) of annotation project files.
+pub const PROJECT_EXTENSION: &str = "scproj";
+
+/// Version written by [`Project::to_bytes`]. Files from newer versions are rejected.
+pub const PROJECT_VERSION: u32 = 1;
+
+/// Marker that tells project files apart from arbitrary JSON.
+const PROJECT_FORMAT: &str = "sc_windows.project";
+
+/// A capture that can be reopened for editing: the unannotated image plus its elements.
+///
+/// Element coordinates are relative to the image's top-left corner. Mosaic and blur areas are
+/// baked into the stored image, so the pixels they hide cannot be recovered from the file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Project {
+    pub image: PixelBuffer,
+    pub elements: Vec<DrawingElement>,
+}
+
+/// On-disk layout: a single JSON document with the image embedded as base64 PNG.
+#[derive(Serialize, Deserialize)]
+struct ProjectFile {
+    format: String,
+    version: u32,
+    width: u32,
+    height: u32,
+    image_png: String,
+    elements: Vec<DrawingElement>,
+}
+
+impl Project {
+    pub fn new(image: PixelBuffer, elements: Vec<DrawingElement>) -> Self {
+        Self { image, elements }
+    }
+
+    /// Serialize to the current project format, with redactions baked into the image.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ExportError> {
+        let (width, height) = (self.image.width, self.image.height);
+        let (image, _) = redact(&self.image, &self.elements);
+        let png = encode_rgba(
+            &opaque_rgba(&image),
+            width,
+            height,
+            ImageFormat::Png,
+            &EncodeOptions::default(),
+        )?;
+
+        let file = ProjectFile {
+            format: PROJECT_FORMAT.to_string(),
+            version: PROJECT_VERSION,
+            width,
+            height,
+            image_png: BASE64.encode(png),
+            elements: self.elements.clone(),
+        };
+        serde_json::to_vec(&file).map_err(|e| ExportError::Encode(e.to_string()))
+    }
+
+    /// Parse a project file. The image is always returned as opaque RGBA.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ExportError> {
+        let file: ProjectFile =
+            serde_json::from_slice(data).map_err(|e| ExportError::InvalidProject(e.to_string()))?;
+        if file.format != PROJECT_FORMAT {
+            return Err(ExportError::InvalidProject(format!(
+                "unknown format '{}'",
+                file.format
+            )));
+        }
+        if file.version > PROJECT_VERSION {
+            return Err(ExportError::UnsupportedProjectVersion(file.version));
+        }
+
+        let png = BASE64
+            .decode(&file.image_png)
+            .map_err(|e| ExportError::InvalidProject(e.to_string()))?;
+        let image = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+            .map_err(|e| ExportError::Decode(e.to_string()))?
+            .into_rgba8();
+        if image.dimensions() != (file.width, file.height) {
+            return Err(ExportError::InvalidDimensions {
+                width: image.width(),
+                height: image.height(),
+            });
+        }
+
+        let image = PixelBuffer::from_raw(
+            file.width,
+            file.height,
+            ChannelOrder::Rgba,
+            image.into_raw(),
+        )
+        .ok_or(ExportError::InvalidDimensions {
+            width: file.width,
+            height: file.height,
+        })?;
+
+        Ok(Self {
+            image,
+            elements: file.elements,
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ExportError> {
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ExportError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+/// Whether `path` names a project file rather than an image.
+pub fn is_project_path(path: impl AsRef<Path>) -> bool {
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(PROJECT_EXTENSION))
+}
+
+/// `image` with every mosaic and blur patch in `elements` pasted over it, plus those patches
+/// in element order (`None` for other elements).
+///
+/// Patches are built from the untouched capture, exactly as the editor draws them.
+pub(crate) fn redact(
+    image: &PixelBuffer,
+    elements: &[DrawingElement],
+) -> (PixelBuffer, Vec<Option<RedactionPatch>>) {
+    let source = RedactionSource::unscaled(Arc::new(image.clone()));
+    let patches: Vec<Option<RedactionPatch>> = elements
+        .iter()
+        .map(|element| {
+            if !element.tool.is_redaction() || element.points.len() < 2 {
+                return None;
+            }
+            let mut element = element.clone();
+            refresh_patch(&mut element, Some(&source));
+            element.redaction
+        })
+        .collect();
+
+    let mut redacted = image.clone();
+    for patch in patches.iter().flatten() {
+        paste(
+            &mut redacted,
+            &patch.pixels,
+            patch.rect.left,
+            patch.rect.top,
+        );
+    }
+    (redacted, patches)
+}
+
+/// Copy `patch` into `target` with its top-left corner at (`left`, `top`).
+fn paste(target: &mut PixelBuffer, patch: &PixelBuffer, left: i32, top: i32) {
+    for y in 0..patch.height {
+        for x in 0..patch.width {
+            let (tx, ty) = (left + x as i32, top + y as i32);
+            if tx >= 0 && ty >= 0 && (tx as u32) < target.width && (ty as u32) < target.height {
+                target.set_pixel(tx as u32, ty as u32, patch.pixel(x, y));
+            }
+        }
+    }
+}
+
+/// RGBA copy of `image` with alpha forced to 255; GDI captures may carry a zero alpha.
+pub(crate) fn opaque_rgba(image: &PixelBuffer) -> Vec<u8> {
+    let mut pixels = image.pixels.clone();
+    for pixel in pixels.chunks_exact_mut(4) {
+        if image.order == ChannelOrder::Bgra {
+            pixel.swap(0, 2);
+        }
+        pixel[3] = 255;
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use sc_drawing::{Color, DrawingTool, ElementManager};
+
+    use super::*;
+
+    fn sample_image(order: ChannelOrder) -> PixelBuffer {
+        let mut image = PixelBuffer::new(6, 4, order);
+        for y in 0..4 {
+            for x in 0..6 {
+                image.set_pixel(x, y, [(x * 40) as u8, (y * 60) as u8, 7, 255]);
+            }
+        }
+        image
+    }
+
+    fn sample_elements() -> Vec<DrawingElement> {
+        let mut rect = DrawingElement::new(DrawingTool::Rectangle);
+        rect.add_point(1, 1);
+        rect.set_end_point(4, 3);
+        rect.update_bounding_rect();
+        rect.color = Color::new(0.2, 0.4, 0.6, 0.8);
+        rect.thickness = 5.0;
+        rect.fill = Some(Color::new(1.0, 1.0, 0.0, 0.25));
+
+        let mut text = DrawingElement::new(DrawingTool::Text);
+        text.add_point(2, 2);
+        text.text = "标注\nsecond line".to_string();
+        text.font_size = 31.5;
+        text.font_name = "Consolas".to_string();
+        text.font_weight = 700;
+        text.font_italic = true;
+        text.font_underline = true;
+        text.font_strikeout = true;
+        text.update_bounding_rect();
+
+        let mut blur = DrawingElement::new(DrawingTool::Blur);
+        blur.add_point(0, 0);
+        blur.set_end_point(5, 3);
+        blur.set_blur_radius(7.5);
+        blur.update_bounding_rect();
+
+        vec![rect, text, blur]
+    }
+
+    #[test]
+    fn round_trip_restores_manager_state_exactly() {
+        let mut manager = ElementManager::new();
+        for element in sample_elements() {
+            manager.add_element(element);
+        }
+        manager.set_selected(Some(1));
+
+        let project = Project::new(
+            sample_image(ChannelOrder::Rgba),
+            manager.get_elements().clone(),
+        );
+        let loaded = Project::from_bytes(&project.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.elements, project.elements);
+        assert_eq!(loaded.image, redact(&project.image, &project.elements).0);
+
+        let mut restored = ElementManager::new();
+        restored.restore_state(loaded.elements);
+        assert_eq!(restored.get_elements(), manager.get_elements());
+    }
+
+    #[test]
+    fn bgra_images_are_stored_as_opaque_rgba() {
+        let mut image = sample_image(ChannelOrder::Bgra);
+        image.set_pixel(0, 0, [1, 2, 3, 0]);
+
+        let loaded = Project::from_bytes(&Project::new(image, vec![]).to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.image.order, ChannelOrder::Rgba);
+        assert_eq!(loaded.image.pixel(0, 0), [3, 2, 1, 255]);
+    }
+
+    #[test]
+    fn redacted_pixels_never_reach_the_file() {
+        // Checkerboard whose 2x2 mosaic average is a flat purple.
+        let mut image = PixelBuffer::new(8, 8, ChannelOrder::Rgba);
+        for y in 0..8 {
+            for x in 0..8 {
+                let v = if (x + y) % 2 == 0 { 255 } else { 0 };
+                image.set_pixel(x, y, [v, 0, 255 - v, 255]);
+            }
+        }
+        let mut mosaic = DrawingElement::new(DrawingTool::Mosaic);
+        mosaic.add_point(2, 2);
+        mosaic.set_end_point(6, 6);
+        mosaic.update_bounding_rect();
+        mosaic.block_size = 2;
+
+        let bytes = Project::new(image.clone(), vec![mosaic])
+            .to_bytes()
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let png = BASE64.decode(value["image_png"].as_str().unwrap()).unwrap();
+        let stored = image::load_from_memory(&png).unwrap().into_rgba8();
+
+        for y in 0..8 {
+            for x in 0..8 {
+                let stored = stored.get_pixel(x, y).0;
+                if (2..6).contains(&x) && (2..6).contains(&y) {
+                    assert_eq!(stored, [128, 0, 128, 255], "pixel ({x}, {y})");
+                } else {
+                    assert_eq!(stored, image.pixel(x, y), "pixel ({x}, {y})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_newer_versions_and_foreign_json() {
+        let project = Project::new(sample_image(ChannelOrder::Rgba), vec![]);
+        let mut value: serde_json::Value =
+            serde_json::from_slice(&project.to_bytes().unwrap()).unwrap();
+
+        value["version"] = (PROJECT_VERSION + 1).into();
+        let newer = serde_json::to_vec(&value).unwrap();
+        assert!(matches!(
+            Project::from_bytes(&newer),
+            Err(ExportError::UnsupportedProjectVersion(v)) if v == PROJECT_VERSION + 1
+        ));
+
+        assert!(matches!(
+            Project::from_bytes(br#"{"format":"other"}"#),
+            Err(ExportError::InvalidProject(_))
+        ));
+    }
+
+    #[test]
+    fn missing_element_fields_fall_back_to_defaults() {
+        let project = Project::new(sample_image(ChannelOrder::Rgba), vec![]);
+        let mut value: serde_json::Value =
+            serde_json::from_slice(&project.to_bytes().unwrap()).unwrap();
+        value["elements"] = serde_json::json!([{ "tool": "Arrow", "thickness": 9.0 }]);
+
+        let loaded = Project::from_bytes(&serde_json::to_vec(&value).unwrap()).unwrap();
+        let element = &loaded.elements[0];
+        assert_eq!(element.tool, DrawingTool::Arrow);
+        assert_eq!(element.thickness, 9.0);
+        assert_eq!(element.font_name, sc_drawing::defaults::FONT_NAME);
+    }
+
+    #[test]
+    fn project_paths_are_detected_by_extension() {
+        assert!(is_project_path("a/shot.scproj"));
+        assert!(is_project_path("shot.SCPROJ"));
+        assert!(!is_project_path("shot.png"));
+        assert!(!is_project_path("scproj"));
+    }
+}
@@ -0,0 +1,731 @@
+//! SVG export: the capture as an embedded bitmap with every annotation as a vector element.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sc_drawing::{
+    ArrowMarker, ArrowShape, CalloutShape, Color, DrawingElement, DrawingTool, LineCap, LineJoin,
+    OutlineSegment, PixelBuffer, RedactionPatch, StrokeStyle, defaults,
+};
+
+use crate::encoder::encode_rgba;
+use crate::project::{opaque_rgba, redact};
+use crate::types::{EncodeOptions, ExportError, ImageFormat};
+
+/// File extension (without the dot) of SVG exports.
+pub const SVG_EXTENSION: &str = "svg";
+
+/// Line pitch of multi-line text, matching the on-screen text layout.
+const TEXT_LINE_HEIGHT_SCALE: f64 = 1.35;
+
+/// Render `elements` over `image` as a standalone SVG document.
+///
+/// Element coordinates are relative to the image's top-left corner, as in [`crate::Project`].
+/// Mosaic and blur areas are also baked into the embedded background, so the original pixels
+/// underneath never end up in the file.
+pub fn elements_to_svg(
+    image: &PixelBuffer,
+    elements: &[DrawingElement],
+) -> Result<String, ExportError> {
+    let (width, height) = (image.width, image.height);
+    if image.is_empty() {
+        return Err(ExportError::InvalidDimensions { width, height });
+    }
+
+    let (background, patches) = redact(image, elements);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<image x="0" y="0" width="{width}" height="{height}" href="{}"/>"#,
+        png_data_uri(&background)?
+    );
+
+    for (element, patch) in elements.iter().zip(&patches) {
+        match patch {
+            // Re-emitted in z-order so annotations drawn before the redaction stay covered.
+            Some(patch) => write_patch(&mut svg, element.tool, patch)?,
+            None if element.is_rotated() => {
+                let (cx, cy) = element.rect.center();
+                let _ = writeln!(
+                    svg,
+                    r#"<g transform="rotate({} {} {})">"#,
+                    num(element.rotation as f64),
+                    num(cx),
+                    num(cy)
+                );
+                write_element(&mut svg, element);
+                svg.push_str("</g>\n");
+            }
+            None => write_element(&mut svg, element),
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Write [`elements_to_svg`] output to `path`.
+pub fn save_svg(
+    image: &PixelBuffer,
+    elements: &[DrawingElement],
+    path: impl AsRef<Path>,
+) -> Result<(), ExportError> {
+    std::fs::write(path, elements_to_svg(image, elements)?)?;
+    Ok(())
+}
+
+/// Whether `path` names an SVG file.
+pub fn is_svg_path(path: impl AsRef<Path>) -> bool {
+    path.as_ref()
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(SVG_EXTENSION))
+}
+
+fn write_patch(
+    svg: &mut String,
+    tool: DrawingTool,
+    patch: &RedactionPatch,
+) -> Result<(), ExportError> {
+    // Mosaic cells must stay crisp if the document is scaled up.
+    let rendering = if tool == DrawingTool::Mosaic {
+        r#" image-rendering="optimizeSpeed""#
+    } else {
+        ""
+    };
+    let _ = writeln!(
+        svg,
+        r#"<image x="{}" y="{}" width="{}" height="{}"{rendering} href="{}"/>"#,
+        patch.rect.left,
+        patch.rect.top,
+        patch.rect.width(),
+        patch.rect.height(),
+        png_data_uri(&patch.pixels)?
+    );
+    Ok(())
+}
+
+fn write_element(svg: &mut String, element: &DrawingElement) {
+    let mut stroke = stroke_attrs(element.color, element.thickness);
+    let mut dashes = String::new();
+    if element.tool.uses_stroke_style() {
+        stroke.push_str(&line_style_attrs(&element.stroke));
+        dashes = dash_attrs(&element.stroke, element.thickness);
+    }
+    let interior = match element.fill {
+        Some(fill) => fill_attrs(fill),
+        None => r#" fill="none""#.to_string(),
+    };
+    match element.tool {
+        DrawingTool::Rectangle => {
+            let [p0, p1] = match element.points.as_slice() {
+                [p0, p1, ..] => [p0, p1],
+                _ => return,
+            };
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}"{interior}{stroke}{dashes}/>"#,
+                p0.x.min(p1.x),
+                p0.y.min(p1.y),
+                (p1.x - p0.x).abs(),
+                (p1.y - p0.y).abs()
+            );
+        }
+        DrawingTool::Circle => {
+            let [p0, p1] = match element.points.as_slice() {
+                [p0, p1, ..] => [p0, p1],
+                _ => return,
+            };
+            let _ = writeln!(
+                svg,
+                r#"<ellipse cx="{}" cy="{}" rx="{}" ry="{}"{interior}{stroke}{dashes}/>"#,
+                num((p0.x + p1.x) as f64 / 2.0),
+                num((p0.y + p1.y) as f64 / 2.0),
+                num((p1.x - p0.x).abs() as f64 / 2.0),
+                num((p1.y - p0.y).abs() as f64 / 2.0)
+            );
+        }
+        DrawingTool::Arrow => {
+            let Some(shape) = ArrowShape::of(element) else {
+                return;
+            };
+            let shaft = shape.shaft;
+            let _ = writeln!(svg, r#"<g fill="none"{stroke}>"#);
+            // Only the shaft is dashed, as on screen.
+            match shaft.control {
+                Some(control) => {
+                    let _ = writeln!(
+                        svg,
+                        r#"<path d="M{},{} Q{},{} {},{}"{dashes}/>"#,
+                        num(shaft.start.0),
+                        num(shaft.start.1),
+                        num(control.0),
+                        num(control.1),
+                        num(shaft.end.0),
+                        num(shaft.end.1)
+                    );
+                }
+                None => {
+                    let _ = writeln!(
+                        svg,
+                        r#"<line x1="{}" y1="{}" x2="{}" y2="{}"{dashes}/>"#,
+                        num(shaft.start.0),
+                        num(shaft.start.1),
+                        num(shaft.end.0),
+                        num(shaft.end.1)
+                    );
+                }
+            }
+            for marker in [shape.head, shape.tail].iter().flatten() {
+                write_arrow_marker(svg, marker, element.color);
+            }
+            svg.push_str("</g>\n");
+        }
+        DrawingTool::Pen | DrawingTool::Highlighter => {
+            if element.points.len() < 2 {
+                return;
+            }
+            let points = element
+                .points
+                .iter()
+                .map(|p| format!("{},{}", p.x, p.y))
+                .collect::<Vec<_>>()
+                .join(" ");
+            // Highlighters tint what is underneath instead of covering it, like on screen.
+            let highlighter = if element.tool == DrawingTool::Highlighter {
+                r#" stroke-linecap="round" stroke-linejoin="round" style="mix-blend-mode:multiply""#
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                svg,
+                r#"<polyline points="{points}" fill="none"{stroke}{dashes}{highlighter}/>"#
+            );
+        }
+        DrawingTool::Text => write_text(svg, element),
+        DrawingTool::Callout => {
+            let Some(shape) = CalloutShape::of(element) else {
+                return;
+            };
+            let mut path = format!("M{},{}", num(shape.start.0), num(shape.start.1));
+            for segment in &shape.segments {
+                match *segment {
+                    OutlineSegment::Line((x, y)) => {
+                        let _ = write!(path, " L{},{}", num(x), num(y));
+                    }
+                    OutlineSegment::Curve { control, to } => {
+                        let _ = write!(
+                            path,
+                            " Q{},{} {},{}",
+                            num(control.0),
+                            num(control.1),
+                            num(to.0),
+                            num(to.1)
+                        );
+                    }
+                }
+            }
+            let _ = writeln!(svg, r#"<path d="{path} Z"{interior}{stroke}{dashes}/>"#);
+            write_text(svg, element);
+        }
+        DrawingTool::Step => {
+            let Some(center) = element.points.first() else {
+                return;
+            };
+            let radius = defaults::STEP_RADIUS as f64;
+            let label = element.step_number.to_string();
+            // Same shrink rule as the screen renderer for multi-digit numbers.
+            let font_size = radius * if label.len() > 2 { 0.9 } else { 1.2 };
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}"{}/>"#,
+                center.x,
+                center.y,
+                num(radius),
+                fill_attrs(element.color)
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="{}" font-size="{}" font-weight="bold" fill="rgb(255,255,255)" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                center.x,
+                center.y,
+                escape(&element.font_name),
+                num(font_size),
+                label
+            );
+        }
+        DrawingTool::None | DrawingTool::Mosaic | DrawingTool::Blur => {}
+    }
+}
+
+fn write_text(svg: &mut String, element: &DrawingElement) {
+    if element.text.is_empty() {
+        return;
+    }
+
+    let font_size = element.get_effective_font_size() as f64;
+    let padding = text_padding(font_size);
+    let x = element.rect.left as f64 + padding;
+    let first_baseline = element.rect.top as f64 + padding + font_size;
+
+    let mut attrs = format!(
+        r#" font-family="{}" font-size="{}" font-weight="{}""#,
+        escape(&element.font_name),
+        num(font_size),
+        element.font_weight
+    );
+    if element.font_italic {
+        attrs.push_str(r#" font-style="italic""#);
+    }
+    let decoration = match (element.font_underline, element.font_strikeout) {
+        (true, true) => "underline line-through",
+        (true, false) => "underline",
+        (false, true) => "line-through",
+        (false, false) => "",
+    };
+    if !decoration.is_empty() {
+        let _ = write!(attrs, r#" text-decoration="{decoration}""#);
+    }
+
+    let _ = write!(
+        svg,
+        r#"<text xml:space="preserve"{attrs}{}>"#,
+        fill_attrs(element.color)
+    );
+    for (i, line) in element.text.lines().enumerate() {
+        let _ = write!(
+            svg,
+            r#"<tspan x="{}" y="{}">{}</tspan>"#,
+            num(x),
+            num(first_baseline + i as f64 * font_size * TEXT_LINE_HEIGHT_SCALE),
+            escape(line)
+        );
+    }
+    svg.push_str("</text>\n");
+}
+
+/// One arrow marker, inside the arrow's `<g>` that carries the stroke.
+fn write_arrow_marker(svg: &mut String, marker: &ArrowMarker, color: Color) {
+    let points = |points: &[(f64, f64)]| {
+        points
+            .iter()
+            .map(|(x, y)| format!("{},{}", num(*x), num(*y)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let _ = match marker {
+        ArrowMarker::Open(wings) => {
+            writeln!(svg, r#"<polyline points="{}"/>"#, points(wings))
+        }
+        ArrowMarker::Triangle(corners) => writeln!(
+            svg,
+            r#"<polygon points="{}"{} stroke="none"/>"#,
+            points(corners),
+            fill_attrs(color)
+        ),
+        ArrowMarker::Dot { center, radius } => writeln!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="{}"{} stroke="none"/>"#,
+            num(center.0),
+            num(center.1),
+            num(*radius),
+            fill_attrs(color)
+        ),
+        ArrowMarker::Bar([from, to]) => writeln!(
+            svg,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}"/>"#,
+            num(from.0),
+            num(from.1),
+            num(to.0),
+            num(to.1)
+        ),
+    };
+}
+
+/// Text inset inside its box; mirrors the renderer's font-size-relative padding.
+fn text_padding(font_size: f64) -> f64 {
+    (font_size * 0.4).clamp(2.0, 24.0)
+}
+
+fn stroke_attrs(color: Color, thickness: f32) -> String {
+    let mut attrs = format!(
+        r#" stroke="{}" stroke-width="{}""#,
+        rgb(color),
+        num(thickness as f64)
+    );
+    if color.a < 1.0 {
+        let _ = write!(attrs, r#" stroke-opacity="{}""#, num(color.a as f64));
+    }
+    attrs
+}
+
+/// Caps and joins that differ from SVG's butt caps and mitred corners.
+fn line_style_attrs(style: &StrokeStyle) -> String {
+    let mut attrs = String::new();
+    match style.cap {
+        LineCap::Flat => {}
+        LineCap::Round => attrs.push_str(r#" stroke-linecap="round""#),
+        LineCap::Square => attrs.push_str(r#" stroke-linecap="square""#),
+    }
+    match style.join {
+        LineJoin::Miter => {}
+        LineJoin::Round => attrs.push_str(r#" stroke-linejoin="round""#),
+        LineJoin::Bevel => attrs.push_str(r#" stroke-linejoin="bevel""#),
+    }
+    attrs
+}
+
+/// Dash pattern scaled from stroke widths to pixels; empty for solid lines.
+fn dash_attrs(style: &StrokeStyle, thickness: f32) -> String {
+    let Some(lengths) = style.dash_array() else {
+        return String::new();
+    };
+    let lengths: Vec<String> = lengths
+        .iter()
+        .map(|length| num((length * thickness) as f64))
+        .collect();
+    format!(r#" stroke-dasharray="{}""#, lengths.join(" "))
+}
+
+fn fill_attrs(color: Color) -> String {
+    let mut attrs = format!(r#" fill="{}""#, rgb(color));
+    if color.a < 1.0 {
+        let _ = write!(attrs, r#" fill-opacity="{}""#, num(color.a as f64));
+    }
+    attrs
+}
+
+fn rgb(color: Color) -> String {
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "rgb({},{},{})",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
+
+/// Shortest decimal form with at most two fractional digits.
+fn num(value: f64) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string()
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn png_data_uri(image: &PixelBuffer) -> Result<String, ExportError> {
+    let png = encode_rgba(
+        &opaque_rgba(image),
+        image.width,
+        image.height,
+        ImageFormat::Png,
+        &EncodeOptions::default(),
+    )?;
+    Ok(format!("data:image/png;base64,{}", BASE64.encode(png)))
+}
+
+#[cfg(test)]
+mod tests {
+    use resvg::tiny_skia::Pixmap;
+    use resvg::usvg::{self, Node};
+    use sc_drawing::{ArrowHead, ArrowStyle, ChannelOrder, Point};
+
+    use super::*;
+
+    fn white_image(width: u32, height: u32) -> PixelBuffer {
+        PixelBuffer::from_raw(
+            width,
+            height,
+            ChannelOrder::Rgba,
+            vec![255; (width * height * 4) as usize],
+        )
+        .unwrap()
+    }
+
+    fn shape(tool: DrawingTool, points: &[(i32, i32)], color: Color) -> DrawingElement {
+        let mut element = DrawingElement::with_color(tool, color);
+        element.points = points.iter().map(|&(x, y)| Point::new(x, y)).collect();
+        element.thickness = 4.0;
+        element.update_bounding_rect();
+        element
+    }
+
+    fn parse(svg: &str) -> usvg::Tree {
+        usvg::Tree::from_str(svg, &usvg::Options::default()).expect("exported SVG must parse")
+    }
+
+    fn render(tree: &usvg::Tree) -> Pixmap {
+        let size = tree.size().to_int_size();
+        let mut pixmap = Pixmap::new(size.width(), size.height()).unwrap();
+        resvg::render(tree, Default::default(), &mut pixmap.as_mut());
+        pixmap
+    }
+
+    fn rgb_at(pixmap: &Pixmap, x: u32, y: u32) -> [u8; 3] {
+        let c = pixmap.pixel(x, y).unwrap().demultiply();
+        [c.red(), c.green(), c.blue()]
+    }
+
+    /// (paths, images) below `group`, descending into nested groups.
+    fn count_nodes(group: &usvg::Group) -> (usize, usize) {
+        group
+            .children()
+            .iter()
+            .fold((0, 0), |(paths, images), node| match node {
+                Node::Path(_) => (paths + 1, images),
+                Node::Image(_) => (paths, images + 1),
+                Node::Group(group) => {
+                    let (p, i) = count_nodes(group);
+                    (paths + p, images + i)
+                }
+                Node::Text(_) => (paths, images),
+            })
+    }
+
+    #[test]
+    fn shapes_become_vector_paths() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0);
+        let elements = vec![
+            shape(DrawingTool::Rectangle, &[(10, 10), (50, 40)], red),
+            shape(DrawingTool::Circle, &[(60, 10), (90, 40)], red),
+            shape(DrawingTool::Arrow, &[(10, 60), (90, 60)], red),
+            shape(DrawingTool::Pen, &[(10, 80), (30, 85), (50, 80)], red),
+        ];
+
+        let svg = elements_to_svg(&white_image(100, 100), &elements).unwrap();
+        assert!(svg.contains("<rect x=\"10\" y=\"10\" width=\"40\" height=\"30\""));
+        assert!(svg.contains("<ellipse cx=\"75\" cy=\"25\" rx=\"15\" ry=\"15\""));
+        assert!(svg.contains("<polyline points=\"10,80 30,85 50,80\""));
+
+        // Rectangle, ellipse, arrow shaft and head, pen stroke; plus the background image.
+        let tree = parse(&svg);
+        assert_eq!(count_nodes(tree.root()), (5, 1));
+    }
+
+    #[test]
+    fn rendered_output_matches_element_geometry() {
+        let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+        let elements = vec![shape(DrawingTool::Rectangle, &[(10, 10), (30, 30)], blue)];
+
+        let pixmap = render(&parse(
+            &elements_to_svg(&white_image(40, 40), &elements).unwrap(),
+        ));
+        assert_eq!(rgb_at(&pixmap, 10, 20), [0, 0, 255]);
+        assert_eq!(rgb_at(&pixmap, 20, 30), [0, 0, 255]);
+        assert_eq!(rgb_at(&pixmap, 20, 20), [255, 255, 255]);
+        assert_eq!(rgb_at(&pixmap, 2, 2), [255, 255, 255]);
+    }
+
+    #[test]
+    fn rotated_elements_turn_about_their_centre() {
+        let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+        let mut bar = shape(DrawingTool::Rectangle, &[(10, 40), (90, 60)], blue);
+        bar.rotation = 90.0;
+
+        let svg = elements_to_svg(&white_image(100, 100), &[bar]).unwrap();
+        assert!(svg.contains(r#"<g transform="rotate(90 50 50)">"#));
+        let pixmap = render(&parse(&svg));
+        // The left edge now runs along the top, and nothing is left where it used to be.
+        assert_eq!(rgb_at(&pixmap, 50, 10), [0, 0, 255]);
+        assert_eq!(rgb_at(&pixmap, 10, 50), [255, 255, 255]);
+    }
+
+    #[test]
+    fn filled_shapes_paint_their_interior() {
+        let blue = Color::new(0.0, 0.0, 1.0, 1.0);
+        let mut rect = shape(DrawingTool::Rectangle, &[(10, 10), (30, 30)], blue);
+        rect.fill = Some(blue.with_alpha(0.5));
+        let mut ellipse = shape(DrawingTool::Circle, &[(40, 10), (60, 30)], blue);
+        ellipse.fill = Some(Color::new(1.0, 0.0, 0.0, 1.0));
+
+        let svg = elements_to_svg(&white_image(70, 40), &[rect, ellipse]).unwrap();
+        assert!(svg.contains(r#"fill="rgb(0,0,255)" fill-opacity="0.5""#));
+        let pixmap = render(&parse(&svg));
+        assert_eq!(rgb_at(&pixmap, 20, 20), [127, 127, 255]);
+        assert_eq!(rgb_at(&pixmap, 50, 20), [255, 0, 0]);
+    }
+
+    #[test]
+    fn stroke_styles_become_dash_cap_and_join_attributes() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0);
+        let mut rect = shape(DrawingTool::Rectangle, &[(10, 10), (50, 40)], red);
+        rect.stroke = StrokeStyle {
+            dash: sc_drawing::DashStyle::Dash,
+            cap: LineCap::Round,
+            join: LineJoin::Bevel,
+        };
+        let mut arrow = shape(DrawingTool::Arrow, &[(10, 60), (90, 60)], red);
+        arrow.stroke.dash = sc_drawing::DashStyle::Dot;
+        let pen = shape(DrawingTool::Pen, &[(10, 80), (30, 85), (50, 80)], red);
+
+        let svg = elements_to_svg(&white_image(100, 100), &[rect, arrow, pen]).unwrap();
+        // Round caps eat into the gaps, so the 3:2 dash becomes 2:3 widths of 4 px.
+        assert!(svg.contains(
+            r#"stroke-linecap="round" stroke-linejoin="bevel" stroke-dasharray="8 12"/>"#
+        ));
+        assert!(svg.contains(r#"y2="60" stroke-dasharray="4 4"/>"#));
+        assert_eq!(svg.matches("stroke-dasharray").count(), 2);
+        assert!(!svg.contains("stroke-linecap=\"round\" stroke-linejoin=\"round\""));
+        parse(&svg);
+    }
+
+    #[test]
+    fn short_arrows_have_no_head() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0);
+        let short = shape(DrawingTool::Arrow, &[(10, 10), (20, 10)], red);
+        let svg = elements_to_svg(&white_image(40, 40), &[short]).unwrap();
+        assert_eq!(count_nodes(parse(&svg).root()), (1, 1));
+    }
+
+    #[test]
+    fn arrow_variants_export_their_markers_and_curve() {
+        let red = Color::new(1.0, 0.0, 0.0, 1.0);
+        let mut arrow = shape(DrawingTool::Arrow, &[(10, 10), (90, 10)], red);
+        arrow.arrow = ArrowStyle {
+            head: ArrowHead::Triangle,
+            tail: ArrowHead::Dot,
+        };
+        arrow.add_point(50, 30);
+        let mut barred = shape(DrawingTool::Arrow, &[(10, 60), (90, 60)], red);
+        barred.arrow = ArrowStyle {
+            head: ArrowHead::Open,
+            tail: ArrowHead::Bar,
+        };
+
+        let svg = elements_to_svg(&white_image(100, 100), &[arrow, barred]).unwrap();
+        assert!(svg.contains(r#"<path d="M10,10 Q"#));
+        assert!(svg.contains("<polygon points="));
+        assert!(svg.contains(r#"<circle cx="10" cy="10""#));
+        assert!(svg.contains(r#"<line x1="10" y1="60" x2="90" y2="60"/>"#));
+        assert!(svg.contains("<polyline points="));
+        parse(&svg);
+    }
+
+    #[test]
+    fn callout_is_one_outline_with_its_text() {
+        let mut callout = shape(
+            DrawingTool::Callout,
+            &[(10, 10), (90, 40), (20, 80)],
+            Color::new(1.0, 0.0, 0.0, 1.0),
+        );
+        callout.fill = Some(Color::rgb(0.0, 0.0, 1.0));
+        callout.text = "look".to_string();
+
+        let svg = elements_to_svg(&white_image(100, 100), &[callout]).unwrap();
+        assert!(svg.contains(r#"<path d="M18,10 L82,10 Q90,10 90,18"#));
+        assert!(svg.contains(r#" Z" fill="rgb(0,0,255)""#));
+        assert!(svg.contains(">look</tspan>"));
+
+        // The tail is filled along with the box; the space beside it is not.
+        let pixmap = render(&parse(&svg));
+        assert_eq!(rgb_at(&pixmap, 24, 50), [0, 0, 255]);
+        assert_eq!(rgb_at(&pixmap, 60, 60), [255, 255, 255]);
+    }
+
+    #[test]
+    fn text_keeps_font_attributes_and_escapes_content() {
+        let mut text = shape(DrawingTool::Text, &[(5, 5)], Color::new(0.0, 0.5, 0.0, 0.5));
+        text.text = "a < b & c\nsecond".to_string();
+        text.font_name = "Consolas".to_string();
+        text.font_size = 24.0;
+        text.font_weight = 700;
+        text.font_italic = true;
+        text.font_underline = true;
+        text.font_strikeout = true;
+        text.update_bounding_rect();
+
+        let svg = elements_to_svg(&white_image(200, 100), &[text]).unwrap();
+        assert!(svg.contains(r#"font-family="Consolas""#));
+        assert!(svg.contains(r#"font-size="24""#));
+        assert!(svg.contains(r#"font-weight="700""#));
+        assert!(svg.contains(r#"font-style="italic""#));
+        assert!(svg.contains(r#"text-decoration="underline line-through""#));
+        assert!(svg.contains(r#"fill="rgb(0,128,0)" fill-opacity="0.5""#));
+        assert!(svg.contains(">a &lt; b &amp; c</tspan>"));
+        assert_eq!(svg.matches("<tspan").count(), 2);
+        parse(&svg);
+    }
+
+    #[test]
+    fn redacted_pixels_never_reach_the_document() {
+        // Checkerboard whose 2x2 mosaic average is a flat purple.
+        let mut image = PixelBuffer::new(8, 8, ChannelOrder::Bgra);
+        for y in 0..8 {
+            for x in 0..8 {
+                let v = if (x + y) % 2 == 0 { 255 } else { 0 };
+                image.set_pixel(x, y, [v, 0, 255 - v, 255]);
+            }
+        }
+        let mut mosaic = shape(
+            DrawingTool::Mosaic,
+            &[(0, 0), (8, 8)],
+            Color::new(0.0, 0.0, 0.0, 1.0),
+        );
+        mosaic.block_size = 2;
+
+        let svg = elements_to_svg(&image, &[mosaic]).unwrap();
+        let tree = parse(&svg);
+        assert_eq!(count_nodes(tree.root()), (0, 2));
+
+        // Drop the patch: the background alone must already be redacted.
+        let background_only = svg
+            .lines()
+            .filter(|line| {
+                !line.starts_with("<image x=\"0\" y=\"0\" width=\"8\" height=\"8\" image-rendering")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let pixmap = render(&parse(&background_only));
+        for (x, y) in [(0, 0), (1, 0), (5, 6)] {
+            assert_eq!(rgb_at(&pixmap, x, y), [128, 0, 128]);
+        }
+    }
+
+    #[test]
+    fn step_markers_are_filled_circles_with_a_label() {
+        let mut step = shape(
+            DrawingTool::Step,
+            &[(30, 30)],
+            Color::new(1.0, 0.0, 0.0, 1.0),
+        );
+        step.step_number = 12;
+
+        let svg = elements_to_svg(&white_image(60, 60), &[step]).unwrap();
+        assert!(svg.contains(r#"<circle cx="30" cy="30" r="14" fill="rgb(255,0,0)"/>"#));
+        assert!(svg.contains(">12</text>"));
+
+        let pixmap = render(&parse(&svg));
+        assert_eq!(rgb_at(&pixmap, 30, 18), [255, 0, 0]);
+    }
+
+    #[test]
+    fn svg_paths_are_detected_by_extension() {
+        assert!(is_svg_path("out/shot.svg"));
+        assert!(is_svg_path("shot.SVG"));
+        assert!(!is_svg_path("shot.png"));
+    }
+}
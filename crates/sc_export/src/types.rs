@@ -0,0 +1,89 @@
+use std::path::Path;
+
+/// Output image format, chosen from the target file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ImageFormat {
+    #[default]
+    Png,
+    Jpeg,
+    WebP,
+    Bmp,
+    Tiff,
+}
+
+impl ImageFormat {
+    /// Map a file extension (case-insensitive, without the dot) to a format.
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_ascii_lowercase().as_str() {
+            "png" => Some(Self::Png),
+            "jpg" | "jpeg" => Some(Self::Jpeg),
+            "webp" => Some(Self::WebP),
+            "bmp" => Some(Self::Bmp),
+            "tif" | "tiff" => Some(Self::Tiff),
+            _ => None,
+        }
+    }
+
+    /// Map a file path to a format by its extension.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        path.as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Preferred file extension (without the dot).
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::WebP => "webp",
+            Self::Bmp => "bmp",
+            Self::Tiff => "tiff",
+        }
+    }
+}
+
+/// Host-provided encoder settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeOptions {
+    /// PNG compression level (0 = uncompressed, 1..=9 = zlib level).
+    pub png_compression: u8,
+    /// JPEG quality (1..=100).
+    pub jpeg_quality: u8,
+    /// WebP quality (1..=99 lossy, 100 = lossless). Lossy needs the `lossy-webp` feature;
+    /// without it WebP is always lossless.
+    pub webp_quality: u8,
+}
+
+impl Default for EncodeOptions {
+    /// Same values as the defaults of the export settings.
+    fn default() -> Self {
+        Self {
+            png_compression: 6,
+            jpeg_quality: 90,
+            webp_quality: 90,
+        }
+    }
+}
+
+/// Export errors.
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error("无效的图像尺寸: {width}x{height}")]
+    InvalidDimensions { width: u32, height: u32 },
+    #[error("图像解码失败: {0}")]
+    Decode(String),
+    #[error("图像编码失败: {0}")]
+    Encode(String),
+    #[error("不支持的图片格式: {0}")]
+    UnsupportedFormat(String),
+    #[error("文件名缺少扩展名")]
+    MissingExtension,
+    #[error("写入文件失败: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("无效的工程文件: {0}")]
+    InvalidProject(String),
+    #[error("不支持的工程文件版本: {0}")]
+    UnsupportedProjectVersion(u32),
+}
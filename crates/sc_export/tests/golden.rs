@@ -0,0 +1,96 @@
+//! Golden image for annotation rendering through the SVG export path.
+//! Regenerate with `SC_UPDATE_GOLDEN=1`.
+
+use std::path::PathBuf;
+
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg;
+use sc_drawing::{ChannelOrder, Color, DrawingElement, DrawingTool, PixelBuffer, Point};
+use sc_export::elements_to_svg;
+use sc_rendering::golden::{GoldenOptions, assert_golden};
+
+fn golden(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.png"))
+}
+
+/// Diagonal colour ramp with a fine stripe pattern, so redactions visibly change it.
+fn backdrop(width: u32, height: u32) -> PixelBuffer {
+    let mut image = PixelBuffer::new(width, height, ChannelOrder::Rgba);
+    for y in 0..height {
+        for x in 0..width {
+            let stripe = if (x / 3 + y / 3) % 2 == 0 { 40 } else { 0 };
+            image.set_pixel(
+                x,
+                y,
+                [
+                    (140 + x * 70 / width + stripe) as u8,
+                    (170 + y * 60 / height) as u8,
+                    (200 - stripe) as u8,
+                    255,
+                ],
+            );
+        }
+    }
+    image
+}
+
+fn element(
+    tool: DrawingTool,
+    points: &[(i32, i32)],
+    color: Color,
+    thickness: f32,
+) -> DrawingElement {
+    let mut element = DrawingElement::with_color(tool, color);
+    element.points = points.iter().map(|&(x, y)| Point::new(x, y)).collect();
+    element.thickness = thickness;
+    element.update_bounding_rect();
+    element
+}
+
+#[test]
+fn annotation_elements() {
+    let red = Color::new(0.9, 0.1, 0.1, 1.0);
+    let blue = Color::new(0.1, 0.3, 0.9, 1.0);
+
+    let mut mosaic = element(DrawingTool::Mosaic, &[(200, 20), (260, 70)], red, 1.0);
+    mosaic.block_size = 8;
+    let mut blur = element(DrawingTool::Blur, &[(200, 90), (260, 140)], red, 1.0);
+    blur.blur_radius = 6.0;
+    let mut step = element(DrawingTool::Step, &[(285, 40)], blue, 1.0);
+    step.step_number = 1;
+
+    let elements = vec![
+        element(DrawingTool::Rectangle, &[(20, 20), (90, 70)], red, 3.0),
+        element(DrawingTool::Circle, &[(110, 20), (180, 70)], blue, 2.0),
+        element(DrawingTool::Arrow, &[(20, 130), (170, 95)], red, 3.0),
+        element(
+            DrawingTool::Pen,
+            &[(20, 150), (50, 140), (80, 155), (110, 140), (140, 152)],
+            blue,
+            2.0,
+        ),
+        element(
+            DrawingTool::Highlighter,
+            &[(150, 150), (290, 150)],
+            Color::new(1.0, 0.92, 0.23, 110.0 / 255.0),
+            16.0,
+        ),
+        mosaic,
+        blur,
+        step,
+    ];
+
+    let svg = elements_to_svg(&backdrop(300, 170), &elements).unwrap();
+    // Default options carry no fonts, so text is left out and the image is machine-independent.
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    let mut image = Pixmap::new(300, 170).unwrap();
+    resvg::render(&tree, Transform::identity(), &mut image.as_mut());
+
+    assert_golden(
+        &image,
+        golden("annotation_elements"),
+        &GoldenOptions::default(),
+    );
+}
@@ -1,5 +1,7 @@
 use sc_app::selection::RectI32;
-use sc_drawing::{DrawingElement, DrawingTool};
+use sc_drawing::{
+    Arrangement, ArrowStyle, Color, DrawingElement, DrawingTool, StrokeStyle, ZOrder,
+};
 
 /// Drawing manager messages.
 #[derive(Debug, Clone, PartialEq)]
@@ -18,12 +20,35 @@ pub enum DrawingMessage {
     Undo,
     /// Redo.
     Redo,
-    /// Delete element.
-    DeleteElement(usize),
-    /// Select element.
-    SelectElement(Option<usize>),
+    /// Delete the element with this id.
+    DeleteElement(u64),
+    /// Select the element with this id, or clear the selection.
+    SelectElement(Option<u64>),
     /// Check element click.
     CheckElementClick(i32, i32),
+    /// Group the selected elements so they move, copy and delete together.
+    GroupSelected,
+    /// Break up the groups the selected elements belong to.
+    UngroupSelected,
+    /// Move the selected elements in the stacking order.
+    ChangeZOrder(ZOrder),
+    /// Align or distribute the selected elements.
+    Arrange(Arrangement),
+    /// Fill the selected shapes with this colour, or make them outline-only.
+    SetFill(Option<Color>),
+    /// Give the selected arrows these head and tail markers.
+    SetArrowStyle(ArrowStyle),
+    /// Give the selected outlined shapes this stroke style.
+    SetStrokeStyle(StrokeStyle),
+    /// Put a marker on both ends of the selected arrows, or take the tail marker off if they
+    /// all have one.
+    ToggleDoubleArrow,
+    /// Put the selected elements on the annotation clipboard.
+    CopySelected,
+    /// Insert copies of the annotation clipboard contents.
+    Paste,
+    /// Insert offset copies of the selected elements.
+    DuplicateSelected,
 }
 
 /// UI manager messages.
@@ -58,6 +83,10 @@ pub enum Command {
 
     /// Take screenshot.
     TakeScreenshot,
+    /// Show or update the delayed-capture countdown (seconds left).
+    ShowCaptureCountdown(u32),
+    /// Remove the delayed-capture countdown.
+    HideCaptureCountdown,
 
     /// Start automatic scrolling capture for the current selection.
     StartScrollingCapture,
@@ -69,8 +98,12 @@ pub enum Command {
 
     /// Save selection to file.
     SaveSelectionToFile,
+    /// Pick an annotation project file and reopen it in the preview editor.
+    OpenProject,
     /// Save selection to clipboard.
     SaveSelectionToClipboard,
+    /// Copy selection to clipboard and keep the capture open (auto-copy).
+    CopySelectionToClipboard,
 
     /// Pin selection.
     PinSelection,
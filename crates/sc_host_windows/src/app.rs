@@ -1,6 +1,4 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::Write;
 use std::sync::atomic::AtomicI8;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -16,15 +14,20 @@ use crate::scroll_capture::{ScrollCaptureEvent, ScrollCaptureWorker, ScrollFrame
 use crate::system::{SystemError, SystemManager};
 use sc_app::AppModel;
 use sc_app::{Action as CoreAction, selection as core_selection};
-use sc_drawing::Rect;
+use sc_drawing::{
+    ArrowStyle, CursorSource, DrawingElement, PixelBuffer, Point, Rect, RedactionSource,
+    StrokeStyle,
+};
 use sc_drawing_host::{DrawingConfig, DrawingError, DrawingManager, DrawingTool};
+use sc_export::EncodeOptions;
 use sc_host_protocol::{Command, DrawingMessage, UIMessage};
 use sc_ocr::{OcrCompletionData, OcrResult};
 use sc_platform::{
-    Color, HostPlatform, InputEvent, KeyCode, MouseButton, PlatformError, PlatformServicesError,
-    WindowEvent,
+    Color, HostPlatform, InputEvent, KeyCode, Modifiers, MouseButton, PlatformError,
+    PlatformServicesError, WindowEvent,
 };
 use sc_platform_windows::windows::bmp::crop_bmp;
+use sc_platform_windows::windows::cursor::WindowsCursorSource;
 use sc_platform_windows::windows::graphics_capture::GraphicsCaptureSource;
 use sc_platform_windows::windows::{Direct2DRenderer, UserEventSender};
 use sc_rendering::DirtyRectTracker;
@@ -52,6 +55,8 @@ pub struct App {
 
     /// Host-facing platform side effects (window ops, timers, clipboard, dialogs, etc.).
     host_platform: Box<dyn HostPlatform<WindowHandle = WindowId>>,
+    /// Reads the pointer for captures when `show_cursor` is enabled.
+    cursor_source: Box<dyn CursorSource>,
 
     platform: Direct2DRenderer,
     screen_size: (i32, i32),
@@ -93,13 +98,14 @@ impl App {
         let drawing_config = Self::drawing_config_from_settings(&config.get());
 
         Ok(Self {
-            core: AppModel::new(),
+            core: Self::new_core_model(&config),
             config,
             screenshot,
             drawing: DrawingManager::new(drawing_config)?,
             ui: UIManager::new()?,
             system: SystemManager::new(shared_settings, events)?,
             host_platform,
+            cursor_source: Box::new(WindowsCursorSource),
             platform,
             screen_size,
             dirty_tracker,
@@ -117,6 +123,13 @@ impl App {
         })
     }
 
+    /// Fresh core model carrying the settings it needs.
+    fn new_core_model(config: &ConfigManager) -> AppModel {
+        let mut core = AppModel::new();
+        core.set_auto_copy(config.auto_copy());
+        core
+    }
+
     pub fn get_screen_size(&self) -> (i32, i32) {
         self.screen_size
     }
@@ -139,7 +152,7 @@ impl App {
         // Zed-style: host state is explicit; core state is a pure model we can reset.
         self.dirty_tracker.mark_full_redraw();
 
-        self.core = AppModel::new();
+        self.core = Self::new_core_model(&self.config);
 
         let screen_size = self.update_screen_size_cache();
 
@@ -316,6 +329,7 @@ impl App {
 
     pub fn reload_settings(&mut self) -> Vec<Command> {
         self.config.reload();
+        self.core.set_auto_copy(self.config.auto_copy());
 
         self.system.reload_settings();
 
@@ -349,6 +363,28 @@ impl App {
             font_underline: settings.font_underline,
             font_strikeout: settings.font_strikeout,
             font_color: settings.font_color,
+            mosaic_block_size: settings.mosaic_block_size,
+            blur_radius: settings.blur_radius,
+            highlighter_width: settings.highlighter_width,
+            highlighter_color: settings.highlighter_color,
+            highlighter_opacity: settings.highlighter_opacity,
+            fill_color: settings.fill_color,
+            fill_opacity: settings.fill_opacity,
+            stroke_style: StrokeStyle::parse(
+                &settings.stroke_dash,
+                &settings.stroke_cap,
+                &settings.stroke_join,
+            ),
+            arrow_style: ArrowStyle::parse(&settings.arrow_head, &settings.arrow_tail),
+        }
+    }
+
+    /// Encoder settings chosen in the settings window.
+    fn export_options(&self) -> EncodeOptions {
+        EncodeOptions {
+            png_compression: self.config.png_compression(),
+            jpeg_quality: self.config.jpeg_quality(),
+            webp_quality: self.config.webp_quality(),
         }
     }
 
@@ -392,7 +428,7 @@ impl App {
         commands
     }
 
-    pub fn handle_key_input(&mut self, key: u32) -> Vec<Command> {
+    pub fn handle_key_input(&mut self, key: u32, modifiers: Modifiers) -> Vec<Command> {
         if key == KeyCode::ESCAPE.0 {
             return vec![Command::Core(sc_app::Action::Cancel)];
         }
@@ -406,7 +442,7 @@ impl App {
             core_selection::Phase::Editing { .. } => {
                 let mut commands = self.system.handle_key_input(key);
                 if commands.is_empty() {
-                    commands = self.drawing.handle_key_input(key);
+                    commands = self.drawing.handle_key_input(key, modifiers);
                 }
                 if commands.is_empty() {
                     commands = self.ui.handle_key_input(key);
@@ -422,17 +458,31 @@ impl App {
     }
 
     pub fn capture_screen_to_d2d_bitmap(&mut self) -> AppResult<()> {
+        let cursor = if self.config.show_cursor() {
+            self.cursor_source.current_cursor()
+        } else {
+            None
+        };
         self.screenshot
-            .capture_screen_to_d2d_bitmap(&mut self.platform)
-            .map_err(|e| AppError::Render(format!("Failed to create D2D bitmap: {e:?}")))
+            .capture_screen_to_d2d_bitmap(&mut self.platform, cursor.as_ref())
+            .map_err(|e| AppError::Render(format!("Failed to create D2D bitmap: {e:?}")))?;
+
+        // Redaction regions sample the capture itself, so composed output never sees the originals.
+        let source = self
+            .screenshot
+            .get_current_image_data()
+            .and_then(PixelBuffer::from_bmp)
+            .map(|pixels| RedactionSource::unscaled(Arc::new(pixels)));
+        self.drawing.set_redaction_source(source);
+        Ok(())
     }
 
-    pub fn handle_mouse_move(&mut self, x: i32, y: i32) -> Vec<Command> {
+    pub fn handle_mouse_move(&mut self, x: i32, y: i32, modifiers: Modifiers) -> Vec<Command> {
         let phase = self.core.selection().phase().clone();
         let hover_selection = self.core.selection().hover_selection();
         let commands = match phase {
             core_selection::Phase::Editing { .. } => {
-                self.handle_mouse_move_editing(x, y, hover_selection)
+                self.handle_mouse_move_editing(x, y, modifiers, hover_selection)
             }
 
             core_selection::Phase::Idle | core_selection::Phase::Selecting { .. } => {
@@ -492,7 +542,7 @@ impl App {
         commands
     }
 
-    pub fn handle_mouse_down(&mut self, x: i32, y: i32) -> Vec<Command> {
+    pub fn handle_mouse_down(&mut self, x: i32, y: i32, modifiers: Modifiers) -> Vec<Command> {
         let phase = self.core.selection().phase().clone();
         let has_auto_highlight = self.core.selection().has_auto_highlight()
             && self.core.selection().hover_selection().is_some();
@@ -527,7 +577,7 @@ impl App {
             }
 
             core_selection::Phase::Editing { .. } => {
-                self.handle_mouse_down_editing(x, y, has_auto_highlight)
+                self.handle_mouse_down_editing(x, y, modifiers, has_auto_highlight)
             }
         }
     }
@@ -558,6 +608,7 @@ impl App {
         &mut self,
         x: i32,
         y: i32,
+        modifiers: Modifiers,
         hover_selection: Option<core_selection::RectI32>,
     ) -> Vec<Command> {
         let mut commands = Vec::new();
@@ -572,7 +623,8 @@ impl App {
         if !ui_consumed {
             let selection_rect = self.confirmed_selection_rect().map(Into::into);
             let (drawing_commands, drawing_consumed) =
-                self.drawing.handle_mouse_move(x, y, selection_rect);
+                self.drawing
+                    .handle_mouse_move(x, y, selection_rect, modifiers);
             commands.extend(drawing_commands);
 
             if !drawing_consumed && !self.drawing.is_dragging() {
@@ -605,6 +657,7 @@ impl App {
         &mut self,
         x: i32,
         y: i32,
+        modifiers: Modifiers,
         has_auto_highlight: bool,
     ) -> Vec<Command> {
         let mut commands = Vec::new();
@@ -619,7 +672,8 @@ impl App {
         if !ui_consumed {
             let selection_rect = self.confirmed_selection_rect().map(Into::into);
             let (drawing_commands, drawing_consumed) =
-                self.drawing.handle_mouse_down(x, y, selection_rect);
+                self.drawing
+                    .handle_mouse_down(x, y, selection_rect, modifiers);
             commands.extend(drawing_commands);
 
             if !drawing_consumed {
@@ -721,6 +775,21 @@ impl App {
         Ok(())
     }
 
+    /// Route a capture request through the delayed-capture countdown.
+    ///
+    /// Returns `false` when no delay is configured and nothing is counting down, in which case
+    /// the caller captures right away.
+    fn start_capture_countdown(&mut self, window: WindowId) -> bool {
+        let delay_ms = self.config.delay_ms();
+        if delay_ms == 0 && !self.core.capture_delay().is_counting_down() {
+            return false;
+        }
+
+        let commands = self.dispatch_core_action(CoreAction::StartCaptureCountdown { delay_ms });
+        self.execute_command_chain(commands, window);
+        true
+    }
+
     pub fn capture_screen_direct(&mut self) -> AppResult<()> {
         let screen_size = self.update_screen_size_cache();
 
@@ -1047,14 +1116,13 @@ impl App {
     pub(crate) fn save_scrolling_to_file(&mut self, window: WindowId) -> AppResult<bool> {
         let Some(path) = self
             .host_platform
-            .show_image_save_dialog(window, "scroll-capture.bmp")
+            .show_image_save_dialog(window, "scroll-capture.png")
             .map_err(|e| AppError::Platform(e.to_string()))?
         else {
             return Ok(false);
         };
         let bmp = self.scrolling_bmp()?;
-        let mut file = File::create(path).map_err(|e| AppError::File(e.to_string()))?;
-        file.write_all(&bmp)
+        sc_export::save_bmp_to_file(&bmp, path, &self.export_options())
             .map_err(|e| AppError::File(e.to_string()))?;
         Ok(true)
     }
@@ -1093,16 +1161,79 @@ impl App {
             return Ok(false);
         };
 
+        if sc_export::is_project_path(&file_path) {
+            self.save_selection_as_project(selection_rect, &file_path)?;
+            return Ok(true);
+        }
+
+        if sc_export::is_svg_path(&file_path) {
+            self.save_selection_as_svg(selection_rect, &file_path)?;
+            return Ok(true);
+        }
+
         let bmp_data = self.compose_selection_with_drawings(selection_rect)?;
 
-        let mut file = File::create(&file_path)
-            .map_err(|e| AppError::File(format!("Failed to create file: {e}")))?;
-        file.write_all(&bmp_data)
-            .map_err(|e| AppError::File(format!("Failed to write file: {e}")))?;
+        sc_export::save_bmp_to_file(&bmp_data, &file_path, &self.export_options())
+            .map_err(|e| AppError::File(format!("Failed to save image: {e}")))?;
 
         Ok(true)
     }
 
+    /// The unannotated selection plus its elements, relative to the selection's corner.
+    fn selection_content(
+        &self,
+        selection_rect: core_selection::RectI32,
+    ) -> AppResult<(PixelBuffer, Vec<DrawingElement>)> {
+        let crop_rect: Rect = selection_rect.into();
+        let image = self
+            .screenshot
+            .get_current_image_data()
+            .and_then(|data| crop_bmp(data, &crop_rect).ok())
+            .and_then(|bmp| PixelBuffer::from_bmp(&bmp))
+            .ok_or_else(|| AppError::Screenshot("没有可用的截图".to_string()))?;
+        let elements = self
+            .drawing
+            .export_elements(Point::new(crop_rect.left, crop_rect.top));
+        Ok((image, elements))
+    }
+
+    /// Save the unannotated selection plus its elements so it can be reopened for editing.
+    fn save_selection_as_project(
+        &self,
+        selection_rect: core_selection::RectI32,
+        path: &str,
+    ) -> AppResult<()> {
+        let (image, elements) = self.selection_content(selection_rect)?;
+        sc_export::Project::new(image, elements)
+            .save(path)
+            .map_err(|e| AppError::File(format!("Failed to save project: {e}")))
+    }
+
+    /// Save the selection with its annotations as vector SVG elements.
+    fn save_selection_as_svg(
+        &self,
+        selection_rect: core_selection::RectI32,
+        path: &str,
+    ) -> AppResult<()> {
+        let (image, elements) = self.selection_content(selection_rect)?;
+        sc_export::save_svg(&image, &elements, path)
+            .map_err(|e| AppError::File(format!("Failed to save SVG: {e}")))
+    }
+
+    pub(crate) fn open_project(&mut self, window: WindowId) -> AppResult<()> {
+        let Some(path) = self
+            .host_platform
+            .show_project_open_dialog(window)
+            .map_err(|e| AppError::Platform(e.to_string()))?
+        else {
+            return Ok(());
+        };
+
+        let project = sc_export::Project::load(&path).map_err(|e| AppError::File(e.to_string()))?;
+        PreviewWindow::show_project(project, self.current_drawing_config())
+            .map_err(|e| AppError::WinApi(format!("打开工程失败: {e:?}")))
+    }
+
     pub fn extract_text_from_selection(&mut self, window: WindowId) -> AppResult<()> {
         let Some(selection_rect) = self.confirmed_selection_rect() else {
             return Ok(());
@@ -1162,13 +1293,14 @@ impl App {
 
     pub fn handle_input_event(&mut self, event: InputEvent) -> Vec<Command> {
         match event {
-            InputEvent::MouseMove { x, y } => self.handle_mouse_move(x, y),
+            InputEvent::MouseMove { x, y, modifiers } => self.handle_mouse_move(x, y, modifiers),
 
             InputEvent::MouseDown {
                 x,
                 y,
                 button: MouseButton::Left,
-            } => self.handle_mouse_down(x, y),
+                modifiers,
+            } => self.handle_mouse_down(x, y, modifiers),
 
             InputEvent::MouseUp {
                 x,
@@ -1182,7 +1314,7 @@ impl App {
                 button: MouseButton::Left,
             } => self.handle_double_click(x, y),
 
-            InputEvent::KeyDown { key, .. } => self.handle_key_input(key.0),
+            InputEvent::KeyDown { key, modifiers } => self.handle_key_input(key.0, modifiers),
 
             InputEvent::TextInput { character } => self.handle_text_input(character),
 
@@ -1258,16 +1390,28 @@ impl sc_platform::WindowMessageHandler for App {
                 if self.host_platform.is_window_visible(window) {
                     let _ = self.host_platform.hide_window(window);
                 }
-                let _ = self.host_platform.start_timer(
-                    window,
-                    TIMER_CAPTURE_DELAY_ID as u32,
-                    TIMER_CAPTURE_DELAY_MS,
-                );
+                if !self.start_capture_countdown(window) {
+                    let _ = self.host_platform.start_timer(
+                        window,
+                        TIMER_CAPTURE_DELAY_ID as u32,
+                        TIMER_CAPTURE_DELAY_MS,
+                    );
+                }
+                Some(0)
+            }
+
+            InputEvent::Tray(sc_platform::TrayEvent::MenuCommand(1005)) => {
+                let commands = self.dispatch_core_action(CoreAction::CancelCaptureCountdown);
+                self.execute_command_chain(commands, window);
                 Some(0)
             }
 
             InputEvent::Hotkey { id } if id == HOTKEY_SCREENSHOT_ID as u32 => {
-                if self.host_platform.is_window_visible(window) {
+                if self.start_capture_countdown(window) {
+                    if self.host_platform.is_window_visible(window) {
+                        let _ = self.host_platform.hide_window(window);
+                    }
+                } else if self.host_platform.is_window_visible(window) {
                     let _ = self.host_platform.hide_window(window);
                     let _ = self.host_platform.start_timer(
                         window,
@@ -1289,6 +1433,12 @@ impl sc_platform::WindowMessageHandler for App {
                 Some(0)
             }
 
+            InputEvent::Timer { id } if id == TIMER_CAPTURE_COUNTDOWN_ID as u32 => {
+                let commands = self.dispatch_core_action(CoreAction::CaptureCountdownTick);
+                self.execute_command_chain(commands, window);
+                Some(0)
+            }
+
             InputEvent::Timer { id } if id == TIMER_SCROLL_CAPTURE_ID as u32 => {
                 if let Err(e) = self.advance_scrolling_capture(window) {
                     eprintln!("[滚动截图] {e}");
@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 
 use sc_host_protocol::{Command, DrawingMessage};
 use sc_platform::WindowId;
-use sc_ui_windows::{PreviewWindow, SettingsWindow};
+use sc_ui_windows::{CaptureCountdownWindow, PreviewWindow, SettingsWindow};
 
 use crate::app::App;
 
@@ -183,6 +183,19 @@ impl CommandExecutor for App {
                     vec![Command::ShowError("请先选择区域".to_string())]
                 }
             }
+            Command::CopySelectionToClipboard => {
+                let result = if self.has_scrolling_capture() {
+                    self.save_scrolling_to_clipboard()
+                } else if self.has_valid_selection() {
+                    self.save_selection_to_clipboard(window)
+                } else {
+                    Ok(())
+                };
+                match result {
+                    Ok(()) => vec![],
+                    Err(e) => vec![Command::ShowError(format!("自动复制失败: {e}"))],
+                }
+            }
             Command::UpdateToolbar => {
                 self.update_toolbar_state();
                 vec![]
@@ -195,6 +208,13 @@ impl CommandExecutor for App {
                     vec![Command::ShowError(format!("打开设置失败: {e}"))]
                 }
             },
+            Command::OpenProject => match self.open_project(window) {
+                Ok(()) => vec![],
+                Err(e) => {
+                    eprintln!("Failed to open project: {e}");
+                    vec![Command::ShowError(format!("打开工程失败: {e}"))]
+                }
+            },
             Command::TakeScreenshot => match self.take_screenshot(window) {
                 Ok(()) => vec![],
                 Err(e) => {
@@ -202,6 +222,16 @@ impl CommandExecutor for App {
                     vec![Command::ShowError(format!("截图失败: {e}"))]
                 }
             },
+            Command::ShowCaptureCountdown(seconds) => {
+                CaptureCountdownWindow::show(seconds);
+                self.host_platform().set_tray_countdown_active(true);
+                vec![]
+            }
+            Command::HideCaptureCountdown => {
+                CaptureCountdownWindow::close();
+                self.host_platform().set_tray_countdown_active(false);
+                vec![]
+            }
             Command::StartScrollingCapture => match self.start_scrolling_capture(window) {
                 Ok(()) => vec![],
                 Err(e) => vec![Command::ShowError(format!("启动滚动截图失败: {e}"))],
@@ -11,6 +11,7 @@ pub const HOTKEY_SCREENSHOT_ID: i32 = 1001;
 pub const TIMER_CAPTURE_DELAY_ID: usize = 2001;
 pub const TIMER_CAPTURE_DELAY_MS: u32 = 50;
 pub const TIMER_SCROLL_CAPTURE_ID: usize = 2002;
+pub const TIMER_CAPTURE_COUNTDOWN_ID: usize = 2003;
 // Poll faster than a 60 Hz producer so the single-slot frame mailbox is drained
 // promptly even when a timer tick is delayed by other UI work.
 pub const TIMER_SCROLL_CAPTURE_MS: u32 = 2;
@@ -2,6 +2,10 @@ use sc_app::{AppModel, Effect, selection as core_selection};
 
 use sc_host_protocol::{Command, DrawingMessage, UIMessage};
 
+use crate::constants::{
+    TIMER_CAPTURE_COUNTDOWN_ID, TIMER_CAPTURE_DELAY_ID, TIMER_CAPTURE_DELAY_MS,
+};
+
 pub fn command_from_effect(effect: Effect) -> Option<Command> {
     match effect {
         Effect::Selection(sel) => match sel {
@@ -16,6 +20,7 @@ pub fn command_from_effect(effect: Effect) -> Option<Command> {
         Effect::SelectDrawingTool(tool) => Some(Command::SelectDrawingTool(tool)),
         Effect::SaveSelectionToFile => Some(Command::SaveSelectionToFile),
         Effect::SaveSelectionToClipboard => Some(Command::SaveSelectionToClipboard),
+        Effect::CopySelectionToClipboard => Some(Command::CopySelectionToClipboard),
         Effect::Undo => Some(Command::Drawing(DrawingMessage::Undo)),
         Effect::ExtractText => Some(Command::ExtractText),
         Effect::ShowOcrPreview => Some(Command::ShowOcrPreview),
@@ -25,6 +30,21 @@ pub fn command_from_effect(effect: Effect) -> Option<Command> {
         Effect::PinSelection => Some(Command::PinSelection),
         Effect::ResetToInitialState => Some(Command::ResetToInitialState),
         Effect::HideWindow => Some(Command::HideWindow),
+        Effect::StartCaptureCountdownTimer { interval_ms } => Some(Command::StartTimer(
+            TIMER_CAPTURE_COUNTDOWN_ID as u32,
+            interval_ms,
+        )),
+        Effect::StopCaptureCountdownTimer => {
+            Some(Command::StopTimer(TIMER_CAPTURE_COUNTDOWN_ID as u32))
+        }
+        Effect::ShowCaptureCountdown { seconds } => Some(Command::ShowCaptureCountdown(seconds)),
+        Effect::HideCaptureCountdown => Some(Command::HideCaptureCountdown),
+        // Go through the short capture-delay timer so the countdown window is gone from the
+        // screen before the desktop is grabbed.
+        Effect::FireCapture => Some(Command::StartTimer(
+            TIMER_CAPTURE_DELAY_ID as u32,
+            TIMER_CAPTURE_DELAY_MS,
+        )),
     }
 }
 
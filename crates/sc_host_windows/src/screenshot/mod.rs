@@ -2,7 +2,7 @@ use sc_platform::WindowId;
 
 use crate::system::SystemError;
 use sc_app::selection as core_selection;
-use sc_drawing::{DragMode, Rect};
+use sc_drawing::{CursorImage, DragMode, PixelBuffer, Point, Rect, composite_cursor};
 use sc_highlight::{AutoHighlightMoveAction, AutoHighlightMoveArgs, AutoHighlighter};
 use sc_host_protocol::Command;
 use sc_platform_windows::windows::Direct2DRenderer;
@@ -133,9 +133,11 @@ impl ScreenshotManager {
         Ok(())
     }
 
+    /// Grab the screen into the background bitmap, drawing `cursor` into it when given.
     pub fn capture_screen_to_d2d_bitmap(
         &mut self,
         d2d_renderer: &mut Direct2DRenderer,
+        cursor: Option<&CursorImage>,
     ) -> std::result::Result<(), ScreenshotError> {
         let screen_rect = Rect {
             left: 0,
@@ -144,12 +146,30 @@ impl ScreenshotManager {
             bottom: self.screen_height,
         };
 
-        let (d2d_bitmap, bmp_data) = d2d_renderer
+        let (mut d2d_bitmap, mut bmp_data) = d2d_renderer
             .capture_screen_region_to_d2d_bitmap_and_bmp_data(screen_rect)
             .map_err(|e| {
                 ScreenshotError::RenderError(format!("Failed to create D2D bitmap: {e:?}"))
             })?;
 
+        if let Some(cursor) = cursor
+            && let Some(mut pixels) = bmp_data.as_deref().and_then(PixelBuffer::from_bmp)
+        {
+            composite_cursor(&mut pixels, cursor, Point::new(0, 0));
+
+            let mut rgba = pixels.pixels.clone();
+            for pixel in rgba.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+                pixel[3] = 255;
+            }
+            d2d_bitmap = d2d_renderer
+                .create_bitmap_from_pixels(&rgba, pixels.width, pixels.height)
+                .map_err(|e| {
+                    ScreenshotError::RenderError(format!("Failed to create D2D bitmap: {e:?}"))
+                })?;
+            bmp_data = Some(pixels.to_bmp());
+        }
+
         if let Some(bmp_data) = bmp_data
             && let Some(ref mut screenshot) = self.current_screenshot
         {
@@ -96,6 +96,7 @@ impl SystemManager {
                 1001 => vec![Command::TakeScreenshot],
                 1002 => vec![Command::ShowSettings],
                 1003 => vec![Command::QuitApp],
+                1004 => vec![Command::OpenProject],
                 _ => vec![],
             },
             TrayEvent::DoubleClick => vec![Command::ShowSettings],
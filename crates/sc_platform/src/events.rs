@@ -58,9 +58,18 @@ impl KeyCode {
 #[derive(Debug, Clone)]
 pub enum InputEvent {
     /// Mouse moved.
-    MouseMove { x: i32, y: i32 },
+    MouseMove {
+        x: i32,
+        y: i32,
+        modifiers: Modifiers,
+    },
     /// Mouse button pressed.
-    MouseDown { x: i32, y: i32, button: MouseButton },
+    MouseDown {
+        x: i32,
+        y: i32,
+        button: MouseButton,
+        modifiers: Modifiers,
+    },
     /// Mouse button released.
     MouseUp { x: i32, y: i32, button: MouseButton },
     /// Mouse double click.
@@ -134,6 +134,12 @@ pub trait HostPlatform {
         default_filename: &str,
     ) -> Result<Option<String>, PlatformServicesError>;
 
+    /// Ask for an annotation project file to reopen.
+    fn show_project_open_dialog(
+        &self,
+        window: Self::WindowHandle,
+    ) -> Result<Option<String>, PlatformServicesError>;
+
     fn show_info_message(&self, window: Self::WindowHandle, title: &str, message: &str);
     fn show_error_message(&self, window: Self::WindowHandle, title: &str, message: &str);
 
@@ -147,6 +153,9 @@ pub trait HostPlatform {
     /// Cleanup the system tray icon (if supported).
     fn cleanup_tray(&self) -> Result<(), PlatformServicesError>;
 
+    /// Enable the tray menu entry that cancels a delayed capture while a countdown runs.
+    fn set_tray_countdown_active(&self, active: bool);
+
     /// Register a global hotkey (if supported).
     fn set_global_hotkey(
         &self,
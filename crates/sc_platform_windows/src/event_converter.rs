@@ -3,8 +3,9 @@ use windows::Win32::Foundation::{LPARAM, WPARAM};
 use windows::Win32::UI::Input::KeyboardAndMouse::{GetKeyState, VK_CONTROL, VK_MENU, VK_SHIFT};
 use windows::Win32::UI::WindowsAndMessaging::*;
 
+/// Modifier keys held while the message being handled was generated.
 #[inline]
-fn current_modifiers() -> Modifiers {
+pub fn current_modifiers() -> Modifiers {
     unsafe {
         Modifiers {
             ctrl: (GetKeyState(VK_CONTROL.0 as i32) as u16 & 0x8000) != 0,
@@ -31,7 +32,11 @@ impl EventConverter {
         match msg {
             WM_MOUSEMOVE => {
                 let (x, y) = extract_mouse_coords(lparam);
-                Some(InputEvent::MouseMove { x, y })
+                Some(InputEvent::MouseMove {
+                    x,
+                    y,
+                    modifiers: current_modifiers(),
+                })
             }
 
             WM_LBUTTONDOWN => {
@@ -40,6 +45,7 @@ impl EventConverter {
                     x,
                     y,
                     button: MouseButton::Left,
+                    modifiers: current_modifiers(),
                 })
             }
 
@@ -58,6 +64,7 @@ impl EventConverter {
                     x,
                     y,
                     button: MouseButton::Right,
+                    modifiers: current_modifiers(),
                 })
             }
 
@@ -76,6 +83,7 @@ impl EventConverter {
                     x,
                     y,
                     button: MouseButton::Middle,
+                    modifiers: current_modifiers(),
                 })
             }
 
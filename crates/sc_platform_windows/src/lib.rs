@@ -6,4 +6,5 @@ pub mod win_api;
 pub mod windows;
 
 pub(crate) use event_converter::EventConverter;
+pub use event_converter::current_modifiers;
 pub use windows::*;
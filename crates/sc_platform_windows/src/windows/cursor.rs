@@ -1,11 +1,17 @@
+use sc_drawing::{ChannelOrder, CursorImage, CursorSource, PixelBuffer, Point};
 use sc_platform::CursorIcon;
 use windows::Win32::Foundation::HINSTANCE;
+use windows::Win32::Graphics::Gdi::{BITMAP, CreateCompatibleDC, GetObjectW, HBITMAP, HDC};
 use windows::Win32::UI::WindowsAndMessaging::{
-    IDC_ARROW, IDC_CROSS, IDC_HAND, IDC_IBEAM, IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS,
-    IDC_SIZENWSE, IDC_SIZEWE, LoadCursorW, SetCursor,
+    CURSOR_SHOWING, CURSORINFO, GetCursorInfo, GetIconInfo, HICON, ICONINFO, IDC_ARROW, IDC_CROSS,
+    IDC_HAND, IDC_IBEAM, IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS, IDC_SIZENWSE, IDC_SIZEWE,
+    LoadCursorW, SetCursor,
 };
 use windows::core::PCWSTR;
 
+use super::bmp::bitmap_to_bgra_pixels;
+use super::resources::{ManagedBitmap, ManagedDC};
+
 pub fn cursor_id(cursor: CursorIcon) -> PCWSTR {
     match cursor {
         CursorIcon::Arrow => IDC_ARROW,
@@ -30,3 +36,117 @@ pub fn set_cursor(cursor: CursorIcon) {
         }
     }
 }
+
+/// Reads the system cursor for compositing into captures.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WindowsCursorSource;
+
+impl CursorSource for WindowsCursorSource {
+    fn current_cursor(&self) -> Option<CursorImage> {
+        unsafe {
+            let mut info = CURSORINFO {
+                cbSize: std::mem::size_of::<CURSORINFO>() as u32,
+                ..Default::default()
+            };
+            GetCursorInfo(&mut info).ok()?;
+            if info.flags.0 & CURSOR_SHOWING.0 == 0 || info.hCursor.is_invalid() {
+                return None;
+            }
+
+            let mut icon = ICONINFO::default();
+            GetIconInfo(HICON(info.hCursor.0), &mut icon).ok()?;
+            let color = ManagedBitmap::new(icon.hbmColor);
+            let mask = ManagedBitmap::new(icon.hbmMask);
+            let dc = ManagedDC::new(CreateCompatibleDC(None));
+
+            let (image, invert) = if color.is_valid() {
+                color_cursor_pixels(dc.handle(), color.handle(), mask.handle())?
+            } else {
+                monochrome_cursor_pixels(dc.handle(), mask.handle())?
+            };
+
+            Some(CursorImage {
+                image,
+                invert,
+                hotspot: Point::new(icon.xHotspot as i32, icon.yHotspot as i32),
+                position: Point::new(info.ptScreenPos.x, info.ptScreenPos.y),
+            })
+        }
+    }
+}
+
+unsafe fn bitmap_size(bitmap: HBITMAP) -> Option<(i32, i32)> {
+    let mut bm = BITMAP::default();
+    let written = unsafe {
+        GetObjectW(
+            bitmap.into(),
+            std::mem::size_of::<BITMAP>() as i32,
+            Some(&mut bm as *mut BITMAP as *mut _),
+        )
+    };
+    (written != 0 && bm.bmWidth > 0 && bm.bmHeight > 0).then_some((bm.bmWidth, bm.bmHeight))
+}
+
+/// Colour cursor: alpha from the colour bitmap if it has any, otherwise from the AND mask,
+/// where masked-out pixels with a non-black colour invert the screen.
+unsafe fn color_cursor_pixels(
+    dc: HDC,
+    color: HBITMAP,
+    mask: HBITMAP,
+) -> Option<(PixelBuffer, Vec<bool>)> {
+    let (width, height) = unsafe { bitmap_size(color)? };
+    let mut pixels = bitmap_to_bgra_pixels(dc, color, width, height).ok()?;
+    let mut invert = Vec::new();
+
+    if pixels.chunks_exact(4).all(|p| p[3] == 0) {
+        let and_mask = unsafe {
+            bitmap_size(mask)
+                .and_then(|(w, h)| (w == width && h >= height).then_some(()))
+                .and_then(|()| bitmap_to_bgra_pixels(dc, mask, width, height).ok())
+        };
+        invert = vec![false; pixels.len() / 4];
+        for (i, pixel) in pixels.chunks_exact_mut(4).enumerate() {
+            let transparent = and_mask.as_ref().is_some_and(|m| m[i * 4] != 0);
+            if transparent {
+                invert[i] = pixel[..3] != [0, 0, 0];
+                pixel[3] = 0;
+            } else {
+                pixel[3] = 255;
+            }
+        }
+    }
+
+    let image = PixelBuffer::from_raw(width as u32, height as u32, ChannelOrder::Bgra, pixels)?;
+    Some((image, invert))
+}
+
+/// Monochrome cursor: the mask holds the AND bitmap on top of the XOR bitmap.
+unsafe fn monochrome_cursor_pixels(dc: HDC, mask: HBITMAP) -> Option<(PixelBuffer, Vec<bool>)> {
+    let (width, double_height) = unsafe { bitmap_size(mask)? };
+    let height = double_height / 2;
+    if height == 0 {
+        return None;
+    }
+    let bits = bitmap_to_bgra_pixels(dc, mask, width, double_height).ok()?;
+    let (and_bits, xor_bits) = bits.split_at(bits.len() / 2);
+
+    let mut image = PixelBuffer::new(width as u32, height as u32, ChannelOrder::Bgra);
+    let mut invert = vec![false; (width * height) as usize];
+    for (i, (and, xor)) in and_bits
+        .chunks_exact(4)
+        .zip(xor_bits.chunks_exact(4))
+        .enumerate()
+    {
+        let (and, xor) = (and[0] != 0, xor[0] != 0);
+        let value = match (and, xor) {
+            (false, false) => [0, 0, 0, 255],
+            (false, true) => [255, 255, 255, 255],
+            (true, xor) => {
+                invert[i] = xor;
+                [0, 0, 0, 0]
+            }
+        };
+        image.pixels[i * 4..i * 4 + 4].copy_from_slice(&value);
+    }
+    Some((image, invert))
+}
@@ -207,8 +207,7 @@ pub fn show_save_file_dialog(hwnd: HWND, title: &str, default_filename: &str) ->
             file_name[..copy_len].copy_from_slice(&default_wide[..copy_len]);
         }
 
-        let filter_str =
-            "PNG 图片\0*.png\0JPEG 图片\0*.jpg;*.jpeg\0BMP 图片\0*.bmp\0所有文件\0*.*\0\0";
+        let filter_str = "PNG 图片\0*.png\0JPEG 图片\0*.jpg;*.jpeg\0WebP 图片\0*.webp\0BMP 图片\0*.bmp\0TIFF 图片\0*.tif;*.tiff\0SVG 矢量图\0*.svg\0标注工程\0*.scproj\0所有文件\0*.*\0\0";
         let filter_wide = to_wide_chars(filter_str);
 
         let title_wide = to_wide_chars(title);
@@ -240,3 +239,30 @@ pub fn show_save_file_dialog(hwnd: HWND, title: &str, default_filename: &str) ->
 pub fn show_image_save_dialog(hwnd: HWND, default_filename: &str) -> Option<String> {
     show_save_file_dialog(hwnd, "保存图片", default_filename)
 }
+
+pub fn show_project_open_dialog(hwnd: HWND) -> Option<String> {
+    unsafe {
+        let mut file_name = [0u16; 260]; // MAX_PATH
+
+        let filter_wide = to_wide_chars("标注工程\0*.scproj\0所有文件\0*.*\0\0");
+        let title_wide = to_wide_chars("打开工程");
+
+        let mut ofn = OPENFILENAMEW {
+            lStructSize: std::mem::size_of::<OPENFILENAMEW>() as u32,
+            hwndOwner: hwnd,
+            lpstrFilter: PCWSTR(filter_wide.as_ptr()),
+            lpstrFile: PWSTR(file_name.as_mut_ptr()),
+            nMaxFile: file_name.len() as u32,
+            lpstrTitle: PCWSTR(title_wide.as_ptr()),
+            Flags: OFN_FILEMUSTEXIST | OFN_PATHMUSTEXIST | OFN_HIDEREADONLY,
+            nFilterIndex: 1,
+            ..Default::default()
+        };
+
+        if GetOpenFileNameW(&mut ofn).as_bool() {
+            PWSTR(file_name.as_mut_ptr()).to_string().ok()
+        } else {
+            None
+        }
+    }
+}
@@ -175,6 +175,13 @@ impl HostPlatform for WindowsHostPlatform {
         ))
     }
 
+    fn show_project_open_dialog(
+        &self,
+        window: WindowId,
+    ) -> Result<Option<String>, PlatformServicesError> {
+        Ok(file_dialog::show_project_open_dialog(super::hwnd(window)))
+    }
+
     fn show_info_message(&self, window: WindowId, title: &str, message: &str) {
         message_box::show_info(super::hwnd(window), title, message);
     }
@@ -196,6 +203,10 @@ impl HostPlatform for WindowsHostPlatform {
         Ok(())
     }
 
+    fn set_tray_countdown_active(&self, active: bool) {
+        super::tray::set_countdown_active(active);
+    }
+
     fn set_global_hotkey(
         &self,
         window: WindowId,
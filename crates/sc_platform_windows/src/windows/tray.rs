@@ -1,4 +1,5 @@
 use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use windows::Win32::Foundation::{HWND, POINT};
 use windows::Win32::UI::Shell::{
@@ -6,8 +7,8 @@ use windows::Win32::UI::Shell::{
 };
 use windows::Win32::UI::WindowsAndMessaging::{
     AppendMenuW, CreatePopupMenu, DestroyMenu, GetCursorPos, HICON, IDI_APPLICATION, IMAGE_ICON,
-    LR_LOADFROMFILE, LoadIconW, LoadImageW, MF_SEPARATOR, MF_STRING, SetForegroundWindow,
-    TPM_RETURNCMD, TPM_RIGHTBUTTON, TrackPopupMenu,
+    LR_LOADFROMFILE, LoadIconW, LoadImageW, MF_GRAYED, MF_SEPARATOR, MF_STRING,
+    SetForegroundWindow, TPM_RETURNCMD, TPM_RIGHTBUTTON, TrackPopupMenu,
 };
 use windows::core::PCWSTR;
 
@@ -44,6 +45,13 @@ impl From<windows::core::Error> for TrayIconError {
 
 pub type Result<T> = std::result::Result<T, TrayIconError>;
 
+/// Whether a delayed capture is counting down, so the menu can offer to cancel it.
+static COUNTDOWN_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_countdown_active(active: bool) {
+    COUNTDOWN_ACTIVE.store(active, Ordering::Relaxed);
+}
+
 pub fn create_default_icon() -> Result<HICON> {
     // SAFETY: `include_bytes!` embeds the icon at compile time.
     const ICON_DATA: &[u8] = include_bytes!(concat!(
@@ -138,6 +146,18 @@ pub fn show_default_context_menu(hwnd: HWND) -> u32 {
             1001,
             windows::core::w!("截图(&S)\tCtrl+Alt+S"),
         );
+        let cancel_flags = if COUNTDOWN_ACTIVE.load(Ordering::Relaxed) {
+            MF_STRING
+        } else {
+            MF_STRING | MF_GRAYED
+        };
+        let _ = AppendMenuW(
+            hmenu,
+            cancel_flags,
+            1005,
+            windows::core::w!("取消延时截图(&C)"),
+        );
+        let _ = AppendMenuW(hmenu, MF_STRING, 1004, windows::core::w!("打开工程(&O)..."));
         let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
         let _ = AppendMenuW(hmenu, MF_STRING, 1002, windows::core::w!("设置(&T)"));
         let _ = AppendMenuW(hmenu, MF_SEPARATOR, 0, PCWSTR::null());
@@ -0,0 +1,669 @@
+//! Headless [`RenderBackend`] on `tiny-skia`.
+//!
+//! Mirrors the Direct2D backend's drawing rules (stroke placement, dash units, handle layout),
+//! so render lists can be rasterized for exports and checked in tests on any platform.
+
+use std::sync::{Arc, OnceLock};
+
+use resvg::usvg::{self, fontdb};
+use tiny_skia::{
+    FillRule, LineCap, LineJoin, Mask, Paint, PathBuilder, Pixmap, PixmapPaint, Stroke, StrokeDash,
+    Transform,
+};
+
+use crate::render_list::RenderBackend;
+use crate::types::{Color, DrawStyle, Point, Rectangle, TextStyle};
+
+/// Dash pattern used by dashed rectangles that do not specify one (in stroke widths).
+const DEFAULT_DASH_PATTERN: [f32; 2] = [4.0, 2.0];
+
+/// Cubic Bézier control-point factor for quarter circles.
+const KAPPA: f32 = 0.552_284_8;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CpuRenderError {
+    #[error("无效的画布尺寸: {width}x{height}")]
+    InvalidSize { width: u32, height: u32 },
+    #[error("裁剪栈为空，无法弹出")]
+    ClipStackUnderflow,
+    #[error("文本排版失败: {0}")]
+    Text(String),
+}
+
+/// CPU renderer that draws into an RGBA pixmap.
+pub struct TinySkiaRenderer {
+    pixmap: Pixmap,
+    /// One mask per pushed clip, each already intersected with the ones below it.
+    clips: Vec<Mask>,
+    fonts: Arc<fontdb::Database>,
+}
+
+impl TinySkiaRenderer {
+    /// Transparent canvas of the given size. Text uses the system fonts.
+    pub fn new(width: u32, height: u32) -> Result<Self, CpuRenderError> {
+        let pixmap =
+            Pixmap::new(width, height).ok_or(CpuRenderError::InvalidSize { width, height })?;
+        Ok(Self {
+            pixmap,
+            clips: Vec::new(),
+            fonts: system_fonts(),
+        })
+    }
+
+    /// Use `fonts` for text instead of the system fonts, e.g. to keep output reproducible.
+    pub fn with_font_database(mut self, fonts: Arc<fontdb::Database>) -> Self {
+        self.fonts = fonts;
+        self
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.pixmap.width()
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.pixmap.height()
+    }
+
+    /// Number of clips currently pushed.
+    #[inline]
+    pub fn clip_depth(&self) -> usize {
+        self.clips.len()
+    }
+
+    /// Fill the whole canvas with `color`, ignoring clips.
+    pub fn clear(&mut self, color: Color) {
+        self.pixmap.fill(to_skia_color(color));
+    }
+
+    /// Rendered pixels (premultiplied RGBA).
+    pub fn pixmap(&self) -> &Pixmap {
+        &self.pixmap
+    }
+
+    pub fn into_pixmap(self) -> Pixmap {
+        self.pixmap
+    }
+
+    /// Straight-alpha RGBA8 copy of the canvas, row-major, top-down.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.pixmap
+            .pixels()
+            .iter()
+            .flat_map(|p| {
+                let c = p.demultiply();
+                [c.red(), c.green(), c.blue(), c.alpha()]
+            })
+            .collect()
+    }
+
+    fn mask(&self) -> Option<&Mask> {
+        self.clips.last()
+    }
+
+    fn fill(&mut self, path: &tiny_skia::Path, color: Color) {
+        let paint = paint(color);
+        let mask = self.clips.last();
+        self.pixmap
+            .fill_path(path, &paint, FillRule::Winding, Transform::identity(), mask);
+    }
+
+    fn stroke(&mut self, path: &tiny_skia::Path, color: Color, stroke: &Stroke) {
+        if stroke.width <= 0.0 {
+            return;
+        }
+        let paint = paint(color);
+        let mask = self.clips.last();
+        self.pixmap
+            .stroke_path(path, &paint, stroke, Transform::identity(), mask);
+    }
+
+    fn fill_and_stroke(&mut self, path: Option<tiny_skia::Path>, style: &DrawStyle) {
+        let Some(path) = path else {
+            return;
+        };
+        if let Some(fill) = style.fill_color {
+            self.fill(&path, fill);
+        }
+        self.stroke(&path, style.stroke_color, &solid_stroke(style.stroke_width));
+    }
+
+    fn fill_bounds(&mut self, left: f32, top: f32, right: f32, bottom: f32, color: Color) {
+        if let Some(path) = rect_path(Rectangle::from_bounds(left, top, right, bottom)) {
+            self.fill(&path, color);
+        }
+    }
+}
+
+impl RenderBackend for TinySkiaRenderer {
+    type Error = CpuRenderError;
+
+    fn draw_rectangle(&mut self, rect: Rectangle, style: &DrawStyle) -> Result<(), Self::Error> {
+        self.fill_and_stroke(rect_path(rect), style);
+        Ok(())
+    }
+
+    fn draw_rounded_rectangle(
+        &mut self,
+        rect: Rectangle,
+        radius: f32,
+        style: &DrawStyle,
+    ) -> Result<(), Self::Error> {
+        self.fill_and_stroke(rounded_rect_path(rect, radius), style);
+        Ok(())
+    }
+
+    fn draw_circle(
+        &mut self,
+        center: Point,
+        radius: f32,
+        style: &DrawStyle,
+    ) -> Result<(), Self::Error> {
+        self.fill_and_stroke(PathBuilder::from_circle(center.x, center.y, radius), style);
+        Ok(())
+    }
+
+    fn draw_line(
+        &mut self,
+        start: Point,
+        end: Point,
+        style: &DrawStyle,
+    ) -> Result<(), Self::Error> {
+        let mut builder = PathBuilder::new();
+        builder.move_to(start.x, start.y);
+        builder.line_to(end.x, end.y);
+        if let Some(path) = builder.finish() {
+            self.stroke(&path, style.stroke_color, &solid_stroke(style.stroke_width));
+        }
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        text: &str,
+        position: Point,
+        style: &TextStyle,
+    ) -> Result<(), Self::Error> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        // usvg does the shaping; the SVG only ever holds this one text run.
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}"><text x="{}" y="{}" font-family="{}" font-size="{}" fill="rgb({},{},{})" fill-opacity="{}" dominant-baseline="text-before-edge" xml:space="preserve">{}</text></svg>"#,
+            self.width(),
+            self.height(),
+            position.x,
+            position.y,
+            escape(&style.font_family),
+            style.font_size,
+            channel(style.color.r),
+            channel(style.color.g),
+            channel(style.color.b),
+            style.color.a.clamp(0.0, 1.0),
+            escape(text),
+        );
+        let options = usvg::Options {
+            fontdb: self.fonts.clone(),
+            ..Default::default()
+        };
+        let tree = usvg::Tree::from_str(&svg, &options)
+            .map_err(|e| CpuRenderError::Text(e.to_string()))?;
+
+        // Rendered on a separate layer so the clip mask still applies.
+        let mut layer =
+            Pixmap::new(self.width(), self.height()).ok_or(CpuRenderError::InvalidSize {
+                width: self.width(),
+                height: self.height(),
+            })?;
+        resvg::render(&tree, Transform::identity(), &mut layer.as_mut());
+        let mask = self.clips.last();
+        self.pixmap.draw_pixmap(
+            0,
+            0,
+            layer.as_ref(),
+            &PixmapPaint::default(),
+            Transform::identity(),
+            mask,
+        );
+        Ok(())
+    }
+
+    fn draw_dashed_rectangle(
+        &mut self,
+        rect: Rectangle,
+        style: &DrawStyle,
+        dash_pattern: &[f32],
+    ) -> Result<(), Self::Error> {
+        let pattern = if dash_pattern.is_empty() {
+            &DEFAULT_DASH_PATTERN[..]
+        } else {
+            dash_pattern
+        };
+        if let Some(path) = rect_path(rect) {
+            let stroke = dashed_stroke(style.stroke_width.max(1.0), pattern);
+            self.stroke(&path, style.stroke_color, &stroke);
+        }
+        Ok(())
+    }
+
+    fn draw_selection_mask(
+        &mut self,
+        screen_rect: Rectangle,
+        selection_rect: Rectangle,
+        mask_color: Color,
+    ) -> Result<(), Self::Error> {
+        let (left, top) = (selection_rect.x, selection_rect.y);
+        let (right, bottom) = (selection_rect.right(), selection_rect.bottom());
+        let (screen_right, screen_bottom) = (screen_rect.right(), screen_rect.bottom());
+
+        if top > screen_rect.y {
+            self.fill_bounds(screen_rect.x, screen_rect.y, screen_right, top, mask_color);
+        }
+        if bottom < screen_bottom {
+            self.fill_bounds(
+                screen_rect.x,
+                bottom,
+                screen_right,
+                screen_bottom,
+                mask_color,
+            );
+        }
+        if left > screen_rect.x {
+            self.fill_bounds(screen_rect.x, top, left, bottom, mask_color);
+        }
+        if right < screen_right {
+            self.fill_bounds(right, top, screen_right, bottom, mask_color);
+        }
+        Ok(())
+    }
+
+    fn draw_selection_border(
+        &mut self,
+        rect: Rectangle,
+        color: Color,
+        width: f32,
+        dash_pattern: Option<&[f32]>,
+    ) -> Result<(), Self::Error> {
+        if let Some(path) = rect_path(rect) {
+            let stroke = match dash_pattern {
+                Some(dash) if !dash.is_empty() => dashed_stroke(width, dash),
+                _ => solid_stroke(width),
+            };
+            self.stroke(&path, color, &stroke);
+        }
+        Ok(())
+    }
+
+    fn draw_selection_handles(
+        &mut self,
+        rect: Rectangle,
+        handle_size: f32,
+        fill_color: Color,
+        border_color: Color,
+        border_width: f32,
+    ) -> Result<(), Self::Error> {
+        let half = handle_size / 2.0;
+        for (x, y) in handle_positions(rect) {
+            let handle = Rectangle::new(x - half, y - half, handle_size, handle_size);
+            if let Some(path) = rect_path(handle) {
+                self.fill(&path, fill_color);
+                self.stroke(&path, border_color, &solid_stroke(border_width));
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_element_handles(
+        &mut self,
+        rect: Rectangle,
+        handle_radius: f32,
+        fill_color: Color,
+        border_color: Color,
+        border_width: f32,
+    ) -> Result<(), Self::Error> {
+        for (x, y) in handle_positions(rect) {
+            if let Some(path) = PathBuilder::from_circle(x, y, handle_radius) {
+                self.fill(&path, fill_color);
+                self.stroke(&path, border_color, &solid_stroke(border_width));
+            }
+        }
+        Ok(())
+    }
+
+    fn push_clip_rect(&mut self, rect: Rectangle) -> Result<(), Self::Error> {
+        let (width, height) = (self.width(), self.height());
+        let mut mask = match self.mask() {
+            Some(mask) => mask.clone(),
+            None => {
+                Mask::new(width, height).ok_or(CpuRenderError::InvalidSize { width, height })?
+            }
+        };
+
+        match rect_path(rect) {
+            Some(path) if self.clips.is_empty() => {
+                mask.fill_path(&path, FillRule::Winding, true, Transform::identity());
+            }
+            Some(path) => {
+                mask.intersect_path(&path, FillRule::Winding, true, Transform::identity())
+            }
+            // An empty clip hides everything until it is popped.
+            None => mask.clear(),
+        }
+        self.clips.push(mask);
+        Ok(())
+    }
+
+    fn pop_clip_rect(&mut self) -> Result<(), Self::Error> {
+        self.clips
+            .pop()
+            .map(|_| ())
+            .ok_or(CpuRenderError::ClipStackUnderflow)
+    }
+}
+
+/// Fonts shared by every renderer that did not get its own database.
+fn system_fonts() -> Arc<fontdb::Database> {
+    static FONTS: OnceLock<Arc<fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut db = fontdb::Database::new();
+            db.load_system_fonts();
+            Arc::new(db)
+        })
+        .clone()
+}
+
+/// Same order as the Direct2D backend: corners and edge midpoints, clockwise from top-left.
+fn handle_positions(rect: Rectangle) -> [(f32, f32); 8] {
+    let cx = rect.x + rect.width / 2.0;
+    let cy = rect.y + rect.height / 2.0;
+    [
+        (rect.x, rect.y),
+        (cx, rect.y),
+        (rect.right(), rect.y),
+        (rect.right(), cy),
+        (rect.right(), rect.bottom()),
+        (cx, rect.bottom()),
+        (rect.x, rect.bottom()),
+        (rect.x, cy),
+    ]
+}
+
+fn skia_rect(rect: Rectangle) -> Option<tiny_skia::Rect> {
+    tiny_skia::Rect::from_ltrb(
+        rect.x.min(rect.right()),
+        rect.y.min(rect.bottom()),
+        rect.x.max(rect.right()),
+        rect.y.max(rect.bottom()),
+    )
+}
+
+fn rect_path(rect: Rectangle) -> Option<tiny_skia::Path> {
+    skia_rect(rect).map(PathBuilder::from_rect)
+}
+
+fn rounded_rect_path(rect: Rectangle, radius: f32) -> Option<tiny_skia::Path> {
+    let bounds = skia_rect(rect)?;
+    let r = radius.min(bounds.width() / 2.0).min(bounds.height() / 2.0);
+    if r <= 0.0 {
+        return Some(PathBuilder::from_rect(bounds));
+    }
+
+    let (l, t, rt, b) = (bounds.left(), bounds.top(), bounds.right(), bounds.bottom());
+    let k = r * KAPPA;
+    let mut builder = PathBuilder::new();
+    builder.move_to(l + r, t);
+    builder.line_to(rt - r, t);
+    builder.cubic_to(rt - r + k, t, rt, t + r - k, rt, t + r);
+    builder.line_to(rt, b - r);
+    builder.cubic_to(rt, b - r + k, rt - r + k, b, rt - r, b);
+    builder.line_to(l + r, b);
+    builder.cubic_to(l + r - k, b, l, b - r + k, l, b - r);
+    builder.line_to(l, t + r);
+    builder.cubic_to(l, t + r - k, l + r - k, t, l + r, t);
+    builder.close();
+    builder.finish()
+}
+
+fn solid_stroke(width: f32) -> Stroke {
+    Stroke {
+        width,
+        line_cap: LineCap::Butt,
+        line_join: LineJoin::Miter,
+        miter_limit: 10.0,
+        ..Default::default()
+    }
+}
+
+/// Direct2D custom dashes are in multiples of the stroke width; tiny-skia wants pixels.
+fn dashed_stroke(width: f32, pattern: &[f32]) -> Stroke {
+    let mut dashes: Vec<f32> = pattern.iter().map(|d| d.max(0.0) * width).collect();
+    if dashes.len() % 2 == 1 {
+        dashes.extend_from_within(..);
+    }
+    Stroke {
+        dash: StrokeDash::new(dashes, 0.0),
+        ..solid_stroke(width)
+    }
+}
+
+fn paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(to_skia_color(color));
+    paint.anti_alias = true;
+    paint
+}
+
+fn to_skia_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba(
+        color.r.clamp(0.0, 1.0),
+        color.g.clamp(0.0, 1.0),
+        color.b.clamp(0.0, 1.0),
+        color.a.clamp(0.0, 1.0),
+    )
+    .unwrap_or(tiny_skia::Color::TRANSPARENT)
+}
+
+fn channel(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_list::{RenderItem, RenderList};
+
+    const RED: Color = Color::rgb(1.0, 0.0, 0.0);
+    const BLUE: Color = Color::rgb(0.0, 0.0, 1.0);
+
+    fn renderer(width: u32, height: u32) -> TinySkiaRenderer {
+        let mut renderer = TinySkiaRenderer::new(width, height)
+            .unwrap()
+            .with_font_database(Arc::new(fontdb::Database::new()));
+        renderer.clear(Color::WHITE);
+        renderer
+    }
+
+    fn rgba(renderer: &TinySkiaRenderer, x: u32, y: u32) -> [u8; 4] {
+        let c = renderer.pixmap().pixel(x, y).unwrap().demultiply();
+        [c.red(), c.green(), c.blue(), c.alpha()]
+    }
+
+    fn filled(color: Color) -> DrawStyle {
+        DrawStyle {
+            stroke_color: color,
+            fill_color: Some(color),
+            stroke_width: 0.0,
+        }
+    }
+
+    #[test]
+    fn zero_sized_canvas_is_rejected() {
+        assert!(matches!(
+            TinySkiaRenderer::new(0, 10),
+            Err(CpuRenderError::InvalidSize {
+                width: 0,
+                height: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn rectangle_fill_and_stroke() {
+        let mut r = renderer(20, 20);
+        let style = DrawStyle {
+            stroke_color: BLUE,
+            fill_color: Some(RED),
+            stroke_width: 2.0,
+        };
+        r.draw_rectangle(Rectangle::new(4.0, 4.0, 12.0, 12.0), &style)
+            .unwrap();
+
+        assert_eq!(rgba(&r, 10, 10), [255, 0, 0, 255]);
+        // The stroke straddles the edge, like Direct2D.
+        assert_eq!(rgba(&r, 3, 10), [0, 0, 255, 255]);
+        assert_eq!(rgba(&r, 4, 10), [0, 0, 255, 255]);
+        assert_eq!(rgba(&r, 1, 1), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn dashed_rectangle_leaves_gaps() {
+        let mut r = renderer(40, 10);
+        let style = DrawStyle {
+            stroke_color: RED,
+            fill_color: None,
+            stroke_width: 2.0,
+        };
+        r.draw_dashed_rectangle(Rectangle::new(0.0, 5.0, 40.0, 4.0), &style, &[2.0, 2.0])
+            .unwrap();
+
+        // Dashes scale with the stroke width: 4px on, 4px off along the top edge.
+        let row: Vec<bool> = (0..16).map(|x| rgba(&r, x, 5)[1] == 0).collect();
+        assert_eq!(
+            row,
+            [
+                true, true, true, true, false, false, false, false, true, true, true, true, false,
+                false, false, false
+            ]
+        );
+    }
+
+    #[test]
+    fn selection_mask_spares_the_selection() {
+        let mut r = renderer(30, 30);
+        r.draw_selection_mask(
+            Rectangle::new(0.0, 0.0, 30.0, 30.0),
+            Rectangle::new(10.0, 10.0, 10.0, 10.0),
+            Color::BLACK,
+        )
+        .unwrap();
+
+        for (x, y) in [(2, 2), (15, 2), (2, 15), (25, 15), (15, 25)] {
+            assert_eq!(rgba(&r, x, y), [0, 0, 0, 255], "({x}, {y})");
+        }
+        assert_eq!(rgba(&r, 15, 15), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn handles_sit_on_corners_and_edge_midpoints() {
+        let mut r = renderer(40, 40);
+        let rect = Rectangle::new(10.0, 10.0, 20.0, 20.0);
+        r.draw_selection_handles(rect, 4.0, RED, RED, 0.0).unwrap();
+        for (x, y) in handle_positions(rect) {
+            assert_eq!(rgba(&r, x as u32, y as u32), [255, 0, 0, 255]);
+        }
+        assert_eq!(rgba(&r, 20, 20), [255, 255, 255, 255]);
+
+        let mut r = renderer(40, 40);
+        r.draw_element_handles(rect, 3.0, BLUE, BLUE, 1.0).unwrap();
+        assert_eq!(rgba(&r, 30, 20), [0, 0, 255, 255]);
+        assert_eq!(rgba(&r, 20, 20), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn nested_clips_intersect_and_pop() {
+        let mut r = renderer(30, 30);
+        r.push_clip_rect(Rectangle::new(0.0, 0.0, 20.0, 20.0))
+            .unwrap();
+        r.push_clip_rect(Rectangle::new(10.0, 10.0, 20.0, 20.0))
+            .unwrap();
+        assert_eq!(r.clip_depth(), 2);
+        r.draw_rectangle(Rectangle::new(0.0, 0.0, 30.0, 30.0), &filled(RED))
+            .unwrap();
+        assert_eq!(rgba(&r, 15, 15), [255, 0, 0, 255]);
+        assert_eq!(rgba(&r, 5, 5), [255, 255, 255, 255]);
+        assert_eq!(rgba(&r, 25, 25), [255, 255, 255, 255]);
+
+        r.pop_clip_rect().unwrap();
+        r.draw_rectangle(Rectangle::new(0.0, 0.0, 30.0, 30.0), &filled(BLUE))
+            .unwrap();
+        assert_eq!(rgba(&r, 5, 5), [0, 0, 255, 255]);
+        assert_eq!(rgba(&r, 25, 25), [255, 255, 255, 255]);
+
+        r.pop_clip_rect().unwrap();
+        assert!(matches!(
+            r.pop_clip_rect(),
+            Err(CpuRenderError::ClipStackUnderflow)
+        ));
+    }
+
+    #[test]
+    fn rounded_corners_are_not_filled() {
+        let mut r = renderer(20, 20);
+        r.draw_rounded_rectangle(Rectangle::new(0.0, 0.0, 20.0, 20.0), 8.0, &filled(RED))
+            .unwrap();
+        assert_eq!(rgba(&r, 0, 0), [255, 255, 255, 255]);
+        assert_eq!(rgba(&r, 10, 0), [255, 0, 0, 255]);
+        assert_eq!(rgba(&r, 10, 10), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn text_without_fonts_is_a_no_op() {
+        let mut r = renderer(20, 20);
+        r.draw_text("a<b", Point::new(2.0, 2.0), &TextStyle::default())
+            .unwrap();
+        assert!(r.to_rgba().iter().all(|&v| v == 255));
+    }
+
+    #[test]
+    fn executes_render_lists_in_z_order() {
+        let mut list = RenderList::new();
+        list.submit(RenderItem::Circle {
+            center: Point::new(10.0, 10.0),
+            radius: 5.0,
+            style: filled(BLUE),
+            z_order: 2,
+        });
+        list.submit(RenderItem::Rectangle {
+            rect: Rectangle::new(0.0, 0.0, 20.0, 20.0),
+            style: filled(RED),
+            z_order: 1,
+        });
+        list.submit(RenderItem::Line {
+            start: Point::new(0.0, 19.0),
+            end: Point::new(20.0, 19.0),
+            style: DrawStyle {
+                stroke_color: BLUE,
+                fill_color: None,
+                stroke_width: 2.0,
+            },
+            z_order: 3,
+        });
+
+        let mut r = renderer(20, 20);
+        list.execute(&mut r).unwrap();
+        assert_eq!(rgba(&r, 10, 10), [0, 0, 255, 255]);
+        assert_eq!(rgba(&r, 2, 2), [255, 0, 0, 255]);
+        assert_eq!(rgba(&r, 2, 19), [0, 0, 255, 255]);
+        assert_eq!(&r.to_rgba()[..4], &[255, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,310 @@
+//! Golden-image checks for render output.
+//!
+//! A render list is rasterized with [`TinySkiaRenderer`] and compared with a PNG checked in
+//! next to the test. When the images differ, `<name>.actual.png` and `<name>.diff.png` are
+//! written beside the golden so the change can be inspected. Set `SC_UPDATE_GOLDEN=1` to
+//! (re)write the goldens from the current output instead of comparing.
+
+use std::path::{Path, PathBuf};
+
+use tiny_skia::{Pixmap, PremultipliedColorU8};
+
+use crate::cpu::{CpuRenderError, TinySkiaRenderer};
+use crate::render_list::RenderList;
+use crate::types::Color;
+
+/// Environment variable that switches [`assert_golden`] to rewriting the goldens.
+pub const UPDATE_GOLDEN_ENV: &str = "SC_UPDATE_GOLDEN";
+
+/// How far an image may drift from its golden before the check fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoldenOptions {
+    /// Largest per-channel difference (straight alpha) still treated as equal.
+    pub channel_tolerance: u8,
+    /// Number of pixels allowed to exceed the tolerance.
+    pub max_mismatched_pixels: usize,
+}
+
+impl Default for GoldenOptions {
+    fn default() -> Self {
+        Self {
+            channel_tolerance: 2,
+            max_mismatched_pixels: 0,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GoldenError {
+    #[error("缺少基准图 {0}，请设置 SC_UPDATE_GOLDEN=1 生成")]
+    Missing(PathBuf),
+    #[error("尺寸不一致: 基准图 {expected:?}，实际 {actual:?}")]
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    #[error("{mismatched} 个像素超出容差 (最大差值 {max_difference})，差异图: {}", diff_path.display())]
+    Mismatch {
+        mismatched: usize,
+        max_difference: u8,
+        diff_path: PathBuf,
+    },
+    #[error("读写基准图失败: {0}")]
+    Io(String),
+}
+
+/// Result of comparing two equally sized images.
+#[derive(Debug)]
+pub struct Comparison {
+    /// Pixels whose largest channel difference exceeds the tolerance.
+    pub mismatched: usize,
+    /// Largest channel difference over all pixels.
+    pub max_difference: u8,
+    /// Mismatched pixels in red over a faded copy of the expected image.
+    pub diff: Pixmap,
+}
+
+/// Rasterize `list` onto a `width` x `height` canvas filled with `background`.
+///
+/// Text is drawn without fonts (i.e. skipped) so the output does not depend on the machine.
+pub fn rasterize(
+    list: &mut RenderList,
+    width: u32,
+    height: u32,
+    background: Color,
+) -> Result<Pixmap, CpuRenderError> {
+    let mut renderer = TinySkiaRenderer::new(width, height)?
+        .with_font_database(std::sync::Arc::new(resvg::usvg::fontdb::Database::new()));
+    renderer.clear(background);
+    list.execute(&mut renderer)?;
+    Ok(renderer.into_pixmap())
+}
+
+/// Compare two images of the same size. Returns `None` if the sizes differ.
+pub fn compare(actual: &Pixmap, expected: &Pixmap, channel_tolerance: u8) -> Option<Comparison> {
+    if (actual.width(), actual.height()) != (expected.width(), expected.height()) {
+        return None;
+    }
+
+    let mut diff = Pixmap::new(expected.width(), expected.height())?;
+    let mut mismatched = 0;
+    let mut max_difference = 0;
+    for ((a, e), d) in actual
+        .pixels()
+        .iter()
+        .zip(expected.pixels())
+        .zip(diff.pixels_mut())
+    {
+        let (a, e) = (a.demultiply(), e.demultiply());
+        let difference = [
+            a.red().abs_diff(e.red()),
+            a.green().abs_diff(e.green()),
+            a.blue().abs_diff(e.blue()),
+            a.alpha().abs_diff(e.alpha()),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+        max_difference = max_difference.max(difference);
+
+        *d = if difference > channel_tolerance {
+            mismatched += 1;
+            opaque(255, 0, 0)
+        } else {
+            let luma = (e.red() as u32 * 3 + e.green() as u32 * 6 + e.blue() as u32) / 10;
+            let faded = (192 + luma / 4) as u8;
+            opaque(faded, faded, faded)
+        };
+    }
+
+    Some(Comparison {
+        mismatched,
+        max_difference,
+        diff,
+    })
+}
+
+/// Check `actual` against the golden PNG at `path`, or rewrite it if `SC_UPDATE_GOLDEN` is set.
+pub fn check_golden(
+    actual: &Pixmap,
+    path: impl AsRef<Path>,
+    options: &GoldenOptions,
+) -> Result<(), GoldenError> {
+    let update = std::env::var_os(UPDATE_GOLDEN_ENV).is_some_and(|v| v != "0" && !v.is_empty());
+    check_golden_with(actual, path.as_ref(), options, update)
+}
+
+/// [`check_golden`] that panics with a readable message, for use in tests.
+#[track_caller]
+pub fn assert_golden(actual: &Pixmap, path: impl AsRef<Path>, options: &GoldenOptions) {
+    let path = path.as_ref();
+    if let Err(e) = check_golden(actual, path, options) {
+        panic!("golden image {} does not match: {e}", path.display());
+    }
+}
+
+fn check_golden_with(
+    actual: &Pixmap,
+    path: &Path,
+    options: &GoldenOptions,
+    update: bool,
+) -> Result<(), GoldenError> {
+    let io = |e: &dyn std::fmt::Display| GoldenError::Io(format!("{}: {e}", path.display()));
+
+    if update {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| io(&e))?;
+        }
+        return actual.save_png(path).map_err(|e| io(&e));
+    }
+    if !path.exists() {
+        return Err(GoldenError::Missing(path.to_path_buf()));
+    }
+
+    let expected = Pixmap::load_png(path).map_err(|e| io(&e))?;
+    let Some(comparison) = compare(actual, &expected, options.channel_tolerance) else {
+        actual
+            .save_png(sibling(path, "actual"))
+            .map_err(|e| io(&e))?;
+        return Err(GoldenError::SizeMismatch {
+            expected: (expected.width(), expected.height()),
+            actual: (actual.width(), actual.height()),
+        });
+    };
+    if comparison.mismatched <= options.max_mismatched_pixels {
+        return Ok(());
+    }
+
+    let diff_path = sibling(path, "diff");
+    actual
+        .save_png(sibling(path, "actual"))
+        .map_err(|e| io(&e))?;
+    comparison.diff.save_png(&diff_path).map_err(|e| io(&e))?;
+    Err(GoldenError::Mismatch {
+        mismatched: comparison.mismatched,
+        max_difference: comparison.max_difference,
+        diff_path,
+    })
+}
+
+/// `dir/name.png` -> `dir/name.<suffix>.png`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}.{suffix}.png"))
+}
+
+fn opaque(r: u8, g: u8, b: u8) -> PremultipliedColorU8 {
+    PremultipliedColorU8::from_rgba(r, g, b, 255).expect("opaque colours are always valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_list::RenderItem;
+    use crate::types::{DrawStyle, Rectangle};
+
+    fn square(color: Color) -> Pixmap {
+        let mut list = RenderList::new();
+        list.submit(RenderItem::Rectangle {
+            rect: Rectangle::new(2.0, 2.0, 4.0, 4.0),
+            style: DrawStyle {
+                stroke_color: color,
+                fill_color: Some(color),
+                stroke_width: 0.0,
+            },
+            z_order: 0,
+        });
+        rasterize(&mut list, 8, 8, Color::WHITE).unwrap()
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sc_golden_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn identical_images_have_no_mismatch() {
+        let image = square(Color::BLACK);
+        let comparison = compare(&image, &image.clone(), 0).unwrap();
+        assert_eq!(comparison.mismatched, 0);
+        assert_eq!(comparison.max_difference, 0);
+    }
+
+    #[test]
+    fn tolerance_absorbs_small_differences() {
+        let a = square(Color::rgb(0.5, 0.5, 0.5));
+        let b = square(Color::rgb(0.51, 0.5, 0.5));
+
+        let strict = compare(&a, &b, 0).unwrap();
+        assert_eq!(strict.mismatched, 16);
+        assert_eq!(strict.diff.pixel(3, 3).unwrap().red(), 255);
+        assert_eq!(strict.diff.pixel(0, 0).unwrap().green(), 255);
+
+        assert_eq!(
+            compare(&a, &b, strict.max_difference).unwrap().mismatched,
+            0
+        );
+        assert!(compare(&a, &square(Color::BLACK).clone(), 0).is_some());
+    }
+
+    #[test]
+    fn size_mismatch_is_reported() {
+        let dir = scratch_dir("size");
+        let golden = dir.join("square.png");
+        check_golden_with(
+            &square(Color::BLACK),
+            &golden,
+            &GoldenOptions::default(),
+            true,
+        )
+        .unwrap();
+
+        let bigger = Pixmap::new(9, 8).unwrap();
+        assert!(compare(&bigger, &square(Color::BLACK), 0).is_none());
+        assert!(matches!(
+            check_golden_with(&bigger, &golden, &GoldenOptions::default(), false),
+            Err(GoldenError::SizeMismatch {
+                expected: (8, 8),
+                actual: (9, 8)
+            })
+        ));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn mismatch_writes_actual_and_diff_images() {
+        let dir = scratch_dir("mismatch");
+        let golden = dir.join("square.png");
+        let options = GoldenOptions::default();
+
+        assert!(matches!(
+            check_golden_with(&square(Color::BLACK), &golden, &options, false),
+            Err(GoldenError::Missing(_))
+        ));
+        check_golden_with(&square(Color::BLACK), &golden, &options, true).unwrap();
+        check_golden_with(&square(Color::BLACK), &golden, &options, false).unwrap();
+
+        let result =
+            check_golden_with(&square(Color::rgb(1.0, 0.0, 0.0)), &golden, &options, false);
+        let Err(GoldenError::Mismatch {
+            mismatched,
+            diff_path,
+            ..
+        }) = result
+        else {
+            panic!("expected a mismatch, got {result:?}");
+        };
+        assert_eq!(mismatched, 16);
+        assert_eq!(diff_path, dir.join("square.diff.png"));
+        assert!(diff_path.exists());
+        assert!(dir.join("square.actual.png").exists());
+
+        let lenient = GoldenOptions {
+            max_mismatched_pixels: 16,
+            ..options
+        };
+        check_golden_with(&square(Color::rgb(1.0, 0.0, 0.0)), &golden, &lenient, false).unwrap();
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
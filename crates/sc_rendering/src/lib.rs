@@ -1,8 +1,14 @@
+#[cfg(feature = "cpu")]
+pub mod cpu;
 pub mod dirty_rect;
+#[cfg(feature = "golden")]
+pub mod golden;
 pub mod layer_cache;
 pub mod render_list;
 pub mod types;
 
+#[cfg(feature = "cpu")]
+pub use cpu::{CpuRenderError, TinySkiaRenderer};
 pub use dirty_rect::{DirtyRectTracker, DirtyType};
 pub use layer_cache::{CacheLayer, CacheState, LayerCache};
 pub use render_list::{RenderBackend, RenderItem, RenderList, RenderListBuilder, z_order};
@@ -36,6 +36,72 @@ pub fn default_hotkey_key() -> u32 {
     'S' as u32
 }
 
+// Image export defaults
+pub fn default_png_compression() -> u8 {
+    6
+}
+
+pub fn default_jpeg_quality() -> u8 {
+    90
+}
+
+pub fn default_webp_quality() -> u8 {
+    90
+}
+
+// Redaction defaults
+pub fn default_mosaic_block_size() -> u32 {
+    10
+}
+
+pub fn default_blur_radius() -> f32 {
+    12.0
+}
+
+// Highlighter defaults
+pub fn default_highlighter_width() -> f32 {
+    16.0
+}
+
+pub fn default_highlighter_color() -> (u8, u8, u8) {
+    (255, 235, 59)
+}
+
+pub fn default_highlighter_opacity() -> u8 {
+    110
+}
+
+// Fill defaults
+pub fn default_fill_color() -> (u8, u8, u8) {
+    (255, 0, 0)
+}
+
+pub fn default_fill_opacity() -> u8 {
+    77
+}
+
+// Stroke style defaults
+pub fn default_stroke_dash() -> String {
+    "solid".to_string()
+}
+
+pub fn default_stroke_cap() -> String {
+    "flat".to_string()
+}
+
+pub fn default_stroke_join() -> String {
+    "miter".to_string()
+}
+
+// Arrow defaults
+pub fn default_arrow_head() -> String {
+    "open".to_string()
+}
+
+pub fn default_arrow_tail() -> String {
+    "none".to_string()
+}
+
 // Font defaults
 pub fn default_font_name() -> String {
     "Microsoft Sans Serif".to_string()
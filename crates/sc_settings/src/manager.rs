@@ -105,11 +105,66 @@ impl ConfigManager {
         self.get().auto_copy
     }
 
+    #[inline]
+    pub fn show_cursor(&self) -> bool {
+        self.get().show_cursor
+    }
+
     #[inline]
     pub fn delay_ms(&self) -> u32 {
         self.get().delay_ms
     }
 
+    #[inline]
+    pub fn png_compression(&self) -> u8 {
+        self.get().png_compression
+    }
+
+    #[inline]
+    pub fn jpeg_quality(&self) -> u8 {
+        self.get().jpeg_quality
+    }
+
+    #[inline]
+    pub fn webp_quality(&self) -> u8 {
+        self.get().webp_quality
+    }
+
+    #[inline]
+    pub fn mosaic_block_size(&self) -> u32 {
+        self.get().mosaic_block_size
+    }
+
+    #[inline]
+    pub fn blur_radius(&self) -> f32 {
+        self.get().blur_radius
+    }
+
+    #[inline]
+    pub fn highlighter_width(&self) -> f32 {
+        self.get().highlighter_width
+    }
+
+    #[inline]
+    pub fn highlighter_color(&self) -> (u8, u8, u8) {
+        self.get().highlighter_color
+    }
+
+    #[inline]
+    pub fn highlighter_opacity(&self) -> u8 {
+        self.get().highlighter_opacity
+    }
+
+    #[inline]
+    pub fn fill_color(&self) -> (u8, u8, u8) {
+        self.get().fill_color
+    }
+
+    #[inline]
+    pub fn fill_opacity(&self) -> u8 {
+        self.get().fill_opacity
+    }
+
     #[inline]
     pub fn ocr_language(&self) -> String {
         self.get().ocr_language
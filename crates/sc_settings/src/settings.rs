@@ -57,6 +57,61 @@ pub struct Settings {
     pub hotkey_modifiers: u32,
     #[serde(default = "default_hotkey_key")]
     pub hotkey_key: u32,
+
+    // Image export settings
+    /// PNG compression level (0 = uncompressed, 1..=9).
+    #[serde(default = "default_png_compression")]
+    pub png_compression: u8,
+    /// JPEG quality (1..=100).
+    #[serde(default = "default_jpeg_quality")]
+    pub jpeg_quality: u8,
+    /// WebP quality (1..=99 lossy, 100 = lossless).
+    #[serde(default = "default_webp_quality")]
+    pub webp_quality: u8,
+
+    // Redaction settings
+    /// Mosaic cell size in pixels for newly drawn mosaic regions.
+    #[serde(default = "default_mosaic_block_size")]
+    pub mosaic_block_size: u32,
+    /// Gaussian radius in pixels for newly drawn blur regions.
+    #[serde(default = "default_blur_radius")]
+    pub blur_radius: f32,
+
+    // Highlighter settings
+    /// Stroke width in pixels for new highlighter strokes.
+    #[serde(default = "default_highlighter_width")]
+    pub highlighter_width: f32,
+    #[serde(default = "default_highlighter_color")]
+    pub highlighter_color: (u8, u8, u8),
+    /// Highlighter stroke alpha (0..=255).
+    #[serde(default = "default_highlighter_opacity")]
+    pub highlighter_opacity: u8,
+
+    // Fill settings
+    /// Colour used when Ctrl+Shift+F fills the selected shapes.
+    #[serde(default = "default_fill_color")]
+    pub fill_color: (u8, u8, u8),
+    /// Fill alpha (0..=255).
+    #[serde(default = "default_fill_opacity")]
+    pub fill_opacity: u8,
+
+    // Stroke style for new shapes and pen strokes
+    /// Dash pattern: "solid", "dash", "dot", "dash-dot", or comma-separated dash and gap
+    /// lengths in stroke widths.
+    #[serde(default = "default_stroke_dash")]
+    pub stroke_dash: String,
+    /// Line cap: "flat", "round" or "square".
+    #[serde(default = "default_stroke_cap")]
+    pub stroke_cap: String,
+    /// Line join: "miter", "round" or "bevel".
+    #[serde(default = "default_stroke_join")]
+    pub stroke_join: String,
+
+    // Markers for new arrows: "none", "open", "triangle", "dot" or "bar".
+    #[serde(default = "default_arrow_head")]
+    pub arrow_head: String,
+    #[serde(default = "default_arrow_tail")]
+    pub arrow_tail: String,
 }
 
 impl Default for Settings {
@@ -79,6 +134,26 @@ impl Default for Settings {
             hotkey_modifiers: default_hotkey_modifiers(),
             hotkey_key: default_hotkey_key(),
 
+            png_compression: default_png_compression(),
+            jpeg_quality: default_jpeg_quality(),
+            webp_quality: default_webp_quality(),
+
+            mosaic_block_size: default_mosaic_block_size(),
+            blur_radius: default_blur_radius(),
+
+            highlighter_width: default_highlighter_width(),
+            highlighter_color: default_highlighter_color(),
+            highlighter_opacity: default_highlighter_opacity(),
+
+            fill_color: default_fill_color(),
+            fill_opacity: default_fill_opacity(),
+
+            stroke_dash: default_stroke_dash(),
+            stroke_cap: default_stroke_cap(),
+            stroke_join: default_stroke_join(),
+            arrow_head: default_arrow_head(),
+            arrow_tail: default_arrow_tail(),
+
             font_name: default_font_name(),
             font_weight: default_font_weight(),
             font_italic: default_font_italic(),
@@ -264,6 +339,35 @@ mod tests {
         assert_eq!(settings.hotkey_key, original_key);
     }
 
+    #[test]
+    fn missing_export_fields_fall_back_to_defaults() {
+        let json = r#"{
+            "line_thickness": 3.0,
+            "font_size": 20.0,
+            "auto_copy": false,
+            "show_cursor": false,
+            "delay_ms": 0
+        }"#;
+
+        let settings: Settings = serde_json::from_str(json).unwrap();
+
+        assert_eq!(settings.png_compression, 6);
+        assert_eq!(settings.jpeg_quality, 90);
+        assert_eq!(settings.webp_quality, 90);
+        assert_eq!(settings.mosaic_block_size, 10);
+        assert_eq!(settings.blur_radius, 12.0);
+        assert_eq!(settings.highlighter_width, 16.0);
+        assert_eq!(settings.highlighter_color, (255, 235, 59));
+        assert_eq!(settings.highlighter_opacity, 110);
+        assert_eq!(settings.fill_color, (255, 0, 0));
+        assert_eq!(settings.fill_opacity, 77);
+        assert_eq!(settings.stroke_dash, "solid");
+        assert_eq!(settings.stroke_cap, "flat");
+        assert_eq!(settings.stroke_join, "miter");
+        assert_eq!(settings.arrow_head, "open");
+        assert_eq!(settings.arrow_tail, "none");
+    }
+
     #[test]
     fn get_hotkey_string_formats_known_key() {
         let settings = Settings {
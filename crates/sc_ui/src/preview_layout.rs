@@ -38,14 +38,24 @@ pub const ICON_TOOL_SQUARE: &str = "square";
 pub const ICON_TOOL_CIRCLE: &str = "circle";
 pub const ICON_TOOL_ARROW: &str = "move-up-right";
 pub const ICON_TOOL_PEN: &str = "pen";
+pub const ICON_TOOL_HIGHLIGHTER: &str = "highlighter";
 pub const ICON_TOOL_TEXT: &str = "type";
+pub const ICON_TOOL_MOSAIC: &str = "mosaic";
+pub const ICON_TOOL_BLUR: &str = "blur";
+pub const ICON_TOOL_STEP: &str = "step";
+pub const ICON_TOOL_CALLOUT: &str = "callout";
 
-pub const PREVIEW_DRAWING_TOOL_ICONS: [&str; 5] = [
+pub const PREVIEW_DRAWING_TOOL_ICONS: [&str; 10] = [
     ICON_TOOL_SQUARE,
     ICON_TOOL_CIRCLE,
     ICON_TOOL_ARROW,
     ICON_TOOL_PEN,
+    ICON_TOOL_HIGHLIGHTER,
     ICON_TOOL_TEXT,
+    ICON_TOOL_MOSAIC,
+    ICON_TOOL_BLUR,
+    ICON_TOOL_STEP,
+    ICON_TOOL_CALLOUT,
 ];
 
 pub fn create_left_icons() -> Vec<PreviewIconLayout> {
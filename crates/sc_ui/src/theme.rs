@@ -57,7 +57,7 @@ pub mod preview {
 pub mod settings {
     // Default window size.
     pub const WINDOW_DEFAULT_WIDTH: i32 = 520;
-    pub const WINDOW_DEFAULT_HEIGHT: i32 = 430;
+    pub const WINDOW_DEFAULT_HEIGHT: i32 = 610;
 
     // Layout constants.
     pub const MARGIN: i32 = 16;
@@ -90,4 +90,6 @@ pub mod settings {
     pub const PATH_BUTTON_GAP: i32 = 8;
     pub const OCR_LANGUAGE_WIDTH: i32 = 180;
     pub const OCR_LANGUAGE_DROPDOWN_HEIGHT: i32 = 200;
+    pub const COMBO_WIDTH: i32 = 112;
+    pub const COMBO_DROPDOWN_HEIGHT: i32 = 160;
 }
@@ -10,7 +10,12 @@ pub enum ToolbarButton {
     Circle,
     Arrow,
     Pen,
+    Highlighter,
     Text,
+    Mosaic,
+    Blur,
+    Step,
+    Callout,
     Undo,
     ExtractText,
     ScrollCapture,
@@ -24,12 +29,17 @@ pub enum ToolbarButton {
 
 /// Deterministic ordering of buttons in the toolbar.
 /// Keep this aligned with the legacy host toolbar ordering.
-pub const TOOLBAR_BUTTONS: [ToolbarButton; 13] = [
+pub const TOOLBAR_BUTTONS: [ToolbarButton; 18] = [
     ToolbarButton::Rectangle,
     ToolbarButton::Circle,
     ToolbarButton::Arrow,
     ToolbarButton::Pen,
+    ToolbarButton::Highlighter,
     ToolbarButton::Text,
+    ToolbarButton::Mosaic,
+    ToolbarButton::Blur,
+    ToolbarButton::Step,
+    ToolbarButton::Callout,
     ToolbarButton::Undo,
     ToolbarButton::ExtractText,
     ToolbarButton::ScrollCapture,
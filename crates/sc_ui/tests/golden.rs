@@ -0,0 +1,90 @@
+//! Golden images for the neutral render lists. Regenerate with `SC_UPDATE_GOLDEN=1`.
+
+use std::path::PathBuf;
+
+use sc_rendering::Color;
+use sc_rendering::golden::{GoldenOptions, assert_golden, rasterize};
+use sc_ui::selection_overlay::{
+    RectI32, SelectionOverlayStyle, build_selection_overlay_render_list_with_style,
+};
+use sc_ui::toolbar::{ToolbarButton, ToolbarStyle, build_toolbar_view};
+
+const SCREEN: (i32, i32) = (640, 240);
+
+/// Mid-grey stand-in for the screenshot, so both dark and light UI stays visible.
+const BACKDROP: Color = Color::rgb(0.55, 0.6, 0.65);
+
+fn golden(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.png"))
+}
+
+fn selection() -> RectI32 {
+    RectI32 {
+        left: 40,
+        top: 20,
+        right: 600,
+        bottom: 170,
+    }
+}
+
+#[test]
+fn selection_overlay_with_handles() {
+    let mut list = build_selection_overlay_render_list_with_style(
+        SCREEN,
+        Some(selection()),
+        true,
+        false,
+        false,
+        &SelectionOverlayStyle::default(),
+    )
+    .unwrap();
+
+    let image = rasterize(&mut list, SCREEN.0 as u32, SCREEN.1 as u32, BACKDROP).unwrap();
+    assert_golden(
+        &image,
+        golden("selection_overlay_handles"),
+        &GoldenOptions::default(),
+    );
+}
+
+#[test]
+fn selection_overlay_auto_highlight() {
+    let mut list = build_selection_overlay_render_list_with_style(
+        SCREEN,
+        Some(selection()),
+        false,
+        false,
+        true,
+        &SelectionOverlayStyle::default(),
+    )
+    .unwrap();
+
+    let image = rasterize(&mut list, SCREEN.0 as u32, SCREEN.1 as u32, BACKDROP).unwrap();
+    assert_golden(
+        &image,
+        golden("selection_overlay_auto_highlight"),
+        &GoldenOptions::default(),
+    );
+}
+
+#[test]
+fn toolbar_with_hovered_button() {
+    let mut view = build_toolbar_view(
+        SCREEN,
+        Some(selection()),
+        ToolbarButton::Arrow,
+        &ToolbarStyle::default(),
+    )
+    .unwrap();
+
+    let image = rasterize(
+        &mut view.background,
+        SCREEN.0 as u32,
+        SCREEN.1 as u32,
+        BACKDROP,
+    )
+    .unwrap();
+    assert_golden(&image, golden("toolbar_hover"), &GoldenOptions::default());
+}
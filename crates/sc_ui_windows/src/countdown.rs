@@ -0,0 +1,166 @@
+use std::cell::Cell;
+
+use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Gdi::*;
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+thread_local! {
+    static COUNTDOWN_HWND: Cell<HWND> = const { Cell::new(HWND(std::ptr::null_mut())) };
+}
+
+/// Side of the square countdown badge, in pixels.
+const COUNTDOWN_SIZE: i32 = 120;
+/// Badge opacity (0-255).
+const COUNTDOWN_ALPHA: u8 = 200;
+
+/// Click-through badge in the middle of the primary screen showing the seconds left before a
+/// delayed capture.
+///
+/// The badge never takes focus, so hover menus and tooltips stay open underneath it, and it is
+/// excluded from capture in case a grab happens while it is still on screen.
+pub struct CaptureCountdownWindow;
+
+impl CaptureCountdownWindow {
+    /// Show the badge, or update the number if it is already visible.
+    pub fn show(seconds: u32) {
+        let existing = COUNTDOWN_HWND.with(Cell::get);
+        if !existing.0.is_null() && unsafe { IsWindow(Some(existing)) }.as_bool() {
+            unsafe {
+                SetWindowLongPtrW(existing, GWLP_USERDATA, seconds as isize);
+                let _ = InvalidateRect(Some(existing), None, false);
+                let _ = UpdateWindow(existing);
+            }
+            return;
+        }
+
+        unsafe {
+            let instance = match GetModuleHandleW(None) {
+                Ok(instance) => instance,
+                Err(_) => return,
+            };
+            let class_name = windows::core::w!("CaptureCountdown");
+            let class = WNDCLASSW {
+                lpfnWndProc: Some(countdown_window_proc),
+                hInstance: instance.into(),
+                lpszClassName: class_name,
+                hCursor: LoadCursorW(None, IDC_ARROW).unwrap_or_default(),
+                style: CS_HREDRAW | CS_VREDRAW,
+                ..Default::default()
+            };
+            if RegisterClassW(&class) == 0 && GetLastError().0 != 1410 {
+                return;
+            }
+
+            let x = (GetSystemMetrics(SM_CXSCREEN) - COUNTDOWN_SIZE) / 2;
+            let y = (GetSystemMetrics(SM_CYSCREEN) - COUNTDOWN_SIZE) / 2;
+            let hwnd = match CreateWindowExW(
+                WS_EX_TOOLWINDOW
+                    | WS_EX_TOPMOST
+                    | WS_EX_NOACTIVATE
+                    | WS_EX_TRANSPARENT
+                    | WS_EX_LAYERED,
+                class_name,
+                windows::core::w!("延时截图倒计时"),
+                WS_POPUP,
+                x,
+                y,
+                COUNTDOWN_SIZE,
+                COUNTDOWN_SIZE,
+                None,
+                None,
+                Some(instance.into()),
+                None,
+            ) {
+                Ok(hwnd) => hwnd,
+                Err(_) => return,
+            };
+            SetWindowLongPtrW(hwnd, GWLP_USERDATA, seconds as isize);
+            let _ = SetLayeredWindowAttributes(hwnd, COLORREF(0), COUNTDOWN_ALPHA, LWA_ALPHA);
+            let _ = SetWindowDisplayAffinity(hwnd, WDA_EXCLUDEFROMCAPTURE);
+            COUNTDOWN_HWND.with(|slot| slot.set(hwnd));
+            let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+            let _ = UpdateWindow(hwnd);
+        }
+    }
+
+    /// Remove the badge if it is showing.
+    pub fn close() {
+        let hwnd = COUNTDOWN_HWND.with(|slot| slot.replace(HWND(std::ptr::null_mut())));
+        if !hwnd.0.is_null() {
+            unsafe {
+                let _ = DestroyWindow(hwnd);
+            }
+        }
+    }
+}
+
+unsafe extern "system" fn countdown_window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_ERASEBKGND => LRESULT(1),
+        WM_PAINT => {
+            let mut paint = PAINTSTRUCT::default();
+            let dc = unsafe { BeginPaint(hwnd, &mut paint) };
+            let mut client = RECT::default();
+            let _ = unsafe { GetClientRect(hwnd, &mut client) };
+            let brush = unsafe { CreateSolidBrush(COLORREF(0x00302820)) };
+            let _ = unsafe { FillRect(dc, &client, brush) };
+            let _ = unsafe { DeleteObject(brush.into()) };
+
+            let font_name: Vec<u16> = "Microsoft YaHei\0".encode_utf16().collect();
+            let font = unsafe {
+                CreateFontW(
+                    -72,
+                    0,
+                    0,
+                    0,
+                    FW_BOLD.0 as i32,
+                    0,
+                    0,
+                    0,
+                    DEFAULT_CHARSET,
+                    OUT_DEFAULT_PRECIS,
+                    CLIP_DEFAULT_PRECIS,
+                    CLEARTYPE_QUALITY,
+                    (DEFAULT_PITCH.0 | FF_DONTCARE.0) as u32,
+                    windows::core::PCWSTR(font_name.as_ptr()),
+                )
+            };
+            let old_font = (!font.is_invalid()).then(|| unsafe { SelectObject(dc, font.into()) });
+            let seconds = unsafe { GetWindowLongPtrW(hwnd, GWLP_USERDATA) };
+            let mut text: Vec<u16> = seconds.to_string().encode_utf16().collect();
+            unsafe {
+                SetBkMode(dc, TRANSPARENT);
+                SetTextColor(dc, COLORREF(0x00ffffff));
+                let _ = DrawTextW(
+                    dc,
+                    &mut text,
+                    &mut client,
+                    DT_CENTER | DT_VCENTER | DT_SINGLELINE | DT_NOPREFIX,
+                );
+            }
+            if let Some(old_font) = old_font {
+                unsafe {
+                    SelectObject(dc, old_font);
+                    let _ = DeleteObject(font.into());
+                }
+            }
+            let _ = unsafe { EndPaint(hwnd, &paint) };
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            COUNTDOWN_HWND.with(|slot| {
+                if slot.get() == hwnd {
+                    slot.set(HWND(std::ptr::null_mut()));
+                }
+            });
+            LRESULT(0)
+        }
+        _ => unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) },
+    }
+}
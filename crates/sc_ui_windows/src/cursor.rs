@@ -67,7 +67,7 @@ impl CursorManager {
                 }
 
                 if element.contains_point(x, y) {
-                    if element.tool == DrawingTool::Text {
+                    if element.tool.is_text() {
                         return CursorIcon::Arrow;
                     }
                     return CursorIcon::SizeAll;
@@ -77,9 +77,13 @@ impl CursorManager {
             if matches!(
                 ctx.current_tool,
                 DrawingTool::Pen
+                    | DrawingTool::Highlighter
                     | DrawingTool::Rectangle
                     | DrawingTool::Circle
                     | DrawingTool::Arrow
+                    | DrawingTool::Mosaic
+                    | DrawingTool::Blur
+                    | DrawingTool::Step
             ) {
                 return if inside_selection {
                     CursorIcon::Crosshair
@@ -87,7 +91,7 @@ impl CursorManager {
                     CursorIcon::NotAllowed
                 };
             }
-            if matches!(ctx.current_tool, DrawingTool::Text) {
+            if ctx.current_tool.is_text() {
                 return if inside_selection {
                     CursorIcon::IBeam
                 } else {
@@ -116,6 +120,8 @@ impl CursorManager {
                 Some(CursorIcon::SizeWE)
             }
             DragMode::Moving => Some(CursorIcon::SizeAll),
+            DragMode::Rotating => Some(CursorIcon::Hand),
+            DragMode::BendingArrow | DragMode::MovingCalloutTail => Some(CursorIcon::SizeAll),
             _ => None,
         }
     }
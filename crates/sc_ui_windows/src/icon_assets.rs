@@ -48,10 +48,30 @@ pub fn preview_icon_svg(name: &str) -> Option<&'static str> {
             env!("CARGO_MANIFEST_DIR"),
             "/../../apps/sc_windows/icons/pen.svg"
         ))),
+        preview_layout::ICON_TOOL_HIGHLIGHTER => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/highlighter.svg"
+        ))),
         preview_layout::ICON_TOOL_TEXT => Some(include_str!(concat!(
             env!("CARGO_MANIFEST_DIR"),
             "/../../apps/sc_windows/icons/type.svg"
         ))),
+        preview_layout::ICON_TOOL_MOSAIC => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/mosaic.svg"
+        ))),
+        preview_layout::ICON_TOOL_BLUR => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/blur.svg"
+        ))),
+        preview_layout::ICON_TOOL_STEP => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/step.svg"
+        ))),
+        preview_layout::ICON_TOOL_CALLOUT => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/callout.svg"
+        ))),
         _ => None,
     }
 }
@@ -74,10 +94,30 @@ pub fn toolbar_icon_svg(button: ToolbarButton) -> Option<&'static str> {
             env!("CARGO_MANIFEST_DIR"),
             "/../../apps/sc_windows/icons/pen.svg"
         ))),
+        ToolbarButton::Highlighter => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/highlighter.svg"
+        ))),
         ToolbarButton::Text => Some(include_str!(concat!(
             env!("CARGO_MANIFEST_DIR"),
             "/../../apps/sc_windows/icons/type.svg"
         ))),
+        ToolbarButton::Mosaic => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/mosaic.svg"
+        ))),
+        ToolbarButton::Blur => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/blur.svg"
+        ))),
+        ToolbarButton::Step => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/step.svg"
+        ))),
+        ToolbarButton::Callout => Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../apps/sc_windows/icons/callout.svg"
+        ))),
         ToolbarButton::Undo => Some(include_str!(concat!(
             env!("CARGO_MANIFEST_DIR"),
             "/../../apps/sc_windows/icons/undo-2.svg"
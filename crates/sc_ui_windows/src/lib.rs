@@ -1,4 +1,5 @@
 pub mod constants;
+pub mod countdown;
 pub mod cursor;
 pub mod icon_assets;
 pub mod preview;
@@ -16,6 +17,7 @@ use sc_host_protocol::{Command, UIMessage};
 use sc_platform_windows::windows::Direct2DRenderer;
 use sc_ui::selection_overlay::build_selection_overlay_render_list;
 
+pub use countdown::CaptureCountdownWindow;
 pub use cursor::CursorManager;
 pub use preview::PreviewWindow;
 pub use sc_ui::toolbar::ToolbarButton;
@@ -168,7 +170,12 @@ impl UIManager {
             DrawingTool::Circle => ToolbarButton::Circle,
             DrawingTool::Arrow => ToolbarButton::Arrow,
             DrawingTool::Pen => ToolbarButton::Pen,
+            DrawingTool::Highlighter => ToolbarButton::Highlighter,
             DrawingTool::Text => ToolbarButton::Text,
+            DrawingTool::Mosaic => ToolbarButton::Mosaic,
+            DrawingTool::Blur => ToolbarButton::Blur,
+            DrawingTool::Step => ToolbarButton::Step,
+            DrawingTool::Callout => ToolbarButton::Callout,
             DrawingTool::None => ToolbarButton::None,
         };
 
@@ -1,8 +1,11 @@
+use std::sync::Arc;
+
 use sc_app::selection::RectI32;
 use sc_drawing::Rect as DrawingRect;
+use sc_drawing::{PixelBuffer, RedactionSource};
 use sc_drawing_host::{DrawingConfig, DrawingManager, DrawingTool};
 use sc_host_protocol::{Command, DrawingMessage};
-use sc_platform::{HostPlatform, WindowId};
+use sc_platform::{HostPlatform, Modifiers, WindowId};
 use sc_platform_windows::windows::WindowsHostPlatform;
 
 pub struct PreviewDrawingState {
@@ -10,6 +13,8 @@ pub struct PreviewDrawingState {
     pub image_area_rect: RectI32,
     /// Preview window id.
     window: WindowId,
+    /// Pinned image pixels that redaction elements sample from.
+    source_image: Option<Arc<PixelBuffer>>,
 }
 
 impl PreviewDrawingState {
@@ -26,11 +31,31 @@ impl PreviewDrawingState {
                 bottom: 0,
             },
             window,
+            source_image: None,
         })
     }
 
     pub fn set_image_area(&mut self, rect: RectI32) {
+        // A new source rebuilds every redaction patch, so only make one when the area moves.
+        if rect == self.image_area_rect {
+            return;
+        }
         self.image_area_rect = rect;
+        self.update_redaction_source();
+    }
+
+    pub fn set_source_image(&mut self, image: PixelBuffer) {
+        self.source_image = Some(Arc::new(image));
+        self.update_redaction_source();
+    }
+
+    fn update_redaction_source(&mut self) {
+        let bounds = self.image_area_rect_drawing();
+        let source = self
+            .source_image
+            .clone()
+            .map(|image| RedactionSource::new(image, bounds));
+        self.manager.set_redaction_source(source);
     }
 
     fn image_area_rect_drawing(&self) -> DrawingRect {
@@ -63,20 +88,24 @@ impl PreviewDrawingState {
             .handle_message(DrawingMessage::SelectTool(new_tool));
     }
 
-    pub fn handle_mouse_down(&mut self, x: i32, y: i32) -> bool {
+    pub fn handle_mouse_down(&mut self, x: i32, y: i32, modifiers: Modifiers) -> bool {
         if !self.is_in_image_area(x, y) {
             return false;
         }
 
         let image_area_rect = self.image_area_rect_drawing();
-        let (commands, consumed) = self.manager.handle_mouse_down(x, y, Some(image_area_rect));
+        let (commands, consumed) =
+            self.manager
+                .handle_mouse_down(x, y, Some(image_area_rect), modifiers);
         self.process_commands(&commands);
         consumed
     }
 
-    pub fn handle_mouse_move(&mut self, x: i32, y: i32) -> bool {
+    pub fn handle_mouse_move(&mut self, x: i32, y: i32, modifiers: Modifiers) -> bool {
         let image_area_rect = self.image_area_rect_drawing();
-        let (commands, consumed) = self.manager.handle_mouse_move(x, y, Some(image_area_rect));
+        let (commands, consumed) =
+            self.manager
+                .handle_mouse_move(x, y, Some(image_area_rect), modifiers);
         self.process_commands(&commands);
         consumed
     }
@@ -99,8 +128,8 @@ impl PreviewDrawingState {
         !commands.is_empty()
     }
 
-    pub fn handle_key_input(&mut self, key_code: u32) -> bool {
-        let commands = self.manager.handle_key_input(key_code);
+    pub fn handle_key_input(&mut self, key_code: u32, modifiers: Modifiers) -> bool {
+        let commands = self.manager.handle_key_input(key_code, modifiers);
         self.process_commands(&commands);
         !commands.is_empty()
     }
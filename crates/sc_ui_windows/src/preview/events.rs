@@ -5,6 +5,7 @@ use windows::Win32::UI::Input::KeyboardAndMouse::*;
 use windows::Win32::UI::WindowsAndMessaging::*;
 
 use sc_platform::HostPlatform;
+use sc_platform_windows::current_modifiers;
 use sc_platform_windows::windows::{WindowsHostPlatform, window_id as to_window_id};
 
 use super::hit_test::{icon_contains_click_point, update_icon_hover_states};
@@ -244,17 +245,44 @@ impl PreviewWindowState {
                                         let _ = platform.request_redraw(window_id);
                                         return LRESULT(0);
                                     }
+                                    preview_layout::ICON_TOOL_HIGHLIGHTER => {
+                                        window.switch_drawing_tool(
+                                            sc_drawing_host::DrawingTool::Highlighter,
+                                        );
+                                        let _ = platform.request_redraw(window_id);
+                                        return LRESULT(0);
+                                    }
                                     preview_layout::ICON_TOOL_TEXT => {
                                         window.switch_drawing_tool(sc_drawing_host::DrawingTool::Text);
                                         let _ = platform.request_redraw(window_id);
                                         return LRESULT(0);
                                     }
+                                    preview_layout::ICON_TOOL_MOSAIC => {
+                                        window.switch_drawing_tool(sc_drawing_host::DrawingTool::Mosaic);
+                                        let _ = platform.request_redraw(window_id);
+                                        return LRESULT(0);
+                                    }
+                                    preview_layout::ICON_TOOL_BLUR => {
+                                        window.switch_drawing_tool(sc_drawing_host::DrawingTool::Blur);
+                                        let _ = platform.request_redraw(window_id);
+                                        return LRESULT(0);
+                                    }
+                                    preview_layout::ICON_TOOL_STEP => {
+                                        window.switch_drawing_tool(sc_drawing_host::DrawingTool::Step);
+                                        let _ = platform.request_redraw(window_id);
+                                        return LRESULT(0);
+                                    }
+                                    preview_layout::ICON_TOOL_CALLOUT => {
+                                        window.switch_drawing_tool(sc_drawing_host::DrawingTool::Callout);
+                                        let _ = platform.request_redraw(window_id);
+                                        return LRESULT(0);
+                                    }
                                     _ => {}
                                 }
                             }
                         }
 
-                        if let Some(ds) = window.drawing_state.as_mut() && ds.handle_mouse_down(x, y) {
+                        if let Some(ds) = window.drawing_state.as_mut() && ds.handle_mouse_down(x, y, current_modifiers()) {
                             let _ = SetCapture(hwnd);
                             return LRESULT(0);
                         }
@@ -334,7 +362,7 @@ impl PreviewWindowState {
                             needs_repaint = true;
                         }
 
-                        if let Some(ds) = window.drawing_state.as_mut() && ds.handle_mouse_move(x, y) {
+                        if let Some(ds) = window.drawing_state.as_mut() && ds.handle_mouse_move(x, y, current_modifiers()) {
                             needs_repaint = true;
                         }
 
@@ -474,7 +502,7 @@ impl PreviewWindowState {
 
                         if let Some(ds) = window.drawing_state.as_mut()
                             && ds.is_text_editing()
-                            && ds.handle_key_input(vk)
+                            && ds.handle_key_input(vk, current_modifiers())
                         {
                             return LRESULT(0);
                         }
@@ -528,6 +556,13 @@ impl PreviewWindowState {
                                 _ => {}
                             }
                         }
+
+                        // Annotation shortcuts (copy/paste/duplicate, delete, grouping, ...).
+                        if let Some(ds) = window.drawing_state.as_mut()
+                            && ds.handle_key_input(vk, current_modifiers())
+                        {
+                            return LRESULT(0);
+                        }
                     }
                     DefWindowProcW(hwnd, msg, wparam, lparam)
                 }
@@ -124,7 +124,12 @@ impl PreviewRenderer {
             preview_layout::ICON_TOOL_CIRCLE,
             preview_layout::ICON_TOOL_ARROW,
             preview_layout::ICON_TOOL_PEN,
+            preview_layout::ICON_TOOL_HIGHLIGHTER,
             preview_layout::ICON_TOOL_TEXT,
+            preview_layout::ICON_TOOL_MOSAIC,
+            preview_layout::ICON_TOOL_BLUR,
+            preview_layout::ICON_TOOL_STEP,
+            preview_layout::ICON_TOOL_CALLOUT,
         ];
 
         for name in icons.iter() {
@@ -157,7 +162,12 @@ impl PreviewRenderer {
                     | preview_layout::ICON_TOOL_CIRCLE
                     | preview_layout::ICON_TOOL_ARROW
                     | preview_layout::ICON_TOOL_PEN
+                    | preview_layout::ICON_TOOL_HIGHLIGHTER
                     | preview_layout::ICON_TOOL_TEXT
+                    | preview_layout::ICON_TOOL_MOSAIC
+                    | preview_layout::ICON_TOOL_BLUR
+                    | preview_layout::ICON_TOOL_STEP
+                    | preview_layout::ICON_TOOL_CALLOUT
             );
 
             let (active_normal, active_hover) = if supports_active_color {
@@ -265,7 +275,12 @@ impl PreviewRenderer {
                         | preview_layout::ICON_TOOL_CIRCLE
                         | preview_layout::ICON_TOOL_ARROW
                         | preview_layout::ICON_TOOL_PEN
+                        | preview_layout::ICON_TOOL_HIGHLIGHTER
                         | preview_layout::ICON_TOOL_TEXT
+                        | preview_layout::ICON_TOOL_MOSAIC
+                        | preview_layout::ICON_TOOL_BLUR
+                        | preview_layout::ICON_TOOL_STEP
+                        | preview_layout::ICON_TOOL_CALLOUT
                 )
             };
 
@@ -13,7 +13,9 @@ use windows::Win32::UI::HiDpi::{
 use windows::Win32::UI::WindowsAndMessaging::*;
 
 use sc_app::selection::RectI32;
-use sc_drawing_host::{DragMode, DrawingConfig, DrawingTool};
+use sc_drawing::{ChannelOrder, PixelBuffer, Point, Rect};
+use sc_drawing_host::{DragMode, DrawingConfig, DrawingElement, DrawingTool};
+use sc_export::{EncodeOptions, ImageFormat, Project};
 use sc_ocr::OcrResult;
 use sc_platform::{CursorIcon, HostPlatform, WindowId};
 use sc_platform_windows::windows::WindowsHostPlatform;
@@ -100,6 +102,37 @@ impl PreviewWindow {
             is_pin_mode,
             drawing_config,
             ocr_source_bmp_data,
+            vec![],
+        )
+    }
+
+    /// Reopen a saved project; its elements stay editable on top of the base image.
+    pub fn show_project(project: Project, drawing_config: DrawingConfig) -> Result<()> {
+        // Loaded projects are always RGBA, which is what `encode_rgba` expects.
+        let image = &project.image;
+        let bmp = sc_export::encode_rgba(
+            &image.pixels,
+            image.width,
+            image.height,
+            ImageFormat::Bmp,
+            &EncodeOptions::default(),
+        )?;
+        let selection_rect = RectI32 {
+            left: 0,
+            top: 0,
+            right: image.width as i32,
+            bottom: image.height as i32,
+        };
+
+        // Pin mode keeps the image unscaled, so project coordinates map 1:1.
+        PreviewWindowState::show(
+            bmp,
+            vec![],
+            selection_rect,
+            true,
+            drawing_config,
+            None,
+            project.elements,
         )
     }
 }
@@ -218,6 +251,8 @@ impl PreviewWindowState {
                 Some(CursorIcon::SizeWE)
             }
             DragMode::Moving => Some(CursorIcon::SizeAll),
+            DragMode::Rotating => Some(CursorIcon::Hand),
+            DragMode::BendingArrow | DragMode::MovingCalloutTail => Some(CursorIcon::SizeAll),
             _ => None,
         }
     }
@@ -287,7 +322,7 @@ impl PreviewWindowState {
 
                 if element.contains_point(x, y) {
                     // Text is draggable but we keep arrow to avoid implying edit-on-click.
-                    return if element.tool == DrawingTool::Text {
+                    return if element.tool.is_text() {
                         CursorIcon::Arrow
                     } else {
                         CursorIcon::SizeAll
@@ -298,16 +333,20 @@ impl PreviewWindowState {
             // 3.4) Tool cursor inside image area
             match manager.get_current_tool() {
                 DrawingTool::Pen
+                | DrawingTool::Highlighter
                 | DrawingTool::Rectangle
                 | DrawingTool::Circle
-                | DrawingTool::Arrow => {
+                | DrawingTool::Arrow
+                | DrawingTool::Mosaic
+                | DrawingTool::Blur
+                | DrawingTool::Step => {
                     return if inside_image {
                         CursorIcon::Crosshair
                     } else {
                         CursorIcon::Arrow
                     };
                 }
-                DrawingTool::Text => {
+                DrawingTool::Text | DrawingTool::Callout => {
                     return if inside_image {
                         CursorIcon::IBeam
                     } else {
@@ -371,7 +410,12 @@ impl PreviewWindowState {
                     | preview_layout::ICON_TOOL_CIRCLE
                     | preview_layout::ICON_TOOL_ARROW
                     | preview_layout::ICON_TOOL_PEN
+                    | preview_layout::ICON_TOOL_HIGHLIGHTER
                     | preview_layout::ICON_TOOL_TEXT
+                    | preview_layout::ICON_TOOL_MOSAIC
+                    | preview_layout::ICON_TOOL_BLUR
+                    | preview_layout::ICON_TOOL_STEP
+                    | preview_layout::ICON_TOOL_CALLOUT
             );
 
             if is_drawing_tool_icon {
@@ -381,7 +425,15 @@ impl PreviewWindowState {
                         | (preview_layout::ICON_TOOL_CIRCLE, DrawingTool::Circle)
                         | (preview_layout::ICON_TOOL_ARROW, DrawingTool::Arrow)
                         | (preview_layout::ICON_TOOL_PEN, DrawingTool::Pen)
+                        | (
+                            preview_layout::ICON_TOOL_HIGHLIGHTER,
+                            DrawingTool::Highlighter
+                        )
                         | (preview_layout::ICON_TOOL_TEXT, DrawingTool::Text)
+                        | (preview_layout::ICON_TOOL_MOSAIC, DrawingTool::Mosaic)
+                        | (preview_layout::ICON_TOOL_BLUR, DrawingTool::Blur)
+                        | (preview_layout::ICON_TOOL_STEP, DrawingTool::Step)
+                        | (preview_layout::ICON_TOOL_CALLOUT, DrawingTool::Callout)
                 );
                 continue;
             }
@@ -575,8 +627,7 @@ impl PreviewWindowState {
         let window_id = self.window_id();
         let platform = WindowsHostPlatform::new();
 
-        // NOTE: current export format is BMP bytes.
-        let file_path = match platform.show_image_save_dialog(window_id, "screenshot.bmp") {
+        let file_path = match platform.show_image_save_dialog(window_id, "screenshot.png") {
             Ok(path) => path,
             Err(e) => {
                 let msg = format!("无法打开保存对话框: {e}");
@@ -590,6 +641,22 @@ impl PreviewWindowState {
             return;
         };
 
+        if sc_export::is_project_path(&file_path) {
+            if let Err(e) = self.save_project(&file_path) {
+                let msg = format!("写入工程失败: {e}");
+                platform.show_error_message(window_id, "保存失败", &msg);
+            }
+            return;
+        }
+
+        if sc_export::is_svg_path(&file_path) {
+            if let Err(e) = self.save_svg(&file_path) {
+                let msg = format!("写入 SVG 失败: {e}");
+                platform.show_error_message(window_id, "保存失败", &msg);
+            }
+            return;
+        }
+
         let image_area_rect = self
             .drawing_state
             .as_ref()
@@ -624,12 +691,57 @@ impl PreviewWindowState {
                 }
             };
 
-        if let Err(e) = std::fs::write(&file_path, &bmp_data) {
+        let settings = Settings::load();
+        let options = EncodeOptions {
+            png_compression: settings.png_compression,
+            jpeg_quality: settings.jpeg_quality,
+            webp_quality: settings.webp_quality,
+        };
+        if let Err(e) = sc_export::save_bmp_to_file(&bmp_data, &file_path, &options) {
             let msg = format!("写入文件失败: {e}");
             platform.show_error_message(window_id, "保存失败", &msg);
         }
     }
 
+    /// The unannotated image plus its elements in image pixel coordinates.
+    fn editable_content(&self) -> Result<(PixelBuffer, Vec<DrawingElement>)> {
+        let image = PixelBuffer::from_raw(
+            self.image_width as u32,
+            self.image_height as u32,
+            ChannelOrder::Rgba,
+            self.image_pixels.clone(),
+        )
+        .ok_or_else(|| anyhow::anyhow!("图像数据无效"))?;
+
+        let elements = match &self.drawing_state {
+            Some(ds) => {
+                let area = ds.image_area_rect;
+                let scale_x = self.image_width as f32 / (area.right - area.left).max(1) as f32;
+                let scale_y = self.image_height as f32 / (area.bottom - area.top).max(1) as f32;
+                ds.manager
+                    .export_elements(Point::new(area.left, area.top))
+                    .into_iter()
+                    .map(|element| scale_element(element, scale_x, scale_y))
+                    .collect()
+            }
+            None => vec![],
+        };
+
+        Ok((image, elements))
+    }
+
+    fn save_project(&self, path: &str) -> Result<()> {
+        let (image, elements) = self.editable_content()?;
+        Project::new(image, elements).save(path)?;
+        Ok(())
+    }
+
+    fn save_svg(&self, path: &str) -> Result<()> {
+        let (image, elements) = self.editable_content()?;
+        sc_export::save_svg(&image, &elements, path)?;
+        Ok(())
+    }
+
     fn center_icons_with<F>(&mut self, should_center: F)
     where
         F: Fn(&SvgIcon) -> bool,
@@ -691,6 +803,7 @@ impl PreviewWindowState {
         is_pin_mode: bool,
         drawing_config: DrawingConfig,
         ocr_source_bmp_data: Option<Vec<u8>>,
+        elements: Vec<DrawingElement>,
     ) -> Result<()> {
         unsafe {
             let _ = SetProcessDpiAwareness(PROCESS_PER_MONITOR_DPI_AWARE);
@@ -810,7 +923,17 @@ impl PreviewWindowState {
             let svg_icons = Self::build_icon_set(window_width, false);
 
             let window_id = sc_platform_windows::windows::window_id(hwnd);
-            let drawing_state = PreviewDrawingState::new(window_id, drawing_config).ok();
+            let mut drawing_state = PreviewDrawingState::new(window_id, drawing_config).ok();
+            if let Some(ds) = drawing_state.as_mut()
+                && let Some(image) = PixelBuffer::from_raw(
+                    actual_width as u32,
+                    actual_height as u32,
+                    ChannelOrder::Rgba,
+                    image_pixels.clone(),
+                )
+            {
+                ds.set_source_image(image);
+            }
 
             let mut window = Self {
                 hwnd,
@@ -855,6 +978,14 @@ impl PreviewWindowState {
             window.center_icons();
             window.recalculate_layout();
 
+            if !elements.is_empty()
+                && let Some(ds) = window.drawing_state.as_mut()
+            {
+                let area = ds.image_area_rect;
+                ds.manager
+                    .load_elements(elements, Point::new(area.left, area.top));
+            }
+
             if let Some(renderer) = &mut window.renderer {
                 let _ = renderer.initialize(window_id, window_width, window_height);
                 let _ = renderer.set_image_from_pixels(
@@ -876,3 +1007,28 @@ impl PreviewWindowState {
         }
     }
 }
+
+/// Map an element from the (possibly shrunk) image area back to image pixels.
+fn scale_element(mut element: DrawingElement, scale_x: f32, scale_y: f32) -> DrawingElement {
+    if (scale_x - 1.0).abs() < f32::EPSILON && (scale_y - 1.0).abs() < f32::EPSILON {
+        return element;
+    }
+    let scale = |v: i32, s: f32| (v as f32 * s).round() as i32;
+    for point in &mut element.points {
+        *point = Point::new(scale(point.x, scale_x), scale(point.y, scale_y));
+    }
+    let rect = element.rect;
+    element.rect = Rect::new(
+        scale(rect.left, scale_x),
+        scale(rect.top, scale_y),
+        scale(rect.right, scale_x),
+        scale(rect.bottom, scale_y),
+    );
+    element.thickness *= scale_x.max(scale_y);
+    element.font_size *= scale_y;
+    // Keep mosaic cells and blur strength the same size relative to the image.
+    let effect_scale = scale_x.max(scale_y);
+    element.set_block_size((element.block_size as f32 * effect_scale).round() as u32);
+    element.set_blur_radius(element.blur_radius * effect_scale);
+    element
+}
@@ -2,11 +2,11 @@ use std::sync::atomic::{AtomicBool, AtomicIsize};
 
 use crate::constants::{
     BUTTON_HEIGHT, BUTTON_SPACING, BUTTON_WIDTH, COLOR_BUTTON_WIDTH, COLOR_PREVIEW_HEIGHT,
-    COLOR_PREVIEW_WIDTH, CONTROL_HEIGHT, FONT_BUTTON_WIDTH, LABEL_CONTROL_GAP, LABEL_HEIGHT,
-    LABEL_WIDTH, LABEL_Y_OFFSET, MARGIN, OCR_LANGUAGE_DROPDOWN_HEIGHT, OCR_LANGUAGE_WIDTH,
-    PATH_BROWSE_BUTTON_WIDTH, PATH_BUTTON_GAP, ROW_HEIGHT, ROW_SPACING, SHORT_EDIT_WIDTH,
-    TAB_CONTENT_MARGIN, TAB_PAGE_HEIGHT_ADJUST, TAB_PAGE_WIDTH_ADJUST, TAB_PAGE_X, TAB_PAGE_Y,
-    WINDOW_DEFAULT_HEIGHT, WINDOW_DEFAULT_WIDTH,
+    COLOR_PREVIEW_WIDTH, COMBO_DROPDOWN_HEIGHT, COMBO_WIDTH, CONTROL_HEIGHT, FONT_BUTTON_WIDTH,
+    LABEL_CONTROL_GAP, LABEL_HEIGHT, LABEL_WIDTH, LABEL_Y_OFFSET, MARGIN,
+    OCR_LANGUAGE_DROPDOWN_HEIGHT, OCR_LANGUAGE_WIDTH, PATH_BROWSE_BUTTON_WIDTH, PATH_BUTTON_GAP,
+    ROW_HEIGHT, ROW_SPACING, SHORT_EDIT_WIDTH, TAB_CONTENT_MARGIN, TAB_PAGE_HEIGHT_ADJUST,
+    TAB_PAGE_WIDTH_ADJUST, TAB_PAGE_X, TAB_PAGE_Y, WINDOW_DEFAULT_HEIGHT, WINDOW_DEFAULT_WIDTH,
 };
 
 mod events;
@@ -27,7 +27,43 @@ const ID_HOTKEY_EDIT: i32 = 1008;
 const ID_CONFIG_PATH_EDIT: i32 = 1011;
 const ID_CONFIG_PATH_BROWSE: i32 = 1012;
 const ID_OCR_LANGUAGE_COMBO: i32 = 1013;
+const ID_FILL_COLOR_BUTTON: i32 = 1014;
+const ID_FILL_OPACITY_COMBO: i32 = 1015;
+const ID_STROKE_DASH_COMBO: i32 = 1016;
+const ID_STROKE_CAP_COMBO: i32 = 1017;
+const ID_STROKE_JOIN_COMBO: i32 = 1018;
+const ID_ARROW_HEAD_COMBO: i32 = 1019;
+const ID_ARROW_TAIL_COMBO: i32 = 1020;
 const ID_OK: i32 = 1009;
 const ID_CANCEL: i32 = 1010;
 
+/// Fill opacities offered for Ctrl+Shift+F: label and alpha (0..=255).
+const FILL_OPACITY_OPTIONS: [(&str, &str); 5] = [
+    ("20%", "51"),
+    ("30%", "77"),
+    ("50%", "128"),
+    ("70%", "179"),
+    ("100%", "255"),
+];
+
+/// Stroke style choices offered in the drawing tab: label and settings value.
+const STROKE_DASH_OPTIONS: [(&str, &str); 4] = [
+    ("实线", "solid"),
+    ("虚线", "dash"),
+    ("点线", "dot"),
+    ("点划线", "dash-dot"),
+];
+const STROKE_CAP_OPTIONS: [(&str, &str); 3] =
+    [("平头", "flat"), ("圆头", "round"), ("方头", "square")];
+const STROKE_JOIN_OPTIONS: [(&str, &str); 3] =
+    [("尖角", "miter"), ("圆角", "round"), ("斜角", "bevel")];
+/// Arrow head and tail markers.
+const ARROW_MARKER_OPTIONS: [(&str, &str); 5] = [
+    ("无", "none"),
+    ("线形", "open"),
+    ("三角", "triangle"),
+    ("圆点", "dot"),
+    ("短线", "bar"),
+];
+
 pub use window::SettingsWindow;
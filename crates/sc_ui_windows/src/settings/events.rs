@@ -106,6 +106,13 @@ impl SettingsWindowState {
                     return Some(LRESULT(self.drawing_color_brush.handle().0 as isize));
                 }
 
+                if control_hwnd == self.fill_color_preview && self.fill_color_brush.is_valid() {
+                    let (r, g, b) = self.settings.fill_color;
+                    let color = (r as u32) | ((g as u32) << 8) | ((b as u32) << 16);
+                    SetBkColor(hdc, COLORREF(color));
+                    return Some(LRESULT(self.fill_color_brush.handle().0 as isize));
+                }
+
                 if control_hwnd == self.text_color_preview && self.text_color_brush.is_valid() {
                     let color = (self.settings.text_color_red as u32)
                         | ((self.settings.text_color_green as u32) << 8)
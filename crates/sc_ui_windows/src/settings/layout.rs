@@ -6,10 +6,11 @@ use windows::Win32::UI::WindowsAndMessaging::*;
 use super::window::SettingsWindowState;
 use super::{
     BUTTON_HEIGHT, BUTTON_SPACING, BUTTON_WIDTH, COLOR_BUTTON_WIDTH, COLOR_PREVIEW_HEIGHT,
-    COLOR_PREVIEW_WIDTH, CONTROL_HEIGHT, FONT_BUTTON_WIDTH, LABEL_CONTROL_GAP, LABEL_HEIGHT,
-    LABEL_WIDTH, LABEL_Y_OFFSET, MARGIN, OCR_LANGUAGE_DROPDOWN_HEIGHT, OCR_LANGUAGE_WIDTH,
-    PATH_BROWSE_BUTTON_WIDTH, PATH_BUTTON_GAP, ROW_HEIGHT, ROW_SPACING, SHORT_EDIT_WIDTH,
-    TAB_CONTENT_MARGIN, TAB_PAGE_HEIGHT_ADJUST, TAB_PAGE_WIDTH_ADJUST, TAB_PAGE_X, TAB_PAGE_Y,
+    COLOR_PREVIEW_WIDTH, COMBO_DROPDOWN_HEIGHT, COMBO_WIDTH, CONTROL_HEIGHT, FONT_BUTTON_WIDTH,
+    LABEL_CONTROL_GAP, LABEL_HEIGHT, LABEL_WIDTH, LABEL_Y_OFFSET, MARGIN,
+    OCR_LANGUAGE_DROPDOWN_HEIGHT, OCR_LANGUAGE_WIDTH, PATH_BROWSE_BUTTON_WIDTH, PATH_BUTTON_GAP,
+    ROW_HEIGHT, ROW_SPACING, SHORT_EDIT_WIDTH, TAB_CONTENT_MARGIN, TAB_PAGE_HEIGHT_ADJUST,
+    TAB_PAGE_WIDTH_ADJUST, TAB_PAGE_X, TAB_PAGE_Y,
 };
 
 impl SettingsWindowState {
@@ -119,26 +120,61 @@ impl SettingsWindowState {
             );
             y += ROW_HEIGHT + ROW_SPACING;
 
-            Self::position_label(self.drawing_color_label, &metrics, y);
-            let _ = SetWindowPos(
-                self.drawing_color_preview,
-                None,
-                metrics.control_x,
-                y + (CONTROL_HEIGHT - COLOR_PREVIEW_HEIGHT) / 2,
-                COLOR_PREVIEW_WIDTH,
-                COLOR_PREVIEW_HEIGHT,
-                SWP_NOZORDER,
-            );
-            Self::position_control(
-                self.drawing_color_button,
-                metrics.control_x + COLOR_PREVIEW_WIDTH + PATH_BUTTON_GAP,
-                y,
-                COLOR_BUTTON_WIDTH.min(
-                    metrics
-                        .control_width
-                        .saturating_sub(COLOR_PREVIEW_WIDTH + PATH_BUTTON_GAP),
+            for (label, preview, button) in [
+                (
+                    self.drawing_color_label,
+                    self.drawing_color_preview,
+                    self.drawing_color_button,
                 ),
-            );
+                (
+                    self.fill_color_label,
+                    self.fill_color_preview,
+                    self.fill_color_button,
+                ),
+            ] {
+                Self::position_label(label, &metrics, y);
+                let _ = SetWindowPos(
+                    preview,
+                    None,
+                    metrics.control_x,
+                    y + (CONTROL_HEIGHT - COLOR_PREVIEW_HEIGHT) / 2,
+                    COLOR_PREVIEW_WIDTH,
+                    COLOR_PREVIEW_HEIGHT,
+                    SWP_NOZORDER,
+                );
+                Self::position_control(
+                    button,
+                    metrics.control_x + COLOR_PREVIEW_WIDTH + PATH_BUTTON_GAP,
+                    y,
+                    COLOR_BUTTON_WIDTH.min(
+                        metrics
+                            .control_width
+                            .saturating_sub(COLOR_PREVIEW_WIDTH + PATH_BUTTON_GAP),
+                    ),
+                );
+                y += ROW_HEIGHT + ROW_SPACING;
+            }
+
+            for (label, combo) in [
+                (self.fill_opacity_label, self.fill_opacity_combo),
+                (self.stroke_dash_label, self.stroke_dash_combo),
+                (self.stroke_cap_label, self.stroke_cap_combo),
+                (self.stroke_join_label, self.stroke_join_combo),
+                (self.arrow_head_label, self.arrow_head_combo),
+                (self.arrow_tail_label, self.arrow_tail_combo),
+            ] {
+                Self::position_label(label, &metrics, y);
+                let _ = SetWindowPos(
+                    combo,
+                    None,
+                    metrics.control_x,
+                    y,
+                    COMBO_WIDTH.min(metrics.control_width),
+                    COMBO_DROPDOWN_HEIGHT,
+                    SWP_NOZORDER,
+                );
+                y += ROW_HEIGHT + ROW_SPACING;
+            }
         }
     }
 
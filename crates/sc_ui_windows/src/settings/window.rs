@@ -17,9 +17,12 @@ use windows::Win32::UI::WindowsAndMessaging::*;
 use windows::core::{Error, HRESULT, PCWSTR};
 
 use super::{
-    BUTTON_HEIGHT, ID_CANCEL, ID_CONFIG_PATH_BROWSE, ID_CONFIG_PATH_EDIT, ID_DRAWING_COLOR_BUTTON,
-    ID_FONT_CHOOSE_BUTTON, ID_HOTKEY_EDIT, ID_LINE_THICKNESS, ID_OCR_LANGUAGE_COMBO, ID_OK, MARGIN,
-    WINDOW_DEFAULT_HEIGHT, WINDOW_DEFAULT_WIDTH,
+    ARROW_MARKER_OPTIONS, BUTTON_HEIGHT, FILL_OPACITY_OPTIONS, ID_ARROW_HEAD_COMBO,
+    ID_ARROW_TAIL_COMBO, ID_CANCEL, ID_CONFIG_PATH_BROWSE, ID_CONFIG_PATH_EDIT,
+    ID_DRAWING_COLOR_BUTTON, ID_FILL_COLOR_BUTTON, ID_FILL_OPACITY_COMBO, ID_FONT_CHOOSE_BUTTON,
+    ID_HOTKEY_EDIT, ID_LINE_THICKNESS, ID_OCR_LANGUAGE_COMBO, ID_OK, ID_STROKE_CAP_COMBO,
+    ID_STROKE_DASH_COMBO, ID_STROKE_JOIN_COMBO, MARGIN, STROKE_CAP_OPTIONS, STROKE_DASH_OPTIONS,
+    STROKE_JOIN_OPTIONS, WINDOW_DEFAULT_HEIGHT, WINDOW_DEFAULT_WIDTH,
 };
 
 /// Settings window.
@@ -73,13 +76,28 @@ pub(super) struct SettingsWindowState {
     pub(super) line_thickness_label: HWND,
     pub(super) font_label: HWND,
     pub(super) drawing_color_label: HWND,
+    pub(super) fill_color_label: HWND,
+    pub(super) fill_opacity_label: HWND,
+    pub(super) stroke_dash_label: HWND,
+    pub(super) stroke_cap_label: HWND,
+    pub(super) stroke_join_label: HWND,
+    pub(super) arrow_head_label: HWND,
+    pub(super) arrow_tail_label: HWND,
 
     // Drawing controls.
     pub(super) line_thickness_edit: HWND,
     pub(super) font_choose_button: HWND,
     pub(super) drawing_color_button: HWND,
     pub(super) drawing_color_preview: HWND,
+    pub(super) fill_color_button: HWND,
+    pub(super) fill_color_preview: HWND,
+    pub(super) fill_opacity_combo: HWND,
     pub(super) text_color_preview: HWND,
+    pub(super) stroke_dash_combo: HWND,
+    pub(super) stroke_cap_combo: HWND,
+    pub(super) stroke_join_combo: HWND,
+    pub(super) arrow_head_combo: HWND,
+    pub(super) arrow_tail_combo: HWND,
 
     // System labels.
     pub(super) hotkey_label: HWND,
@@ -99,6 +117,7 @@ pub(super) struct SettingsWindowState {
     // Resources.
     pub(super) font: HFONT,
     pub(super) drawing_color_brush: OwnedBrush,
+    pub(super) fill_color_brush: OwnedBrush,
     pub(super) text_color_brush: OwnedBrush,
 }
 
@@ -140,11 +159,26 @@ impl SettingsWindowState {
             line_thickness_label: HWND::default(),
             font_label: HWND::default(),
             drawing_color_label: HWND::default(),
+            fill_color_label: HWND::default(),
+            fill_opacity_label: HWND::default(),
+            stroke_dash_label: HWND::default(),
+            stroke_cap_label: HWND::default(),
+            stroke_join_label: HWND::default(),
+            arrow_head_label: HWND::default(),
+            arrow_tail_label: HWND::default(),
             line_thickness_edit: HWND::default(),
             font_choose_button: HWND::default(),
             drawing_color_button: HWND::default(),
             drawing_color_preview: HWND::default(),
+            fill_color_button: HWND::default(),
+            fill_color_preview: HWND::default(),
+            fill_opacity_combo: HWND::default(),
             text_color_preview: HWND::default(),
+            stroke_dash_combo: HWND::default(),
+            stroke_cap_combo: HWND::default(),
+            stroke_join_combo: HWND::default(),
+            arrow_head_combo: HWND::default(),
+            arrow_tail_combo: HWND::default(),
             hotkey_label: HWND::default(),
             config_path_label: HWND::default(),
             ocr_language_label: HWND::default(),
@@ -156,6 +190,7 @@ impl SettingsWindowState {
             cancel_button: HWND::default(),
             font: HFONT::default(),
             drawing_color_brush: OwnedBrush::default(),
+            fill_color_brush: OwnedBrush::default(),
             text_color_brush: OwnedBrush::default(),
         }
     }
@@ -339,6 +374,54 @@ impl SettingsWindowState {
                 instance,
             )?;
 
+            self.fill_color_label = self.create_label("填充颜色:", self.tab_drawing, instance)?;
+
+            self.fill_color_preview = self.create_static_preview(self.tab_drawing, instance)?;
+
+            self.fill_color_button = self.create_button(
+                "选择颜色...",
+                self.tab_drawing,
+                ID_FILL_COLOR_BUTTON,
+                instance,
+            )?;
+
+            self.fill_opacity_label =
+                self.create_label("填充透明度:", self.tab_drawing, instance)?;
+            self.fill_opacity_combo =
+                self.create_combo_box(self.tab_drawing, ID_FILL_OPACITY_COMBO, instance)?;
+            Self::set_modern_theme(self.fill_opacity_combo);
+            Self::add_combo_items(self.fill_opacity_combo, &FILL_OPACITY_OPTIONS);
+
+            self.stroke_dash_label = self.create_label("线条样式:", self.tab_drawing, instance)?;
+            self.stroke_dash_combo =
+                self.create_combo_box(self.tab_drawing, ID_STROKE_DASH_COMBO, instance)?;
+            Self::set_modern_theme(self.stroke_dash_combo);
+            Self::add_combo_items(self.stroke_dash_combo, &STROKE_DASH_OPTIONS);
+
+            self.stroke_cap_label = self.create_label("线条端点:", self.tab_drawing, instance)?;
+            self.stroke_cap_combo =
+                self.create_combo_box(self.tab_drawing, ID_STROKE_CAP_COMBO, instance)?;
+            Self::set_modern_theme(self.stroke_cap_combo);
+            Self::add_combo_items(self.stroke_cap_combo, &STROKE_CAP_OPTIONS);
+
+            self.stroke_join_label = self.create_label("线条拐角:", self.tab_drawing, instance)?;
+            self.stroke_join_combo =
+                self.create_combo_box(self.tab_drawing, ID_STROKE_JOIN_COMBO, instance)?;
+            Self::set_modern_theme(self.stroke_join_combo);
+            Self::add_combo_items(self.stroke_join_combo, &STROKE_JOIN_OPTIONS);
+
+            self.arrow_head_label = self.create_label("箭头样式:", self.tab_drawing, instance)?;
+            self.arrow_head_combo =
+                self.create_combo_box(self.tab_drawing, ID_ARROW_HEAD_COMBO, instance)?;
+            Self::set_modern_theme(self.arrow_head_combo);
+            Self::add_combo_items(self.arrow_head_combo, &ARROW_MARKER_OPTIONS);
+
+            self.arrow_tail_label = self.create_label("箭尾样式:", self.tab_drawing, instance)?;
+            self.arrow_tail_combo =
+                self.create_combo_box(self.tab_drawing, ID_ARROW_TAIL_COMBO, instance)?;
+            Self::set_modern_theme(self.arrow_tail_combo);
+            Self::add_combo_items(self.arrow_tail_combo, &ARROW_MARKER_OPTIONS);
+
             // System tab.
             self.hotkey_label = self.create_label("截图热键:", self.tab_system, instance)?;
 
@@ -524,6 +607,41 @@ impl SettingsWindowState {
         }
     }
 
+    /// Append the labels of `options` to a combo box, in order.
+    fn add_combo_items(combo: HWND, options: &[(&str, &str)]) {
+        for (label, _) in options {
+            let text = to_wide_chars(label);
+            unsafe {
+                SendMessageW(
+                    combo,
+                    CB_ADDSTRING,
+                    Some(WPARAM(0)),
+                    Some(LPARAM(text.as_ptr() as isize)),
+                );
+            }
+        }
+    }
+
+    /// Select the item whose settings value is `value`. Returns `false` if there is none.
+    fn select_combo_value(combo: HWND, options: &[(&str, &str)], value: &str) -> bool {
+        let Some(index) = options.iter().position(|(_, v)| *v == value) else {
+            return false;
+        };
+        unsafe {
+            SendMessageW(combo, CB_SETCURSEL, Some(WPARAM(index)), None);
+        }
+        true
+    }
+
+    /// Settings value of the selected item, or `None` if the selection is not one of `options`.
+    fn selected_combo_value<'a>(combo: HWND, options: &[(&str, &'a str)]) -> Option<&'a str> {
+        let index = unsafe { SendMessageW(combo, CB_GETCURSEL, None, None).0 };
+        if index == CB_ERR as isize {
+            return None;
+        }
+        options.get(index as usize).map(|(_, value)| *value)
+    }
+
     fn add_ocr_language_item(&self, display: &str, language_id: Option<&str>) {
         unsafe {
             let text = to_wide_chars(display);
@@ -673,6 +791,61 @@ impl SettingsWindowState {
             let config_path_text = to_wide_chars(&self.settings.config_path);
             let _ = SetWindowTextW(self.config_path_edit, PCWSTR(config_path_text.as_ptr()));
 
+            if !Self::select_combo_value(
+                self.fill_opacity_combo,
+                &FILL_OPACITY_OPTIONS,
+                &self.settings.fill_opacity.to_string(),
+            ) {
+                Self::select_combo_value(self.fill_opacity_combo, &FILL_OPACITY_OPTIONS, "77");
+            }
+            // Stroke style. A custom dash pattern from the settings file gets an extra item so
+            // that saving keeps it.
+            if !Self::select_combo_value(
+                self.stroke_dash_combo,
+                &STROKE_DASH_OPTIONS,
+                &self.settings.stroke_dash,
+            ) {
+                if SendMessageW(self.stroke_dash_combo, CB_GETCOUNT, None, None).0
+                    == STROKE_DASH_OPTIONS.len() as isize
+                {
+                    Self::add_combo_items(self.stroke_dash_combo, &[("自定义", "")]);
+                }
+                SendMessageW(
+                    self.stroke_dash_combo,
+                    CB_SETCURSEL,
+                    Some(WPARAM(STROKE_DASH_OPTIONS.len())),
+                    None,
+                );
+            }
+            if !Self::select_combo_value(
+                self.stroke_cap_combo,
+                &STROKE_CAP_OPTIONS,
+                &self.settings.stroke_cap,
+            ) {
+                SendMessageW(self.stroke_cap_combo, CB_SETCURSEL, Some(WPARAM(0)), None);
+            }
+            if !Self::select_combo_value(
+                self.stroke_join_combo,
+                &STROKE_JOIN_OPTIONS,
+                &self.settings.stroke_join,
+            ) {
+                SendMessageW(self.stroke_join_combo, CB_SETCURSEL, Some(WPARAM(0)), None);
+            }
+            if !Self::select_combo_value(
+                self.arrow_head_combo,
+                &ARROW_MARKER_OPTIONS,
+                &self.settings.arrow_head,
+            ) {
+                Self::select_combo_value(self.arrow_head_combo, &ARROW_MARKER_OPTIONS, "open");
+            }
+            if !Self::select_combo_value(
+                self.arrow_tail_combo,
+                &ARROW_MARKER_OPTIONS,
+                &self.settings.arrow_tail,
+            ) {
+                Self::select_combo_value(self.arrow_tail_combo, &ARROW_MARKER_OPTIONS, "none");
+            }
+
             // OCR language.
             let mut selected = false;
             for i in 0..self.ocr_language_item_count() {
@@ -721,6 +894,10 @@ impl SettingsWindowState {
                 self.show_drawing_color_dialog();
             }
 
+            ID_FILL_COLOR_BUTTON => {
+                self.show_fill_color_dialog();
+            }
+
             ID_CONFIG_PATH_BROWSE => {
                 self.show_folder_browser_dialog();
             }
@@ -773,6 +950,14 @@ impl SettingsWindowState {
         }
     }
 
+    fn show_fill_color_dialog(&mut self) {
+        if let Some(color) = file_dialog::show_color_dialog(self.hwnd, self.settings.fill_color) {
+            self.settings.fill_color = color;
+            self.update_color_brushes();
+            self.update_color_preview();
+        }
+    }
+
     fn update_color_brushes(&mut self) {
         unsafe {
             let drawing_color = (self.settings.drawing_color_red as u32)
@@ -781,6 +966,11 @@ impl SettingsWindowState {
             self.drawing_color_brush
                 .reset(CreateSolidBrush(COLORREF(drawing_color)));
 
+            let (r, g, b) = self.settings.fill_color;
+            let fill_color = (r as u32) | ((g as u32) << 8) | ((b as u32) << 16);
+            self.fill_color_brush
+                .reset(CreateSolidBrush(COLORREF(fill_color)));
+
             let text_color = (self.settings.text_color_red as u32)
                 | ((self.settings.text_color_green as u32) << 8)
                 | ((self.settings.text_color_blue as u32) << 16);
@@ -792,6 +982,7 @@ impl SettingsWindowState {
     fn update_color_preview(&self) {
         let platform = WindowsHostPlatform::new();
         let _ = platform.request_redraw_erase(to_window_id(self.drawing_color_preview));
+        let _ = platform.request_redraw_erase(to_window_id(self.fill_color_preview));
         let _ = platform.request_redraw_erase(to_window_id(self.text_color_preview));
     }
 
@@ -817,6 +1008,38 @@ impl SettingsWindowState {
                 self.settings.config_path = config_path_text;
             }
 
+            if let Some(value) =
+                Self::selected_combo_value(self.fill_opacity_combo, &FILL_OPACITY_OPTIONS)
+                && let Ok(opacity) = value.parse::<u8>()
+            {
+                self.settings.fill_opacity = opacity;
+            }
+            if let Some(value) =
+                Self::selected_combo_value(self.stroke_dash_combo, &STROKE_DASH_OPTIONS)
+            {
+                self.settings.stroke_dash = value.to_string();
+            }
+            if let Some(value) =
+                Self::selected_combo_value(self.stroke_cap_combo, &STROKE_CAP_OPTIONS)
+            {
+                self.settings.stroke_cap = value.to_string();
+            }
+            if let Some(value) =
+                Self::selected_combo_value(self.stroke_join_combo, &STROKE_JOIN_OPTIONS)
+            {
+                self.settings.stroke_join = value.to_string();
+            }
+            if let Some(value) =
+                Self::selected_combo_value(self.arrow_head_combo, &ARROW_MARKER_OPTIONS)
+            {
+                self.settings.arrow_head = value.to_string();
+            }
+            if let Some(value) =
+                Self::selected_combo_value(self.arrow_tail_combo, &ARROW_MARKER_OPTIONS)
+            {
+                self.settings.arrow_tail = value.to_string();
+            }
+
             let selected_index = SendMessageW(self.ocr_language_combo, CB_GETCURSEL, None, None).0;
             if selected_index != CB_ERR as isize
                 && let Some(value) = self.ocr_language_value_at(selected_index as usize)
@@ -852,6 +1075,7 @@ impl SettingsWindowState {
             self.clear_ocr_language_items();
             Self::unsubclass_hotkey_edit(self.hotkey_edit);
             self.drawing_color_brush.clear();
+            self.fill_color_brush.clear();
             self.text_color_brush.clear();
         }
     }
@@ -36,7 +36,12 @@ impl SvgIconManager {
             ToolbarButton::Rectangle,
             ToolbarButton::Circle,
             ToolbarButton::Pen,
+            ToolbarButton::Highlighter,
             ToolbarButton::Text,
+            ToolbarButton::Mosaic,
+            ToolbarButton::Blur,
+            ToolbarButton::Step,
+            ToolbarButton::Callout,
             ToolbarButton::Undo,
             ToolbarButton::ExtractText,
             ToolbarButton::ScrollCapture,
@@ -125,7 +125,12 @@ impl ToolbarManager {
             | ToolbarButton::Circle
             | ToolbarButton::Arrow
             | ToolbarButton::Pen
-            | ToolbarButton::Text => {
+            | ToolbarButton::Highlighter
+            | ToolbarButton::Text
+            | ToolbarButton::Mosaic
+            | ToolbarButton::Blur
+            | ToolbarButton::Step
+            | ToolbarButton::Callout => {
                 self.clicked_button = button;
             }
             _ => {}
@@ -147,9 +152,24 @@ impl ToolbarManager {
             ToolbarButton::Pen => vec![Command::Core(sc_app::Action::SelectDrawingTool(
                 DrawingTool::Pen,
             ))],
+            ToolbarButton::Highlighter => vec![Command::Core(sc_app::Action::SelectDrawingTool(
+                DrawingTool::Highlighter,
+            ))],
             ToolbarButton::Text => vec![Command::Core(sc_app::Action::SelectDrawingTool(
                 DrawingTool::Text,
             ))],
+            ToolbarButton::Mosaic => vec![Command::Core(sc_app::Action::SelectDrawingTool(
+                DrawingTool::Mosaic,
+            ))],
+            ToolbarButton::Blur => vec![Command::Core(sc_app::Action::SelectDrawingTool(
+                DrawingTool::Blur,
+            ))],
+            ToolbarButton::Step => vec![Command::Core(sc_app::Action::SelectDrawingTool(
+                DrawingTool::Step,
+            ))],
+            ToolbarButton::Callout => vec![Command::Core(sc_app::Action::SelectDrawingTool(
+                DrawingTool::Callout,
+            ))],
             ToolbarButton::Undo => vec![Command::Core(sc_app::Action::Undo)],
             ToolbarButton::ExtractText => vec![Command::Core(sc_app::Action::ExtractText)],
             ToolbarButton::ScrollCapture => vec![Command::StartScrollingCapture],